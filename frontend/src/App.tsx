@@ -1,9 +1,10 @@
 import React, { useState, useEffect } from 'react';
-import { View, Image, Group } from './types';
+import { View, Image, Group, TagSuggestion } from './types';
 import { useAuth } from './hooks/useAuth';
 import { useData } from './hooks/useData';
 import { imagesAPI, tagsAPI } from './services/api';
-import { Login, Sidebar, Header, ImageModal, ErrorBoundary } from './components';
+import { isOpenSuggestion } from './utils/helpers';
+import { Login, Sidebar, Header, ImageModal, RejectReasonModal, ErrorBoundary } from './components';
 import { Dashboard, Gallery, Upload, Groups, Tags, TagReview, GroupDetail } from './components/views';
 import Chat from './components/views/Chat';
 
@@ -32,6 +33,7 @@ const App: React.FC = () => {
   const [showImageModal, setShowImageModal] = useState(false);
   const [suggestingTag, setSuggestingTag] = useState(false);
   const [selectedGroupDetail, setSelectedGroupDetail] = useState<Group | null>(null);
+  const [rejectingTag, setRejectingTag] = useState<{ suggestion: TagSuggestion; done: () => void } | null>(null);
 
   // Update selectedGroupDetail when groups are updated
   useEffect(() => {
@@ -152,13 +154,32 @@ const App: React.FC = () => {
     }
   };
 
-  const handleRejectTag = async (suggestionId: string) => {
-    if (!user || user.role !== 'admin') return;
+  // Opens the reason picker; resolves once the suggestion is rejected or the picker is closed
+  const handleRejectTag = (suggestionId: string): Promise<void> => {
+    const suggestion = tagSuggestions.find(sug => sug.id === suggestionId);
+    if (!user || user.role !== 'admin' || !suggestion) return Promise.resolve();
+
+    return new Promise(resolve => setRejectingTag({ suggestion, done: resolve }));
+  };
+
+  const closeRejectPicker = () => {
+    rejectingTag?.done();
+    setRejectingTag(null);
+  };
+
+  const rejectReasonsFor = (suggestion: TagSuggestion): string[] => {
+    const image = images.find(img => img.id === suggestion.image_id);
+    return groups.find(g => g.id === image?.group_id)?.reject_reasons ?? [];
+  };
+
+  const handleConfirmReject = async (reason: string) => {
+    if (!user || !rejectingTag) return;
 
     try {
-      const response = await tagsAPI.review(suggestionId, {
+      const response = await tagsAPI.review(rejectingTag.suggestion.id, {
         status: 'rejected',
-        reviewed_by: user.username
+        reviewed_by: user.username,
+        reason
       });
 
       if (response.success) {
@@ -170,6 +191,8 @@ const App: React.FC = () => {
     } catch (error: unknown) {
       console.error('Error rejecting tag:', error);
       setError('Failed to reject tag');
+    } finally {
+      closeRejectPicker();
     }
   };
 
@@ -327,7 +350,7 @@ const App: React.FC = () => {
     return <Login onLogin={login} loading={false} error={authError} />;
   }
 
-  const pendingSuggestionsCount = tagSuggestions.filter(sug => sug.status === 'pending').length;
+  const pendingSuggestionsCount = tagSuggestions.filter(isOpenSuggestion).length;
 
   return (
     <ErrorBoundary>
@@ -373,6 +396,16 @@ const App: React.FC = () => {
             suggestingTag={suggestingTag}
           />
                     )}
+
+        {/* Reject reason picker */}
+        {rejectingTag && (
+          <RejectReasonModal
+            suggestion={rejectingTag.suggestion}
+            reasons={rejectReasonsFor(rejectingTag.suggestion)}
+            onConfirm={handleConfirmReject}
+            onCancel={closeRejectPicker}
+          />
+        )}
                   </div>
     </ErrorBoundary>
   );
@@ -1,6 +1,7 @@
 import React from 'react';
 import { Image, Group, ApprovedTag, TagSuggestion, User } from '../types';
 import { UPLOADS_BASE_URL } from '../config';
+import { isOpenSuggestion } from '../utils/helpers';
 
 interface ImageCardProps {
   image: Image;
@@ -67,10 +68,10 @@ const ImageCard: React.FC<ImageCardProps> = ({
             )}
           </div>
         )}
-        {imageSuggestions.filter(s => s.status === 'pending').length > 0 && (
+        {imageSuggestions.filter(isOpenSuggestion).length > 0 && (
           <div className="pending-tags">
             <span className="pending-count">
-              {imageSuggestions.filter(s => s.status === 'pending').length} pending
+              {imageSuggestions.filter(isOpenSuggestion).length} pending
             </span>
           </div>
         )}
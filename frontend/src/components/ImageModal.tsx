@@ -3,6 +3,7 @@ import axios from 'axios';
 import { Image, Group, ApprovedTag, TagSuggestion, TagUpvote, User } from '../types';
 import { aiAPI, tagsAPI } from '../services/api';
 import { UPLOADS_BASE_URL } from '../config';
+import { isOpenSuggestion } from '../utils/helpers';
 
 interface ImageModalProps {
   image: Image | null;
@@ -65,7 +66,7 @@ const ImageModal: React.FC<ImageModalProps> = ({
   const group = groups.find(g => g.id === image.group_id);
   const imageApprovedTags = approvedTags.filter(tag => tag.image_id === image.id);
   const imagePendingSuggestions = tagSuggestions.filter(sug => 
-    sug.image_id === image.id && sug.status === 'pending'
+    sug.image_id === image.id && isOpenSuggestion(sug)
   );
   const imageRejectedSuggestions = tagSuggestions.filter(sug => 
     sug.image_id === image.id && sug.status === 'rejected'
@@ -103,6 +104,7 @@ const ImageModal: React.FC<ImageModalProps> = ({
         pending_tags: pendingTagsList,
         image_name: image.original_name,
         image_url: `${UPLOADS_BASE_URL}/${image.filename}`,
+        group_id: image.group_id,
       };
       
       const response = await aiAPI.generateTagSuggestion(suggestionPayload);
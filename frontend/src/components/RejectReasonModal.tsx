@@ -0,0 +1,85 @@
+import React, { useState } from 'react';
+import { TagSuggestion } from '../types';
+
+interface RejectReasonModalProps {
+  suggestion: TagSuggestion;
+  reasons: string[];
+  onConfirm: (reason: string) => Promise<void>;
+  onCancel: () => void;
+}
+
+// Reason codes are snake_case on the backend: "too_vague" → "Too vague"
+const formatReason = (reason: string): string => {
+  const text = reason.replace(/_/g, ' ');
+  return text.charAt(0).toUpperCase() + text.slice(1);
+};
+
+const RejectReasonModal: React.FC<RejectReasonModalProps> = ({
+  suggestion,
+  reasons,
+  onConfirm,
+  onCancel
+}) => {
+  const [reason, setReason] = useState(reasons[0] ?? '');
+  const [rejecting, setRejecting] = useState(false);
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!reason) return;
+
+    setRejecting(true);
+    try {
+      await onConfirm(reason);
+    } finally {
+      setRejecting(false);
+    }
+  };
+
+  return (
+    <div className="modal-overlay" onClick={onCancel}>
+      <div className="modal-content-new" onClick={(e) => e.stopPropagation()}>
+        <button
+          className="modal-close"
+          onClick={onCancel}
+        >
+          ×
+        </button>
+
+        <div className="create-group-section">
+          <h3>Reject "{suggestion.tag}"</h3>
+          <form onSubmit={handleSubmit}>
+            <div className="form-group">
+              <label className="form-label">Reason</label>
+              {reasons.length > 0 ? (
+                <select
+                  className="form-input"
+                  value={reason}
+                  onChange={(e) => setReason(e.target.value)}
+                  required
+                >
+                  {reasons.map(code => (
+                    <option key={code} value={code}>{formatReason(code)}</option>
+                  ))}
+                </select>
+              ) : (
+                <p>This group has no reject reasons configured.</p>
+              )}
+            </div>
+
+            <div className="modal-save-section">
+              <button
+                type="submit"
+                className="save-changes-button"
+                disabled={rejecting || !reason}
+              >
+                {rejecting ? 'Rejecting...' : 'Reject'}
+              </button>
+            </div>
+          </form>
+        </div>
+      </div>
+    </div>
+  );
+};
+
+export default RejectReasonModal;
@@ -4,5 +4,6 @@ export { default as Header } from './Header';
 export { default as ImageCard } from './ImageCard';
 export { default as GroupCard } from './GroupCard';
 export { default as ImageModal } from './ImageModal';
+export { default as RejectReasonModal } from './RejectReasonModal';
 export { default as ErrorBoundary } from './ErrorBoundary';
 
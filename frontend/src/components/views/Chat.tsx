@@ -3,6 +3,7 @@ import ReactMarkdown from 'react-markdown';
 import remarkGfm from 'remark-gfm';
 import { Group, Image, TagSuggestion, ApprovedTag } from '../../types';
 import { chatAPI, ChatRequest } from '../../services/chat';
+import { isOpenSuggestion } from '../../utils/helpers';
 
 interface ChatProps {
   groups: Group[];
@@ -51,7 +52,7 @@ const Chat: React.FC<ChatProps> = ({
     const totalImages = images.length;
     const totalGroups = groups.length;
     const totalTags = approvedTags.length;
-    const pendingSuggestions = tagSuggestions.filter(s => s.status === 'pending').length;
+    const pendingSuggestions = tagSuggestions.filter(isOpenSuggestion).length;
     
     const groupStats = groups.map(group => ({
       name: group.name,
@@ -1,5 +1,6 @@
 import React, { memo, useMemo } from 'react';
 import { User, Image, Group, TagSuggestion, ApprovedTag, View } from '../../types';
+import { isOpenSuggestion } from '../../utils/helpers';
 
 interface DashboardProps {
   user: User;
@@ -25,7 +26,7 @@ const Dashboard: React.FC<DashboardProps> = memo(({
     const totalImages = images.length;
     const totalGroups = groups.length;
     const totalUsers = availableUsers.length;
-    const pendingSuggestionsCount = tagSuggestions.filter(sug => sug.status === 'pending').length;
+    const pendingSuggestionsCount = tagSuggestions.filter(isOpenSuggestion).length;
     const approvedTagsCount = approvedTags.length;
     return {
       totalImages,
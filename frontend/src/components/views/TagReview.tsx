@@ -1,6 +1,7 @@
 import React, { useState, useEffect } from 'react';
 import { TagSuggestion, Image, Group, User } from '../../types';
 import { UPLOADS_BASE_URL } from '../../config';
+import { isOpenSuggestion } from '../../utils/helpers';
 
 interface TagReviewProps {
   tagSuggestions: TagSuggestion[];
@@ -24,7 +25,7 @@ const TagReview: React.FC<TagReviewProps> = ({
   const [currentReviewIndex, setCurrentReviewIndex] = useState(0);
   const [reviewingTag, setReviewingTag] = useState(false);
 
-  const pendingSuggestionsForReview = tagSuggestions.filter(sug => sug.status === 'pending');
+  const pendingSuggestionsForReview = tagSuggestions.filter(isOpenSuggestion);
   const currentSuggestion = pendingSuggestionsForReview[currentReviewIndex];
   const currentImage = currentSuggestion ? images.find(img => img.id === currentSuggestion.image_id) : null;
 
@@ -43,7 +44,7 @@ const TagReview: React.FC<TagReviewProps> = ({
       await onApproveTag(suggestionId);
       // Move to next suggestion, but don't go beyond the array
       setCurrentReviewIndex(prev => {
-        const pendingSuggestions = tagSuggestions.filter(sug => sug.status === 'pending');
+        const pendingSuggestions = tagSuggestions.filter(isOpenSuggestion);
         return Math.min(prev + 1, pendingSuggestions.length - 1);
       });
     } catch (error) {
@@ -61,7 +62,7 @@ const TagReview: React.FC<TagReviewProps> = ({
       await onRejectTag(suggestionId);
       // Move to next suggestion, but don't go beyond the array
       setCurrentReviewIndex(prev => {
-        const pendingSuggestions = tagSuggestions.filter(sug => sug.status === 'pending');
+        const pendingSuggestions = tagSuggestions.filter(isOpenSuggestion);
         return Math.min(prev + 1, pendingSuggestions.length - 1);
       });
     } catch (error) {
@@ -73,7 +74,7 @@ const TagReview: React.FC<TagReviewProps> = ({
 
   const handleSkip = () => {
     setCurrentReviewIndex(prev => {
-      const pendingSuggestions = tagSuggestions.filter(sug => sug.status === 'pending');
+      const pendingSuggestions = tagSuggestions.filter(isOpenSuggestion);
       return Math.min(prev + 1, pendingSuggestions.length - 1);
     });
   };
@@ -137,6 +138,9 @@ const TagReview: React.FC<TagReviewProps> = ({
               {groups.find(g => g.id === currentImage.group_id)?.name || 'Unknown'}
             </div>
             <div className="tag-modern">{currentSuggestion.tag}</div>
+            {currentSuggestion.status === 'reopened' && (
+              <div className="group-info-discrete">Reopened</div>
+            )}
           </div>
         </div>
 
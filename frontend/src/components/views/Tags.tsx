@@ -1,6 +1,7 @@
 import React from 'react';
 import { TagSuggestion, ApprovedTag, User, Image, Group } from '../../types';
 import { UPLOADS_BASE_URL } from '../../config';
+import { isOpenSuggestion } from '../../utils/helpers';
 
 interface TagsProps {
   tagSuggestions: TagSuggestion[];
@@ -25,7 +26,7 @@ const Tags: React.FC<TagsProps> = ({
   onUpvoteTag,
   onImageClick
 }) => {
-  const pendingSuggestions = tagSuggestions.filter(sug => sug.status === 'pending');
+  const pendingSuggestions = tagSuggestions.filter(isOpenSuggestion);
   const rejectedSuggestions = tagSuggestions.filter(sug => sug.status === 'rejected');
 
   return (
@@ -93,7 +93,7 @@ export const tagsAPI = {
   getImageTags: (imageId: string): Promise<{ tags: TagSuggestion[] }> =>
     api.get(`/images/${imageId}/tags`).then(res => res.data),
   
-  review: (tagId: string, data: { status: string; reviewed_by: string }): Promise<{ success: boolean }> =>
+  review: (tagId: string, data: { status: string; reviewed_by: string; reason?: string }): Promise<{ success: boolean }> =>
     api.put(`/tags/${tagId}`, data).then(res => res.data),
   
   upvote: (tagId: string, data: { user_id: string }): Promise<{ success: boolean }> =>
@@ -139,6 +139,7 @@ export const aiAPI = {
     pending_tags: string[];
     image_name: string;
     image_url: string;
+    group_id?: string;
   }): Promise<{ success: boolean; suggestion?: string; error?: string }> =>
     api.post('/ai/tag-suggestions', data).then(res => res.data),
 };
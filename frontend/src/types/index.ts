@@ -19,15 +19,18 @@ export interface Group {
   members: string[];
   created_at: string;
   created_by: string;
+  reject_reasons: string[];
 }
 
+export type SuggestionStatus = 'pending' | 'approved' | 'rejected' | 'reopened' | 'superseded';
+
 export interface TagSuggestion {
   id: string;
   image_id: string;
   tag: string;
   suggested_by: string;
   suggested_at: string;
-  status: 'pending' | 'approved' | 'rejected';
+  status: SuggestionStatus;
   reviewed_by: string | null;
   reviewed_at: string | null;
 }
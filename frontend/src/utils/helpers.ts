@@ -104,6 +104,11 @@ export const getImageSuggestions = (imageId: string, tagSuggestions: TagSuggesti
   return tagSuggestions.filter(suggestion => suggestion.image_id === imageId);
 };
 
+// Same rule as SuggestionStatus::is_open on the backend: pending and reopened suggestions await review
+export const isOpenSuggestion = (suggestion: TagSuggestion): boolean => {
+  return suggestion.status === 'pending' || suggestion.status === 'reopened';
+};
+
 export const generateId = (): string => {
   return Math.random().toString(36).substr(2, 9);
 };
@@ -0,0 +1,50 @@
+use actix_web::{web, HttpResponse, Result};
+use serde_json;
+use crate::models::BatchImageRequest;
+use crate::services::{DataService, UserService};
+use crate::services::batch_service::BatchOperation;
+
+pub async fn batch_images(
+    req: web::Json<BatchImageRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    println!("📦 Batch '{}' on {} images by '{}'", req.operation, req.image_ids.len(), req.performed_by);
+
+    let operation = match BatchOperation::from_request(&req) {
+        Ok(operation) => operation,
+        Err(e) => {
+            println!("❌ Invalid batch request: {}", e);
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "success": false,
+                "error": e
+            })));
+        }
+    };
+    if req.image_ids.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "success": false,
+            "error": "No images selected"
+        })));
+    }
+
+    let is_admin = user_service.is_admin(&req.performed_by);
+    let mut data = data_service.lock().unwrap();
+    let outcome = data.run_batch(&operation, &req.image_ids, &req.performed_by, is_admin);
+
+    if outcome.committed {
+        println!("✅ Batch '{}' applied to {} images", req.operation, outcome.results.len());
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "results": outcome.results,
+            "message": "Batch operation completed successfully"
+        })))
+    } else {
+        println!("❌ Batch '{}' rolled back", req.operation);
+        Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "success": false,
+            "results": outcome.results,
+            "error": "Batch operation failed and was rolled back"
+        })))
+    }
+}
@@ -0,0 +1,204 @@
+use actix_web::{web, HttpResponse, Result};
+use serde_json;
+use crate::models::{CaptionListQuery, EditCaptionRequest, ReviewAnnotationRequest, SuggestCaptionRequest, UpdateCaptionPolicyRequest};
+use crate::services::{DataService, UserService};
+use crate::services::caption_service::CaptionError;
+
+fn caption_error_response(error: &CaptionError) -> HttpResponse {
+    let body = serde_json::json!({
+        "success": false,
+        "error": error.to_string()
+    });
+    match error {
+        CaptionError::ImageNotFound | CaptionError::GroupNotFound | CaptionError::CaptionNotFound => {
+            HttpResponse::NotFound().json(body)
+        }
+        CaptionError::Forbidden => HttpResponse::Forbidden().json(body),
+        CaptionError::Invalid(_) => HttpResponse::BadRequest().json(body),
+    }
+}
+
+pub async fn update_caption_policy(
+    path: web::Path<String>,
+    req: web::Json<UpdateCaptionPolicyRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("📝 Updating caption policy of group '{}' by '{}'", group_id, req.updated_by);
+    if !user_service.is_admin(&req.updated_by) {
+        println!("❌ User '{}' is not allowed to change caption policies", req.updated_by);
+        return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+            "success": false,
+            "error": "Admin access required"
+        })));
+    }
+
+    let mut data = data_service.lock().unwrap();
+    match data.set_caption_policy(&group_id, req.policy.clone()) {
+        Ok(()) => {
+            let _ = data.save_to_json();
+            println!("✅ Caption policy of group '{}' updated successfully", group_id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "message": "Caption policy updated successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to update caption policy of group '{}': {}", group_id, e);
+            Ok(caption_error_response(&e))
+        }
+    }
+}
+
+pub async fn suggest_caption(
+    path: web::Path<String>,
+    req: web::Json<SuggestCaptionRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let image_id = path.into_inner();
+    println!("📝 Suggesting caption for image '{}' by user '{}'", image_id, req.suggested_by);
+    let mut data = data_service.lock().unwrap();
+
+    match data.suggest_caption(&image_id, &req) {
+        Ok(caption) => {
+            let _ = data.save_to_json();
+            println!("✅ Caption created successfully (ID: {})", caption.id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "caption": caption,
+                "message": "Caption created successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to suggest caption: {}", e);
+            Ok(caption_error_response(&e))
+        }
+    }
+}
+
+pub async fn get_image_captions(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let image_id = path.into_inner();
+    println!("🔍 Fetching captions for image '{}'", image_id);
+    let data = data_service.lock().unwrap();
+
+    let Some(image) = data.get_image(&image_id) else {
+        return Ok(caption_error_response(&CaptionError::ImageNotFound));
+    };
+    let policy = data.get_group(&image.group_id).map(|group| &group.caption_policy);
+    let query = CaptionListQuery { image_id: Some(image_id.clone()), group_id: None, status: None, language: None };
+    let captions = data.list_captions(&query);
+
+    println!("✅ Found {} captions for image '{}'", captions.len(), image_id);
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "image_id": image_id,
+        "policy": policy,
+        "captions": captions
+    })))
+}
+
+pub async fn list_captions(
+    query: web::Query<CaptionListQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    println!("🔍 Listing captions (image: {:?}, group: {:?}, status: {:?}, language: {:?})",
+             query.image_id, query.group_id, query.status, query.language);
+    let data = data_service.lock().unwrap();
+    let captions = data.list_captions(&query);
+    println!("✅ Found {} captions", captions.len());
+    Ok(HttpResponse::Ok().json(captions))
+}
+
+pub async fn get_caption(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let caption_id = path.into_inner();
+    println!("🔍 Fetching caption '{}'", caption_id);
+    let data = data_service.lock().unwrap();
+
+    match data.captions.get(&caption_id) {
+        Some(caption) => Ok(HttpResponse::Ok().json(caption)),
+        None => {
+            println!("❌ Caption '{}' not found", caption_id);
+            Ok(caption_error_response(&CaptionError::CaptionNotFound))
+        }
+    }
+}
+
+pub async fn edit_caption(
+    path: web::Path<String>,
+    req: web::Json<EditCaptionRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let caption_id = path.into_inner();
+    println!("✏️ Editing caption '{}' by '{}'", caption_id, req.edited_by);
+    let mut data = data_service.lock().unwrap();
+
+    match data.edit_caption(&caption_id, &req, user_service.is_admin(&req.edited_by)) {
+        Ok(caption) => {
+            let _ = data.save_to_json();
+            println!("✅ Caption '{}' edited ({} previous versions)", caption.id, caption.history.len());
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "caption": caption,
+                "message": "Caption edited successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to edit caption '{}': {}", caption_id, e);
+            Ok(caption_error_response(&e))
+        }
+    }
+}
+
+pub async fn review_caption(
+    path: web::Path<String>,
+    req: web::Json<ReviewAnnotationRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let caption_id = path.into_inner();
+    println!("👀 Reviewing caption '{}' as '{}' by '{}'", caption_id, req.status, req.reviewed_by);
+    let mut data = data_service.lock().unwrap();
+
+    match data.review_caption(&caption_id, &req.status, &req.reviewed_by) {
+        Ok(caption) => {
+            let _ = data.save_to_json();
+            println!("✅ Caption '{}' marked as {}", caption.id, caption.status);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "caption": caption,
+                "message": "Caption reviewed successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to review caption '{}': {}", caption_id, e);
+            Ok(caption_error_response(&e))
+        }
+    }
+}
+
+pub async fn delete_caption(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let caption_id = path.into_inner();
+    println!("🗑️ Removing caption '{}'", caption_id);
+    let mut data = data_service.lock().unwrap();
+
+    if data.captions.remove(&caption_id).is_some() {
+        let _ = data.save_to_json();
+        println!("✅ Caption '{}' removed successfully", caption_id);
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": "Caption removed successfully"
+        })))
+    } else {
+        println!("❌ Caption '{}' not found", caption_id);
+        Ok(caption_error_response(&CaptionError::CaptionNotFound))
+    }
+}
@@ -0,0 +1,251 @@
+use actix_web::{web, HttpResponse, Result};
+use serde_json;
+use crate::models::{AdminQuery, CommentTarget, EditCommentRequest, PostCommentRequest, ResolveThreadRequest};
+use crate::services::{DataService, UserService};
+use crate::services::comment_service::CommentError;
+
+fn comment_error_response(error: &CommentError) -> HttpResponse {
+    let body = serde_json::json!({
+        "success": false,
+        "error": error.to_string()
+    });
+    match error {
+        CommentError::ImageNotFound | CommentError::TagNotFound | CommentError::CommentNotFound => HttpResponse::NotFound().json(body),
+        CommentError::Forbidden(_) => HttpResponse::Forbidden().json(body),
+        CommentError::Invalid(_) => HttpResponse::BadRequest().json(body),
+    }
+}
+
+pub async fn comment_on_image(
+    path: web::Path<String>,
+    req: web::Json<PostCommentRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let image_id = path.into_inner();
+    println!("💬 New thread on image '{}' by user '{}'", image_id, req.author);
+    let mut data = data_service.lock().unwrap();
+
+    match data.start_thread(&image_id, CommentTarget::Image, &image_id, &req.body, &req.author) {
+        Ok(comment) => {
+            let _ = data.save_to_json();
+            println!("✅ Comment created (ID: {}, mentions: {:?})", comment.id, comment.mentions);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "comment": comment,
+                "message": "Comment created successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to comment on image '{}': {}", image_id, e);
+            Ok(comment_error_response(&e))
+        }
+    }
+}
+
+pub async fn comment_on_tag(
+    path: web::Path<String>,
+    req: web::Json<PostCommentRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let tag_id = path.into_inner();
+    println!("💬 New thread on tag '{}' by user '{}'", tag_id, req.author);
+    let mut data = data_service.lock().unwrap();
+
+    let result = data.resolve_tag_target(&tag_id)
+        .and_then(|(target, image_id)| data.start_thread(&image_id, target, &tag_id, &req.body, &req.author));
+    match result {
+        Ok(comment) => {
+            let _ = data.save_to_json();
+            println!("✅ Comment created (ID: {}, mentions: {:?})", comment.id, comment.mentions);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "comment": comment,
+                "message": "Comment created successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to comment on tag '{}': {}", tag_id, e);
+            Ok(comment_error_response(&e))
+        }
+    }
+}
+
+pub async fn get_image_comments(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let image_id = path.into_inner();
+    println!("🔍 Fetching comment threads for image '{}'", image_id);
+    let data = data_service.lock().unwrap();
+
+    if data.get_image(&image_id).is_none() {
+        return Ok(comment_error_response(&CommentError::ImageNotFound));
+    }
+    let threads = data.image_threads(&image_id);
+    println!("✅ Found {} threads for image '{}'", threads.len(), image_id);
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "image_id": image_id,
+        "threads": threads
+    })))
+}
+
+pub async fn get_tag_comments(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let tag_id = path.into_inner();
+    println!("🔍 Fetching comment threads for tag '{}'", tag_id);
+    let data = data_service.lock().unwrap();
+
+    let threads = data.target_threads(&tag_id);
+    println!("✅ Found {} threads for tag '{}'", threads.len(), tag_id);
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "tag_id": tag_id,
+        "threads": threads
+    })))
+}
+
+pub async fn reply_to_comment(
+    path: web::Path<String>,
+    req: web::Json<PostCommentRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let comment_id = path.into_inner();
+    println!("↩️ Replying to comment '{}' by user '{}'", comment_id, req.author);
+    let mut data = data_service.lock().unwrap();
+
+    match data.reply_to_comment(&comment_id, &req.body, &req.author) {
+        Ok(reply) => {
+            let _ = data.save_to_json();
+            println!("✅ Reply created (ID: {}, thread: {:?})", reply.id, reply.parent_id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "comment": reply,
+                "message": "Reply created successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to reply to comment '{}': {}", comment_id, e);
+            Ok(comment_error_response(&e))
+        }
+    }
+}
+
+pub async fn edit_comment(
+    path: web::Path<String>,
+    req: web::Json<EditCommentRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let comment_id = path.into_inner();
+    println!("✏️ Editing comment '{}' by user '{}'", comment_id, req.edited_by);
+    let mut data = data_service.lock().unwrap();
+
+    match data.edit_comment(&comment_id, &req.body, &req.edited_by) {
+        Ok(comment) => {
+            let _ = data.save_to_json();
+            println!("✅ Comment '{}' updated", comment.id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "comment": comment,
+                "message": "Comment updated successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to edit comment '{}': {}", comment_id, e);
+            Ok(comment_error_response(&e))
+        }
+    }
+}
+
+pub async fn resolve_thread(
+    path: web::Path<String>,
+    req: web::Json<ResolveThreadRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let comment_id = path.into_inner();
+    println!("📌 Setting thread of comment '{}' resolved={} by '{}'", comment_id, req.resolved, req.resolved_by);
+    let is_admin = user_service.is_admin(&req.resolved_by);
+    let mut data = data_service.lock().unwrap();
+
+    match data.resolve_thread(&comment_id, req.resolved, &req.resolved_by, is_admin) {
+        Ok(thread) => {
+            let _ = data.save_to_json();
+            println!("✅ Thread '{}' is now {}", thread.id, if thread.resolved { "resolved" } else { "open" });
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "comment": thread,
+                "message": "Thread updated successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to update thread of comment '{}': {}", comment_id, e);
+            Ok(comment_error_response(&e))
+        }
+    }
+}
+
+pub async fn delete_comment(
+    path: web::Path<String>,
+    query: web::Query<AdminQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let comment_id = path.into_inner();
+    println!("🗑️ Removing comment '{}' by '{}'", comment_id, query.requested_by);
+    let is_admin = user_service.is_admin(&query.requested_by);
+    let mut data = data_service.lock().unwrap();
+
+    match data.delete_comment(&comment_id, &query.requested_by, is_admin) {
+        Ok(removed) => {
+            let _ = data.save_to_json();
+            println!("✅ Comment '{}' removed ({} comments in total)", comment_id, removed);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "removed": removed,
+                "message": "Comment removed successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to remove comment '{}': {}", comment_id, e);
+            Ok(comment_error_response(&e))
+        }
+    }
+}
+
+pub async fn get_unresolved_threads(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("🔍 Fetching unresolved threads of group '{}'", group_id);
+    let data = data_service.lock().unwrap();
+
+    if data.get_group(&group_id).is_none() {
+        return Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "success": false,
+            "error": "Group not found"
+        })));
+    }
+    let threads = data.unresolved_threads(&group_id);
+    println!("✅ Found {} unresolved threads in group '{}'", threads.len(), group_id);
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "group_id": group_id,
+        "threads": threads
+    })))
+}
+
+pub async fn get_user_mentions(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    println!("🔔 Fetching mentions of user '{}'", username);
+    let data = data_service.lock().unwrap();
+    let comments = data.user_mentions(&username);
+    println!("✅ Found {} mentions of '{}'", comments.len(), username);
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "username": username,
+        "comments": comments
+    })))
+}
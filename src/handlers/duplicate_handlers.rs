@@ -0,0 +1,101 @@
+use actix_web::{web, HttpResponse, Result};
+use serde_json;
+use crate::models::{DuplicatesQuery, MergeDuplicatesRequest};
+use crate::services::{DataService, MergeError, UserService};
+use crate::services::ingest_service::remove_stored_file;
+use crate::services::phash_service::DEFAULT_MAX_DISTANCE;
+
+pub async fn get_duplicate_clusters(
+    query: web::Query<DuplicatesQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let max_distance = query.max_distance.unwrap_or(DEFAULT_MAX_DISTANCE);
+    println!("🔍 Searching near-duplicate clusters (group: {:?}, max distance: {})",
+             query.group_id, max_distance);
+    let data = data_service.lock().unwrap();
+
+    if let Some(group_id) = &query.group_id {
+        if data.get_group(group_id).is_none() {
+            println!("❌ Group '{}' not found", group_id);
+            return Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "success": false,
+                "error": "Group not found"
+            })));
+        }
+    }
+
+    let clusters: Vec<serde_json::Value> = data.duplicate_clusters(query.group_id.as_deref(), max_distance)
+        .into_iter()
+        .map(|members| {
+            let images: Vec<serde_json::Value> = members.iter()
+                .filter_map(|(image_id, distance)| {
+                    let image = data.get_image(image_id)?;
+                    Some(serde_json::json!({
+                        "image": image,
+                        "distance": distance
+                    }))
+                })
+                .collect();
+            serde_json::json!({ "images": images })
+        })
+        .collect();
+
+    println!("✅ Found {} near-duplicate clusters", clusters.len());
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "max_distance": max_distance,
+        "clusters": clusters
+    })))
+}
+
+pub async fn merge_duplicates(
+    req: web::Json<MergeDuplicatesRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    println!("🧬 Merging {} images into '{}' by '{}'",
+             req.image_ids.len(), req.keep_image_id, req.merged_by);
+    if !user_service.is_admin(&req.merged_by) {
+        println!("❌ User '{}' is not allowed to merge images", req.merged_by);
+        return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+            "success": false,
+            "error": "Admin access required"
+        })));
+    }
+
+    let max_distance = req.max_distance.unwrap_or(DEFAULT_MAX_DISTANCE);
+    let mut data = data_service.lock().unwrap();
+    let checkpoint = data.history_checkpoint([req.keep_image_id.as_str()]);
+    match data.merge_duplicates(&req.keep_image_id, &req.image_ids, max_distance, req.supersede) {
+        Ok(result) => {
+            data.record_history(checkpoint, &req.merged_by);
+            let _ = data.save_to_json();
+            for filename in &result.removed_files {
+                remove_stored_file(filename);
+            }
+            println!("✅ Merged {} images into '{}' ({} tags moved, {} tags merged)",
+                     result.removed_image_ids.len(), result.kept_image_id, result.moved_tags, result.merged_tags);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "result": result,
+                "message": "Images merged successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to merge images into '{}': {}", req.keep_image_id, e);
+            let mut body = serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            });
+            Ok(match e {
+                MergeError::ImageNotFound(_) => HttpResponse::NotFound().json(body),
+                MergeError::Invalid(_) => HttpResponse::BadRequest().json(body),
+                MergeError::ExclusiveConflict(conflicts) => {
+                    body["conflicting_tags"] = conflicts.iter()
+                        .map(|(id, tag)| serde_json::json!({ "id": id, "tag": tag }))
+                        .collect();
+                    HttpResponse::Conflict().json(body)
+                }
+            })
+        }
+    }
+}
@@ -1,11 +1,44 @@
 use actix_web::{web, HttpResponse, Result};
+use crate::models::ExportQuery;
 use crate::services::DataService;
 
 pub async fn export_annotations(
+    query: web::Query<ExportQuery>,
     data_service: web::Data<std::sync::Mutex<DataService>>,
 ) -> Result<HttpResponse> {
     let data = data_service.lock().unwrap();
-    let export = data.export_annotations();
+
+    // Filtro opcional pela linguagem de consulta de tags
+    let image_ids = match query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        Some(q) => match data.search_image_ids(q) {
+            Ok(ids) => Some(ids),
+            Err(e) => {
+                println!("❌ Invalid export query '{}': {}", q, e);
+                return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                    "success": false,
+                    "error": format!("Invalid query: {}", e)
+                })));
+            }
+        },
+        None => None,
+    };
+    let label_depth = match (query.labels.as_deref().unwrap_or("leaf"), query.depth) {
+        ("leaf", _) => None,
+        ("depth", Some(depth)) => Some(depth),
+        ("depth", None) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "success": false,
+                "error": "labels=depth requires a depth parameter"
+            })));
+        }
+        (other, _) => {
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "success": false,
+                "error": format!("Invalid labels mode '{}'; use 'leaf' or 'depth'", other)
+            })));
+        }
+    };
+    let export = data.export_annotations(image_ids.as_ref(), label_depth);
 
     let json = serde_json::to_string_pretty(&export).unwrap_or_else(|_| "{}".to_string());
 
@@ -14,4 +47,3 @@ pub async fn export_annotations(
         .append_header(("Content-Disposition", "attachment; filename=annotations_export.json"))
         .body(json))
 }
-
@@ -1,7 +1,8 @@
 use actix_web::{web, HttpResponse, Result};
 use serde_json;
-use crate::models::{CreateGroupRequest, AddUserToGroupRequest, UpdateGroupRequest, Group};
-use crate::services::DataService;
+use crate::models::{CreateGroupRequest, AddUserToGroupRequest, UpdateGroupRequest, UpdateMetadataPolicyRequest, UpdateLabelingPolicyRequest, UpdateRejectReasonsRequest, UpdateConsensusPolicyRequest, UpdateReviewQuorumRequest, Group};
+use crate::services::{DataService, UserService};
+use crate::services::tag_review_service::ReviewError;
 
 pub async fn get_groups(
     data_service: web::Data<std::sync::Mutex<DataService>>,
@@ -167,5 +168,240 @@ pub async fn delete_group(
     }
 }
 
+pub async fn update_metadata_policy(
+    path: web::Path<String>,
+    req: web::Json<UpdateMetadataPolicyRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("🛡️ Updating metadata policy of group '{}' by '{}'", group_id, req.updated_by);
+    if !user_service.is_admin(&req.updated_by) {
+        println!("❌ User '{}' is not allowed to change metadata policies", req.updated_by);
+        return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+            "success": false,
+            "error": "Admin access required"
+        })));
+    }
+
+    let mut data = data_service.lock().unwrap();
+    if let Some(group) = data.get_group_mut(&group_id) {
+        group.metadata_policy = req.policy.clone();
+        let _ = data.save_to_json();
+        println!("✅ Metadata policy of group '{}' updated successfully", group_id);
+
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": "Metadata policy updated successfully"
+        })))
+    } else {
+        println!("❌ Group '{}' not found", group_id);
+        Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "success": false,
+            "error": "Group not found"
+        })))
+    }
+}
+
+pub async fn update_labeling_policy(
+    path: web::Path<String>,
+    req: web::Json<UpdateLabelingPolicyRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("🎯 Updating labeling mode of group '{}' by '{}'", group_id, req.updated_by);
+    if !user_service.is_admin(&req.updated_by) {
+        println!("❌ User '{}' is not allowed to change labeling modes", req.updated_by);
+        return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+            "success": false,
+            "error": "Admin access required"
+        })));
+    }
+
+    let mut data = data_service.lock().unwrap();
+    if let Some(group) = data.get_group_mut(&group_id) {
+        group.labeling = req.policy;
+        let _ = data.save_to_json();
+        println!("✅ Labeling mode of group '{}' updated successfully", group_id);
+
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": "Labeling mode updated successfully"
+        })))
+    } else {
+        println!("❌ Group '{}' not found", group_id);
+        Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "success": false,
+            "error": "Group not found"
+        })))
+    }
+}
+
+pub async fn get_labeling_completeness(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("📋 Checking labeling completeness of group '{}'", group_id);
+    let data = data_service.lock().unwrap();
 
+    if let Some(report) = data.labeling_completeness(&group_id) {
+        println!("✅ Group '{}': {} unlabeled, {} with several classes", group_id, report.unlabeled.len(), report.multiple_labels.len());
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "completeness": report
+        })))
+    } else {
+        println!("❌ Group '{}' not found", group_id);
+        Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "success": false,
+            "error": "Group not found"
+        })))
+    }
+}
+
+pub async fn update_reject_reasons(
+    path: web::Path<String>,
+    req: web::Json<UpdateRejectReasonsRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("🚫 Updating reject reasons of group '{}' by '{}'", group_id, req.updated_by);
+    if !user_service.is_admin(&req.updated_by) {
+        println!("❌ User '{}' is not allowed to change reject reasons", req.updated_by);
+        return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+            "success": false,
+            "error": "Admin access required"
+        })));
+    }
 
+    let mut data = data_service.lock().unwrap();
+    match data.set_reject_reasons(&group_id, &req.reasons) {
+        Ok(reasons) => {
+            let _ = data.save_to_json();
+            println!("✅ Reject reasons of group '{}' set to {:?}", group_id, reasons);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "reasons": reasons,
+                "message": "Reject reasons updated successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to update reject reasons of group '{}': {}", group_id, e);
+            let body = serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            });
+            Ok(match e {
+                ReviewError::GroupNotFound => HttpResponse::NotFound().json(body),
+                _ => HttpResponse::BadRequest().json(body),
+            })
+        }
+    }
+}
+
+pub async fn update_consensus_policy(
+    path: web::Path<String>,
+    req: web::Json<UpdateConsensusPolicyRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("🤝 Updating consensus policy of group '{}' by '{}': {:?}", group_id, req.updated_by, req.policy);
+    if !user_service.is_admin(&req.updated_by) {
+        println!("❌ User '{}' is not allowed to change the consensus policy", req.updated_by);
+        return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+            "success": false,
+            "error": "Admin access required"
+        })));
+    }
+
+    let mut data = data_service.lock().unwrap();
+    match data.set_consensus_policy(&group_id, req.policy) {
+        Ok(()) => {
+            let _ = data.save_to_json();
+            println!("✅ Consensus policy of group '{}' updated", group_id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "policy": req.policy,
+                "message": "Consensus policy updated successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to update consensus policy of group '{}': {}", group_id, e);
+            let body = serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            });
+            Ok(match e {
+                ReviewError::GroupNotFound => HttpResponse::NotFound().json(body),
+                _ => HttpResponse::BadRequest().json(body),
+            })
+        }
+    }
+}
+
+pub async fn update_review_quorum(
+    path: web::Path<String>,
+    req: web::Json<UpdateReviewQuorumRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("🗳️ Updating review quorum of group '{}' by '{}': {:?}", group_id, req.updated_by, req.quorum);
+    if !user_service.is_admin(&req.updated_by) {
+        println!("❌ User '{}' is not allowed to change the review quorum", req.updated_by);
+        return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+            "success": false,
+            "error": "Admin access required"
+        })));
+    }
+
+    let mut data = data_service.lock().unwrap();
+    match data.set_review_quorum(&group_id, &req.quorum) {
+        Ok(quorum) => {
+            let _ = data.save_to_json();
+            println!("✅ Group '{}' now requires {} approval(s)", group_id, quorum.approvals_required);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "quorum": quorum,
+                "message": "Review quorum updated successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to update review quorum of group '{}': {}", group_id, e);
+            let body = serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            });
+            Ok(match e {
+                ReviewError::GroupNotFound => HttpResponse::NotFound().json(body),
+                _ => HttpResponse::BadRequest().json(body),
+            })
+        }
+    }
+}
+
+pub async fn get_reject_stats(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("📊 Aggregating reject reasons of group '{}'", group_id);
+    let data = data_service.lock().unwrap();
+
+    match data.reject_stats(&group_id) {
+        Some(report) => {
+            println!("✅ {} annotators with suggestions in group '{}'", report.annotators.len(), group_id);
+            Ok(HttpResponse::Ok().json(report))
+        }
+        None => {
+            println!("❌ Group '{}' not found", group_id);
+            Ok(HttpResponse::NotFound().json(serde_json::json!({
+                "success": false,
+                "error": "Group not found"
+            })))
+        }
+    }
+}
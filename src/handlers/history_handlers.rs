@@ -0,0 +1,71 @@
+use actix_web::{web, HttpResponse, Result};
+use serde_json;
+use crate::models::{HistoryQuery, StateAtQuery};
+use crate::services::DataService;
+use crate::services::history_service::parse_timestamp;
+
+fn invalid_timestamp(error: String) -> HttpResponse {
+    HttpResponse::BadRequest().json(serde_json::json!({
+        "success": false,
+        "error": error
+    }))
+}
+
+fn image_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(serde_json::json!({
+        "success": false,
+        "error": "Image not found"
+    }))
+}
+
+pub async fn get_image_history(
+    path: web::Path<String>,
+    query: web::Query<HistoryQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let image_id = path.into_inner();
+    println!("📜 Fetching annotation history of image '{}' (since: {:?}, until: {:?})", image_id, query.since, query.until);
+    let data = data_service.lock().unwrap();
+
+    if data.get_image(&image_id).is_none() {
+        return Ok(image_not_found());
+    }
+    let since = match query.since.as_deref().map(parse_timestamp).transpose() {
+        Ok(since) => since,
+        Err(e) => return Ok(invalid_timestamp(e)),
+    };
+    let until = match query.until.as_deref().map(parse_timestamp).transpose() {
+        Ok(until) => until,
+        Err(e) => return Ok(invalid_timestamp(e)),
+    };
+
+    let events = data.image_history(&image_id, since, until);
+    println!("✅ Found {} history events for image '{}'", events.len(), image_id);
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "image_id": image_id,
+        "events": events
+    })))
+}
+
+pub async fn get_image_state_at(
+    path: web::Path<String>,
+    query: web::Query<StateAtQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let image_id = path.into_inner();
+    println!("⏪ Reconstructing annotations of image '{}' at {}", image_id, query.at);
+    let data = data_service.lock().unwrap();
+
+    if data.get_image(&image_id).is_none() {
+        return Ok(image_not_found());
+    }
+    let at = match parse_timestamp(&query.at) {
+        Ok(at) => at,
+        Err(e) => return Ok(invalid_timestamp(e)),
+    };
+
+    let state = data.image_state_at(&image_id, at);
+    println!("✅ Image '{}' had {} suggestions and {} approved tags at {}",
+             image_id, state.tag_suggestions.len(), state.approved_tags.len(), state.at);
+    Ok(HttpResponse::Ok().json(state))
+}
@@ -1,9 +1,12 @@
 use actix_web::{web, HttpResponse, Result};
 use actix_multipart::Multipart;
-use std::io::Write;
 use serde_json;
-use crate::models::Image;
-use crate::services::DataService;
+use crate::models::{AdminQuery, AttributeMap, ImageListQuery};
+use crate::services::{DataService, UserService};
+use crate::services::{archive_service, exif_service, ingest_service};
+use crate::services::ingest_service::IngestError;
+use crate::services::phash_service::DEFAULT_MAX_DISTANCE;
+use crate::services::vocabulary_service::LabelOutcome;
 use futures_util::TryStreamExt;
 
 pub async fn upload_image(
@@ -11,7 +14,6 @@ pub async fn upload_image(
     data_service: web::Data<std::sync::Mutex<DataService>>,
 ) -> Result<HttpResponse> {
     println!("📤 Starting image upload process");
-    let mut filename = String::new();
     let mut original_name = String::new();
     let mut group_id = String::new();
     let mut uploaded_by = String::new();
@@ -23,10 +25,6 @@ pub async fn upload_image(
                 let content_disposition = item.content_disposition();
                 if let Some(name) = content_disposition.get_filename() {
                     original_name = name.to_string();
-                    filename = format!("{}_{}", 
-                        chrono::Utc::now().timestamp_millis(),
-                        name.replace(" ", "_")
-                    );
                 }
                 
                 let mut bytes = Vec::new();
@@ -56,59 +54,237 @@ pub async fn upload_image(
         }
     }
 
-    if filename.is_empty() || group_id.is_empty() || uploaded_by.is_empty() {
+    if original_name.is_empty() || group_id.is_empty() || uploaded_by.is_empty() {
         return Ok(HttpResponse::BadRequest().json(serde_json::json!({
             "success": false,
             "error": "Missing required fields"
         })));
     }
 
-    // Validate file type
-    let allowed_extensions = ["jpg", "jpeg", "png", "gif", "webp", "jfif", "bmp", "tiff"];
-    let file_extension = filename.split('.').last().unwrap_or("").to_lowercase();
+    let mut data = data_service.lock().unwrap();
+    let image_id = match ingest_service::ingest_image(&mut data, &group_id, &original_name, &file_data, &uploaded_by) {
+        Ok(image_id) => image_id,
+        Err(e) => {
+            println!("❌ Upload of '{}' failed: {}", original_name, e);
+            return Ok(ingest_error_response(&e));
+        }
+    };
+    let _ = data.save_to_json();
     
-    if !allowed_extensions.contains(&file_extension.as_str()) {
+    println!("✅ Image '{}' uploaded successfully by '{}' to group '{}' (ID: {})", 
+             original_name, uploaded_by, group_id, image_id);
+
+    // Avisar sobre imagens quase idênticas já existentes
+    let near_duplicates: Vec<serde_json::Value> = data.find_near_duplicates(&image_id, DEFAULT_MAX_DISTANCE)
+        .into_iter()
+        .map(|(image, distance)| serde_json::json!({
+            "image_id": image.id,
+            "original_name": image.original_name,
+            "group_id": image.group_id,
+            "distance": distance
+        }))
+        .collect();
+    if !near_duplicates.is_empty() {
+        println!("⚠️ Image '{}' has {} near-duplicates", image_id, near_duplicates.len());
+    }
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "image_id": image_id,
+        "near_duplicates": near_duplicates,
+        "message": "Image uploaded successfully"
+    })))
+}
+
+fn ingest_error_response(error: &IngestError) -> HttpResponse {
+    let body = serde_json::json!({
+        "success": false,
+        "error": error.to_string()
+    });
+    match error {
+        IngestError::UnsupportedType => HttpResponse::BadRequest().json(body),
+        IngestError::GroupNotFound | IngestError::ImageNotFound => HttpResponse::NotFound().json(body),
+        IngestError::Processing(_) => HttpResponse::UnprocessableEntity().json(body),
+        IngestError::Storage(_) => HttpResponse::InternalServerError().json(body),
+    }
+}
+
+pub async fn upload_archive(
+    mut payload: Multipart,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    println!("📦 Starting archive upload process");
+    let mut archive_name = String::new();
+    let mut group_id = String::new();
+    let mut uploaded_by = String::new();
+    let mut folder_tags = false;
+    let mut archive_data = Vec::new();
+
+    loop {
+        let item = match payload.try_next().await {
+            Ok(Some(item)) => item,
+            Ok(None) => break,
+            Err(e) => return Ok(invalid_multipart(e)),
+        };
+        match item.name() {
+            "archive" => {
+                if let Some(name) = item.content_disposition().get_filename() {
+                    archive_name = name.to_string();
+                }
+
+                let mut stream = item;
+                loop {
+                    let chunk = match stream.try_next().await {
+                        Ok(Some(chunk)) => chunk,
+                        Ok(None) => break,
+                        Err(e) => return Ok(invalid_multipart(e)),
+                    };
+                    if archive_data.len() + chunk.len() > archive_service::MAX_ARCHIVE_BYTES {
+                        return Ok(HttpResponse::PayloadTooLarge().json(serde_json::json!({
+                            "success": false,
+                            "error": format!("Archive exceeds {} bytes", archive_service::MAX_ARCHIVE_BYTES)
+                        })));
+                    }
+                    archive_data.extend_from_slice(&chunk);
+                }
+            }
+            "group_id" | "uploaded_by" | "folder_tags" => {
+                let field = item.name().to_string();
+                let mut bytes = Vec::new();
+                let mut stream = item;
+                loop {
+                    let chunk = match stream.try_next().await {
+                        Ok(Some(chunk)) => chunk,
+                        Ok(None) => break,
+                        Err(e) => return Ok(invalid_multipart(e)),
+                    };
+                    if bytes.len() + chunk.len() > 1024 {
+                        return Ok(invalid_multipart(format!("field '{}' is too long", field)));
+                    }
+                    bytes.extend_from_slice(&chunk);
+                }
+                let value = String::from_utf8(bytes).unwrap_or_default();
+                match field.as_str() {
+                    "group_id" => group_id = value,
+                    "uploaded_by" => uploaded_by = value,
+                    _ => folder_tags = value.trim() == "true",
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if archive_name.is_empty() || group_id.is_empty() || uploaded_by.is_empty() {
         return Ok(HttpResponse::BadRequest().json(serde_json::json!({
             "success": false,
-            "error": format!("Unsupported file type. Supported formats: {}", allowed_extensions.join(", "))
+            "error": "Missing required fields"
         })));
     }
 
-    // Save file to uploads directory
-    let file_path = format!("uploads/{}", filename);
-    if let Ok(mut file) = std::fs::File::create(&file_path) {
-        if let Err(_) = file.write_all(&file_data) {
-            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
-                "success": false,
-                "error": "Failed to save file"
-            })));
-        }
-    } else {
-        return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+    let Some(kind) = archive_service::ArchiveKind::from_file_name(&archive_name) else {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "success": false,
+            "error": "Unsupported archive type. Supported formats: zip, tar, tar.gz, tgz"
+        })));
+    };
+
+    if user_service.get_user(&uploaded_by).is_none() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
             "success": false,
-            "error": "Failed to create file"
+            "error": "Unknown user"
         })));
     }
 
-    // Create image record
-    let image = Image::new(
-        filename.clone(),
-        original_name.clone(),
-        group_id.clone(),
-        uploaded_by.clone(),
-    );
+    let policy = data_service.lock().unwrap().get_group(&group_id).map(|group| group.metadata_policy.clone());
+    let Some(policy) = policy else {
+        println!("❌ Group '{}' not found", group_id);
+        return Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "success": false,
+            "error": "Group not found"
+        })));
+    };
+
+    // Cada entrada é processada e gravada assim que sai do arquivo, sem o lock, e seus bytes são descartados;
+    // o lock só é tomado depois para registrar as imagens
+    let mut staged = Vec::new();
+    let extracted = archive_service::extract_archive(kind, &archive_data, |entry| {
+        let stored = ingest_service::store_image_file(&entry.file_name, &entry.data, &policy);
+        staged.push((entry.path, entry.file_name, entry.folder, stored));
+    });
+    drop(archive_data);
+    let skipped_entries = match extracted {
+        Ok(skipped) => skipped,
+        Err(e) => {
+            for (_, _, _, stored) in &staged {
+                if let Ok(stored) = stored {
+                    ingest_service::remove_stored_file(&stored.filename);
+                }
+            }
+            println!("❌ Archive '{}' rejected: {}", archive_name, e);
+            return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "success": false,
+                "error": e
+            })));
+        }
+    };
+
+    let mut report = Vec::new();
+    for skipped in &skipped_entries {
+        report.push(serde_json::json!({
+            "path": skipped.path,
+            "status": "skipped",
+            "error": skipped.reason
+        }));
+    }
 
+    let mut imported = 0;
     let mut data = data_service.lock().unwrap();
-    let image_id = data.create_image(image);
+    for (path, file_name, folder, stored) in staged {
+        let inserted = stored.and_then(|stored| ingest_service::insert_stored_image(&mut data, &group_id, &file_name, stored, &uploaded_by));
+        let image_id = match inserted {
+            Ok(image_id) => image_id,
+            Err(e) => {
+                report.push(serde_json::json!({
+                    "path": path,
+                    "status": "failed",
+                    "error": e.to_string()
+                }));
+                continue;
+            }
+        };
+
+        let mut suggested_tags = Vec::new();
+        let mut proposed_labels = Vec::new();
+        if folder_tags {
+            if let Some(folder) = folder.as_ref().map(|f| f.trim()).filter(|f| !f.is_empty()) {
+                // Pastas fora do vocabulário do grupo viram propostas ou são ignoradas
+                match data.submit_tag_suggestion(&image_id, folder, &AttributeMap::new(), &uploaded_by) {
+                    Ok(LabelOutcome::Suggested(suggestion)) => suggested_tags.push(suggestion.tag),
+                    Ok(LabelOutcome::Proposed(proposal)) => proposed_labels.push(proposal.label),
+                    Err(e) => println!("⚠️ Folder tag '{}' not suggested: {}", folder, e),
+                }
+            }
+        }
+
+        imported += 1;
+        report.push(serde_json::json!({
+            "path": path,
+            "status": "imported",
+            "image_id": image_id,
+            "suggested_tags": suggested_tags,
+            "proposed_labels": proposed_labels
+        }));
+    }
     let _ = data.save_to_json();
-    
-    println!("✅ Image '{}' uploaded successfully by '{}' to group '{}' (ID: {})", 
-             original_name, uploaded_by, group_id, image_id);
+
+    println!("✅ Archive '{}' imported {} images into group '{}' ({} entries reported)",
+             archive_name, imported, group_id, report.len());
 
     Ok(HttpResponse::Ok().json(serde_json::json!({
         "success": true,
-        "image_id": image_id,
-        "message": "Image uploaded successfully"
+        "imported": imported,
+        "report": report
     })))
 }
 
@@ -134,6 +310,229 @@ pub async fn get_image(
     }
 }
 
+pub async fn list_images(
+    query: web::Query<ImageListQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    println!("🖼️ Listing images with filters: {:?}", query);
+    let data = data_service.lock().unwrap();
+
+    match data.list_images(&query) {
+        Ok(page) => {
+            println!("✅ Retrieved {} of {} matching images", page.images.len(), page.total);
+            Ok(HttpResponse::Ok().json(page))
+        }
+        Err(e) => {
+            println!("❌ Invalid image listing query: {}", e);
+            Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "success": false,
+                "error": e
+            })))
+        }
+    }
+}
+
+pub async fn search_images(
+    query: web::Query<ImageListQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let Some(q) = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) else {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "success": false,
+            "error": "Missing search query"
+        })));
+    };
+    println!("🔎 Searching images with query: {}", q);
+    let data = data_service.lock().unwrap();
+
+    match data.list_images(&query) {
+        Ok(page) => {
+            println!("✅ Search matched {} images", page.total);
+            Ok(HttpResponse::Ok().json(page))
+        }
+        Err(e) => {
+            println!("❌ Invalid search query: {}", e);
+            Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "success": false,
+                "error": e
+            })))
+        }
+    }
+}
+
+fn invalid_multipart(error: impl std::fmt::Display) -> HttpResponse {
+    HttpResponse::BadRequest().json(serde_json::json!({
+        "success": false,
+        "error": format!("Invalid multipart body: {}", error)
+    }))
+}
+
+pub async fn replace_image_file(
+    path: web::Path<String>,
+    mut payload: Multipart,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let image_id = path.into_inner();
+    println!("🔄 Replacing file of image: {}", image_id);
+    let mut original_name = String::new();
+    let mut replaced_by = String::new();
+    let mut flag_for_review = false;
+    let mut file_data = Vec::new();
+
+    loop {
+        let item = match payload.try_next().await {
+            Ok(Some(item)) => item,
+            Ok(None) => break,
+            Err(e) => return Ok(invalid_multipart(e)),
+        };
+        let field = item.name().to_string();
+        if field == "image" {
+            if let Some(name) = item.content_disposition().get_filename() {
+                original_name = name.to_string();
+            }
+        }
+
+        let mut bytes = Vec::new();
+        let mut stream = item;
+        loop {
+            let chunk = match stream.try_next().await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => break,
+                Err(e) => return Ok(invalid_multipart(e)),
+            };
+            if bytes.len() + chunk.len() > ingest_service::MAX_IMAGE_BYTES {
+                return Ok(HttpResponse::PayloadTooLarge().json(serde_json::json!({
+                    "success": false,
+                    "error": format!("Field '{}' exceeds {} bytes", field, ingest_service::MAX_IMAGE_BYTES)
+                })));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        match field.as_str() {
+            "image" => file_data = bytes,
+            "replaced_by" => replaced_by = String::from_utf8(bytes).unwrap_or_default(),
+            "flag_for_review" => flag_for_review = String::from_utf8(bytes).unwrap_or_default().trim() == "true",
+            _ => {}
+        }
+    }
+
+    if original_name.is_empty() || replaced_by.is_empty() {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "success": false,
+            "error": "Missing required fields"
+        })));
+    }
+
+    // O arquivo é processado fora do lock; com ele só se troca o registro da imagem
+    let policy = {
+        let data = data_service.lock().unwrap();
+        match data.get_image(&image_id) {
+            Some(image) => data.get_group(&image.group_id).map(|group| group.metadata_policy.clone()).unwrap_or_default(),
+            None => return Ok(ingest_error_response(&IngestError::ImageNotFound)),
+        }
+    };
+    let stored = match ingest_service::store_image_file(&original_name, &file_data, &policy) {
+        Ok(stored) => stored,
+        Err(e) => {
+            println!("❌ Replacing image '{}' failed: {}", image_id, e);
+            return Ok(ingest_error_response(&e));
+        }
+    };
+
+    let mut data = data_service.lock().unwrap();
+    match ingest_service::replace_image(&mut data, &image_id, &original_name, stored, &replaced_by, flag_for_review) {
+        Ok((previous, flagged)) => {
+            let _ = data.save_to_json();
+            println!("✅ Image '{}' replaced by '{}' (previous file kept as '{}')",
+                     image_id, replaced_by, previous.filename);
+            if flagged.out_of_bounds > 0 {
+                println!("⚠️ {} annotations of image '{}' fall outside the new dimensions", flagged.out_of_bounds, image_id);
+            }
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "image": data.get_image(&image_id),
+                "flagged_for_review": flag_for_review,
+                "flagged": flagged,
+                "message": "Image file replaced successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Replacing image '{}' failed: {}", image_id, e);
+            Ok(ingest_error_response(&e))
+        }
+    }
+}
+
+pub async fn get_image_versions(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let image_id = path.into_inner();
+    println!("🗂️ Fetching versions of image: {}", image_id);
+    let data = data_service.lock().unwrap();
+
+    if let Some(image) = data.get_image(&image_id) {
+        println!("✅ Retrieved {} previous versions of image '{}'", image.versions.len(), image_id);
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "current": {
+                "filename": image.filename,
+                "original_name": image.original_name
+            },
+            "versions": image.versions
+        })))
+    } else {
+        println!("❌ Image '{}' not found", image_id);
+        Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "success": false,
+            "error": "Image not found"
+        })))
+    }
+}
+
+pub async fn get_image_metadata(
+    path: web::Path<String>,
+    query: web::Query<AdminQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let image_id = path.into_inner();
+    println!("🛡️ Fetching restricted metadata of image '{}' for '{}'", image_id, query.requested_by);
+    if !user_service.is_admin(&query.requested_by) {
+        println!("❌ User '{}' is not allowed to view restricted metadata", query.requested_by);
+        return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+            "success": false,
+            "error": "Admin access required"
+        })));
+    }
+
+    let data = data_service.lock().unwrap();
+    let Some(image) = data.get_image(&image_id) else {
+        println!("❌ Image '{}' not found", image_id);
+        return Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "success": false,
+            "error": "Image not found"
+        })));
+    };
+
+    let metadata = exif_service::load_restricted_metadata(&image.filename);
+    let versions: Vec<serde_json::Value> = image.versions.iter()
+        .filter_map(|version| {
+            let metadata = exif_service::load_restricted_metadata(&version.filename)?;
+            Some(serde_json::json!({
+                "filename": version.filename,
+                "metadata": metadata
+            }))
+        })
+        .collect();
+
+    println!("✅ Retrieved restricted metadata of image '{}'", image_id);
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "image_id": image_id,
+        "metadata": metadata,
+        "versions": versions
+    })))
+}
+
 pub async fn get_user_images(
     path: web::Path<String>,
     data_service: web::Data<std::sync::Mutex<DataService>>,
@@ -158,10 +557,11 @@ pub async fn delete_image(
     let mut data = data_service.lock().unwrap();
     
     if let Some(image) = data.get_image(&image_id) {
-        // Delete file from filesystem
-        let file_path = format!("uploads/{}", image.filename);
+        // Delete file (and previous versions) from filesystem
         let filename = image.filename.clone();
-        let _ = std::fs::remove_file(file_path);
+        for file in image.all_filenames() {
+            ingest_service::remove_stored_file(&file);
+        }
         
         // Remove from data
         if data.delete_image(&image_id) {
@@ -0,0 +1,28 @@
+use actix_web::{web, HttpResponse, Result};
+use serde_json;
+use crate::models::AdminQuery;
+use crate::services::{HotFolderService, UserService};
+
+pub async fn get_ingestion_status(
+    query: web::Query<AdminQuery>,
+    hot_folders: web::Data<std::sync::Mutex<HotFolderService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    println!("📥 Fetching hot folder ingestion status");
+    if !user_service.is_admin(&query.requested_by) {
+        println!("❌ User '{}' is not allowed to view ingestion status", query.requested_by);
+        return Ok(HttpResponse::Forbidden().json(serde_json::json!({
+            "success": false,
+            "error": "Admin access required"
+        })));
+    }
+
+    let service = hot_folders.lock().unwrap();
+    println!("✅ Retrieved status for {} hot folders", service.folders.len());
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "enabled": service.is_enabled(),
+        "interval_secs": service.interval.as_secs(),
+        "folders": service.folders
+    })))
+}
@@ -0,0 +1,207 @@
+use actix_web::{web, HttpResponse, Result};
+use serde_json;
+use crate::models::{AdminQuery, KeypointListQuery, KeypointSchema, ReviewAnnotationRequest, SuggestKeypointsRequest, UpdateKeypointSchemaRequest};
+use crate::services::{DataService, UserService};
+use crate::services::keypoint_service::KeypointError;
+
+fn keypoint_error_response(error: &KeypointError) -> HttpResponse {
+    let body = serde_json::json!({
+        "success": false,
+        "error": error.to_string()
+    });
+    match error {
+        KeypointError::ImageNotFound | KeypointError::GroupNotFound | KeypointError::AnnotationNotFound => {
+            HttpResponse::NotFound().json(body)
+        }
+        KeypointError::MissingSchema | KeypointError::UnknownDimensions => HttpResponse::UnprocessableEntity().json(body),
+        KeypointError::Invalid(_) => HttpResponse::BadRequest().json(body),
+        KeypointError::Conflict(_) => HttpResponse::Conflict().json(body),
+    }
+}
+
+fn admin_required() -> HttpResponse {
+    HttpResponse::Forbidden().json(serde_json::json!({
+        "success": false,
+        "error": "Admin access required"
+    }))
+}
+
+pub async fn update_keypoint_schema(
+    path: web::Path<String>,
+    req: web::Json<UpdateKeypointSchemaRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("🦴 Updating keypoint schema of group '{}' by '{}'", group_id, req.updated_by);
+    if !user_service.is_admin(&req.updated_by) {
+        println!("❌ User '{}' is not allowed to change keypoint schemas", req.updated_by);
+        return Ok(admin_required());
+    }
+
+    let schema = match (&req.preset, &req.schema) {
+        (Some(preset), None) => match KeypointSchema::preset(preset) {
+            Some(schema) => schema,
+            None => return Ok(keypoint_error_response(&KeypointError::Invalid(format!("Unknown keypoint preset '{}'", preset)))),
+        },
+        (None, Some(schema)) => schema.clone(),
+        _ => return Ok(keypoint_error_response(&KeypointError::Invalid("Provide exactly one of preset or schema".to_string()))),
+    };
+
+    let mut data = data_service.lock().unwrap();
+    match data.set_keypoint_schema(&group_id, Some(schema.clone())) {
+        Ok(()) => {
+            let _ = data.save_to_json();
+            println!("✅ Keypoint schema '{}' set for group '{}'", schema.name, group_id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "schema": schema,
+                "message": "Keypoint schema updated successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to update keypoint schema of group '{}': {}", group_id, e);
+            Ok(keypoint_error_response(&e))
+        }
+    }
+}
+
+pub async fn delete_keypoint_schema(
+    path: web::Path<String>,
+    query: web::Query<AdminQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("🦴 Removing keypoint schema of group '{}' by '{}'", group_id, query.requested_by);
+    if !user_service.is_admin(&query.requested_by) {
+        println!("❌ User '{}' is not allowed to change keypoint schemas", query.requested_by);
+        return Ok(admin_required());
+    }
+
+    let mut data = data_service.lock().unwrap();
+    match data.set_keypoint_schema(&group_id, None) {
+        Ok(()) => {
+            let _ = data.save_to_json();
+            println!("✅ Keypoint schema removed from group '{}'", group_id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "message": "Keypoint schema removed successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to remove keypoint schema of group '{}': {}", group_id, e);
+            Ok(keypoint_error_response(&e))
+        }
+    }
+}
+
+pub async fn suggest_keypoints(
+    path: web::Path<String>,
+    req: web::Json<SuggestKeypointsRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let image_id = path.into_inner();
+    println!("🦴 Suggesting {} keypoints for image '{}' by user '{}'",
+             req.keypoints.len(), image_id, req.suggested_by);
+    let mut data = data_service.lock().unwrap();
+
+    match data.suggest_keypoints(&image_id, &req) {
+        Ok(annotation) => {
+            let _ = data.save_to_json();
+            println!("✅ Keypoint annotation created successfully (ID: {})", annotation.id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "annotation": annotation,
+                "message": "Keypoint annotation created successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to suggest keypoints: {}", e);
+            Ok(keypoint_error_response(&e))
+        }
+    }
+}
+
+pub async fn get_image_keypoints(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let image_id = path.into_inner();
+    println!("🔍 Fetching keypoint annotations for image '{}'", image_id);
+    let data = data_service.lock().unwrap();
+
+    let Some(image) = data.get_image(&image_id) else {
+        return Ok(keypoint_error_response(&KeypointError::ImageNotFound));
+    };
+    let schema = data.get_group(&image.group_id).and_then(|group| group.keypoint_schema.as_ref());
+    let query = KeypointListQuery { image_id: Some(image_id.clone()), group_id: None, status: None };
+    let annotations = data.list_keypoints(&query);
+
+    println!("✅ Found {} keypoint annotations for image '{}'", annotations.len(), image_id);
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "image_id": image_id,
+        "schema": schema,
+        "annotations": annotations
+    })))
+}
+
+pub async fn list_keypoints(
+    query: web::Query<KeypointListQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    println!("🔍 Listing keypoint annotations (image: {:?}, group: {:?}, status: {:?})",
+             query.image_id, query.group_id, query.status);
+    let data = data_service.lock().unwrap();
+    let annotations = data.list_keypoints(&query);
+    println!("✅ Found {} keypoint annotations", annotations.len());
+    Ok(HttpResponse::Ok().json(annotations))
+}
+
+pub async fn review_keypoints(
+    path: web::Path<String>,
+    req: web::Json<ReviewAnnotationRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let annotation_id = path.into_inner();
+    println!("👀 Reviewing keypoint annotation '{}' as '{}' by '{}'",
+             annotation_id, req.status, req.reviewed_by);
+    let mut data = data_service.lock().unwrap();
+
+    match data.review_keypoints(&annotation_id, &req.status, &req.reviewed_by) {
+        Ok(annotation) => {
+            let _ = data.save_to_json();
+            println!("✅ Keypoint annotation '{}' marked as {}", annotation.id, annotation.status);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "annotation": annotation,
+                "message": "Keypoint annotation reviewed successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to review keypoint annotation '{}': {}", annotation_id, e);
+            Ok(keypoint_error_response(&e))
+        }
+    }
+}
+
+pub async fn delete_keypoints(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let annotation_id = path.into_inner();
+    println!("🗑️ Removing keypoint annotation '{}'", annotation_id);
+    let mut data = data_service.lock().unwrap();
+
+    if data.keypoint_annotations.remove(&annotation_id).is_some() {
+        let _ = data.save_to_json();
+        println!("✅ Keypoint annotation '{}' removed successfully", annotation_id);
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": "Keypoint annotation removed successfully"
+        })))
+    } else {
+        println!("❌ Keypoint annotation '{}' not found", annotation_id);
+        Ok(keypoint_error_response(&KeypointError::AnnotationNotFound))
+    }
+}
@@ -5,6 +5,17 @@ pub mod tag_handlers;
 pub mod chat_handlers;
 pub mod openai_handlers;
 pub mod export_handlers;
+pub mod ingestion_handlers;
+pub mod duplicate_handlers;
+pub mod batch_handlers;
+pub mod region_handlers;
+pub mod keypoint_handlers;
+pub mod vocabulary_handlers;
+pub mod caption_handlers;
+pub mod vqa_handlers;
+pub mod rating_handlers;
+pub mod comment_handlers;
+pub mod history_handlers;
 
 pub use auth_handlers::*;
 pub use group_handlers::*;
@@ -13,3 +24,14 @@ pub use tag_handlers::*;
 pub use chat_handlers::*;
 pub use openai_handlers::*;
 pub use export_handlers::*;
+pub use ingestion_handlers::*;
+pub use duplicate_handlers::*;
+pub use batch_handlers::*;
+pub use region_handlers::*;
+pub use keypoint_handlers::*;
+pub use vocabulary_handlers::*;
+pub use caption_handlers::*;
+pub use vqa_handlers::*;
+pub use rating_handlers::*;
+pub use comment_handlers::*;
+pub use history_handlers::*;
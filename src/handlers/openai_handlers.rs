@@ -1,6 +1,8 @@
 use actix_web::{web, HttpResponse, Result};
 use serde::{Deserialize, Serialize};
 use reqwest;
+use crate::models::LabelVocabulary;
+use crate::services::DataService;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TagSuggestionRequest {
@@ -10,6 +12,9 @@ pub struct TagSuggestionRequest {
     pub pending_tags: Vec<String>,
     pub image_name: String,
     pub image_url: String,
+    // Quando informado e o grupo tiver vocabulário, a sugestão fica restrita a ele
+    #[serde(default)]
+    pub group_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -19,7 +24,10 @@ pub struct TagSuggestionResponse {
     pub error: Option<String>,
 }
 
-pub async fn generate_tag_suggestion(request: web::Json<TagSuggestionRequest>) -> Result<HttpResponse> {
+pub async fn generate_tag_suggestion(
+    request: web::Json<TagSuggestionRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
     println!("🤖 Generating AI tag suggestion for image '{}' in group '{}'", 
              request.image_name, request.group_name);
     
@@ -48,6 +56,25 @@ pub async fn generate_tag_suggestion(request: web::Json<TagSuggestionRequest>) -
     };
 
     let req = request.into_inner();
+
+    // Vocabulário controlado do grupo, se existir
+    let vocabulary: Option<LabelVocabulary> = req.group_id.as_ref().and_then(|group_id| {
+        let data = data_service.lock().unwrap();
+        data.get_group(group_id).and_then(|group| group.vocabulary.clone())
+    });
+    let vocabulary_text = match &vocabulary {
+        Some(vocabulary) => {
+            let labels: Vec<String> = vocabulary.labels.iter()
+                .map(|label| if label.description.is_empty() {
+                    format!("- {}", label.name)
+                } else {
+                    format!("- {}: {}", label.name, label.description)
+                })
+                .collect();
+            format!("\n\nVocabulário permitido (use SOMENTE um destes rótulos, escrito exatamente como abaixo):\n{}", labels.join("\n"))
+        }
+        None => String::new(),
+    };
     
     // Criar lista de tags já existentes para evitar repetições
     let existing_tags: Vec<String> = req.approved_tags.iter()
@@ -88,7 +115,7 @@ Contexto:
 - Grupo: {}
 - Tags aprovadas: {}
 - Tags rejeitadas: {}
-- Tags pendentes: {}{}
+- Tags pendentes: {}{}{}
 
 Instruções:
 1. Sugira apenas UMA tag
@@ -97,6 +124,7 @@ Instruções:
 4. Seja específico e conciso
 5. NÃO repita tags já existentes
 6. Foque em características visuais, objetos, cores, ou conceitos principais
+7. Se houver um vocabulário permitido, a tag DEVE ser um dos rótulos listados
 
 Responda apenas com a tag sugerida, sem explicações adicionais.",
         req.group_name,
@@ -105,7 +133,8 @@ Responda apenas com a tag sugerida, sem explicações adicionais.",
         approved_tags_text,
         rejected_tags_text,
         pending_tags_text,
-        existing_tags_text
+        existing_tags_text,
+        vocabulary_text
     );
 
     // Converter a imagem para base64
@@ -202,6 +231,22 @@ Responda apenas com a tag sugerida, sem explicações adicionais.",
 
         println!("🔍 Extracted suggestion: {:?}", suggestion);
 
+        // Com vocabulário, aceita apenas rótulos da lista e devolve o nome canônico
+        let suggestion = match (suggestion, &vocabulary) {
+            (Some(sug), Some(vocabulary)) if !sug.is_empty() => match vocabulary.find(sug.trim_matches(|c: char| c == '"' || c == '.')) {
+                Some(label) => Some(label.name.clone()),
+                None => {
+                    println!("❌ AI suggestion '{}' is not in the group's vocabulary", sug);
+                    return Ok(HttpResponse::Ok().json(TagSuggestionResponse {
+                        success: false,
+                        suggestion: None,
+                        error: Some(format!("AI suggestion '{}' is not in the group's vocabulary. Please try again.", sug)),
+                    }));
+                }
+            },
+            (suggestion, _) => suggestion,
+        };
+
         match suggestion {
             Some(sug) if !sug.is_empty() => {
                 println!("✅ AI tag suggestion generated: '{}'", sug);
@@ -0,0 +1,181 @@
+use actix_web::{web, HttpResponse, Result};
+use serde_json;
+use crate::models::{ComparePairRequest, NextItemQuery, RateImageRequest, UpdateRatingCriteriaRequest};
+use crate::services::{DataService, UserService};
+use crate::services::rating_service::RatingError;
+
+fn rating_error_response(error: &RatingError) -> HttpResponse {
+    let body = serde_json::json!({
+        "success": false,
+        "error": error.to_string()
+    });
+    match error {
+        RatingError::GroupNotFound | RatingError::CriterionNotFound | RatingError::ImageNotFound => HttpResponse::NotFound().json(body),
+        RatingError::Invalid(_) => HttpResponse::BadRequest().json(body),
+        RatingError::Conflict(_) => HttpResponse::Conflict().json(body),
+    }
+}
+
+fn admin_required() -> HttpResponse {
+    HttpResponse::Forbidden().json(serde_json::json!({
+        "success": false,
+        "error": "Admin access required"
+    }))
+}
+
+pub async fn update_rating_criteria(
+    path: web::Path<String>,
+    req: web::Json<UpdateRatingCriteriaRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("⭐ Updating rating criteria of group '{}' by '{}'", group_id, req.updated_by);
+    if !user_service.is_admin(&req.updated_by) {
+        println!("❌ User '{}' is not allowed to change rating criteria", req.updated_by);
+        return Ok(admin_required());
+    }
+
+    let mut data = data_service.lock().unwrap();
+    match data.set_rating_criteria(&group_id, req.criteria.clone()) {
+        Ok(()) => {
+            let _ = data.save_to_json();
+            println!("✅ {} rating criteria set for group '{}'", req.criteria.len(), group_id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "criteria": req.criteria,
+                "message": "Rating criteria updated successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to update rating criteria of group '{}': {}", group_id, e);
+            Ok(rating_error_response(&e))
+        }
+    }
+}
+
+pub async fn get_next_pair(
+    path: web::Path<(String, String)>,
+    query: web::Query<NextItemQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let (group_id, criterion) = path.into_inner();
+    println!("🔍 Picking next pair of group '{}' on '{}' for '{}'", group_id, criterion, query.annotator);
+    let data = data_service.lock().unwrap();
+
+    match data.next_pair(&group_id, &criterion, &query.annotator) {
+        Ok(pair) => {
+            match pair {
+                Some((a, b)) => println!("✅ Next pair: '{}' vs '{}'", a.id, b.id),
+                None => println!("✅ No pairs left for '{}'", query.annotator),
+            }
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "criterion": criterion,
+                "image_a": pair.map(|(a, _)| a),
+                "image_b": pair.map(|(_, b)| b),
+                "done": pair.is_none()
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to pick next pair: {}", e);
+            Ok(rating_error_response(&e))
+        }
+    }
+}
+
+pub async fn compare_pair(
+    path: web::Path<(String, String)>,
+    req: web::Json<ComparePairRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let (group_id, criterion) = path.into_inner();
+    println!("⚖️ Comparing '{}' vs '{}' on '{}' by '{}'", req.image_a, req.image_b, criterion, req.annotator);
+    let mut data = data_service.lock().unwrap();
+
+    match data.compare_pair(&group_id, &criterion, &req) {
+        Ok(comparison) => {
+            let _ = data.save_to_json();
+            println!("✅ Comparison recorded (ID: {}, winner: {:?})", comparison.id, comparison.winner_id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "comparison": comparison,
+                "message": "Comparison recorded successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to record comparison: {}", e);
+            Ok(rating_error_response(&e))
+        }
+    }
+}
+
+pub async fn get_next_rating_image(
+    path: web::Path<(String, String)>,
+    query: web::Query<NextItemQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let (group_id, criterion) = path.into_inner();
+    println!("🔍 Picking next image of group '{}' on '{}' for '{}'", group_id, criterion, query.annotator);
+    let data = data_service.lock().unwrap();
+
+    match data.next_rating_image(&group_id, &criterion, &query.annotator) {
+        Ok(image) => {
+            match image {
+                Some(image) => println!("✅ Next image: '{}'", image.id),
+                None => println!("✅ No images left for '{}'", query.annotator),
+            }
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "criterion": criterion,
+                "image": image,
+                "done": image.is_none()
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to pick next image: {}", e);
+            Ok(rating_error_response(&e))
+        }
+    }
+}
+
+pub async fn rate_image(
+    path: web::Path<(String, String)>,
+    req: web::Json<RateImageRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let (group_id, criterion) = path.into_inner();
+    println!("⭐ Rating image '{}' with {} on '{}' by '{}'", req.image_id, req.score, criterion, req.annotator);
+    let mut data = data_service.lock().unwrap();
+
+    match data.rate_image(&group_id, &criterion, &req) {
+        Ok(rating) => {
+            let _ = data.save_to_json();
+            println!("✅ Rating recorded (ID: {})", rating.id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "rating": rating,
+                "message": "Rating recorded successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to record rating: {}", e);
+            Ok(rating_error_response(&e))
+        }
+    }
+}
+
+pub async fn get_criterion_scores(
+    path: web::Path<(String, String)>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let (group_id, criterion) = path.into_inner();
+    println!("📊 Computing scores of group '{}' on '{}'", group_id, criterion);
+    let data = data_service.lock().unwrap();
+
+    match data.criterion_scores(&group_id, &criterion) {
+        Ok(scores) => Ok(HttpResponse::Ok().json(scores)),
+        Err(e) => {
+            println!("❌ Failed to compute scores: {}", e);
+            Ok(rating_error_response(&e))
+        }
+    }
+}
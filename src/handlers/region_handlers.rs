@@ -0,0 +1,150 @@
+use actix_web::{web, HttpResponse, Result};
+use serde_json;
+use crate::models::{SuggestRegionRequest, ReviewAnnotationRequest, RegionListQuery};
+use crate::services::DataService;
+use crate::services::region_service::RegionError;
+
+fn region_error_response(error: &RegionError) -> HttpResponse {
+    let body = serde_json::json!({
+        "success": false,
+        "error": error.to_string()
+    });
+    match error {
+        RegionError::ImageNotFound | RegionError::RegionNotFound => HttpResponse::NotFound().json(body),
+        RegionError::UnknownDimensions => HttpResponse::UnprocessableEntity().json(body),
+        RegionError::Invalid(_) => HttpResponse::BadRequest().json(body),
+        RegionError::Storage(_) => HttpResponse::InternalServerError().json(body),
+    }
+}
+
+pub async fn suggest_region(
+    path: web::Path<String>,
+    req: web::Json<SuggestRegionRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let image_id = path.into_inner();
+    println!("🔲 Suggesting region '{}' for image '{}' by user '{}'",
+             req.label, image_id, req.suggested_by);
+    let mut data = data_service.lock().unwrap();
+
+    match data.suggest_region(&image_id, &req) {
+        Ok(region) => {
+            let _ = data.save_to_json();
+            println!("✅ Region suggestion '{}' created successfully (ID: {})", region.label, region.id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "region": region,
+                "message": "Region suggestion created successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to suggest region: {}", e);
+            Ok(region_error_response(&e))
+        }
+    }
+}
+
+pub async fn get_image_regions(
+    path: web::Path<String>,
+    query: web::Query<RegionListQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let image_id = path.into_inner();
+    println!("🔍 Fetching regions for image '{}'", image_id);
+    let data = data_service.lock().unwrap();
+
+    let Some(image) = data.get_image(&image_id) else {
+        return Ok(region_error_response(&RegionError::ImageNotFound));
+    };
+    let mut query = query.into_inner();
+    query.image_id = Some(image_id.clone());
+    let regions = data.region_views(data.list_regions(&query), query.include_geometry);
+
+    println!("✅ Found {} regions for image '{}'", regions.len(), image_id);
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "image_id": image_id,
+        "width": image.width,
+        "height": image.height,
+        "regions": regions
+    })))
+}
+
+pub async fn list_regions(
+    query: web::Query<RegionListQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    println!("🔍 Listing regions (image: {:?}, group: {:?}, status: {:?}, label: {:?}, kind: {:?})",
+             query.image_id, query.group_id, query.status, query.label, query.kind);
+    let data = data_service.lock().unwrap();
+    let regions = data.region_views(data.list_regions(&query), query.include_geometry);
+    println!("✅ Found {} regions", regions.len());
+    Ok(HttpResponse::Ok().json(regions))
+}
+
+pub async fn get_region(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let region_id = path.into_inner();
+    println!("🔍 Fetching region '{}'", region_id);
+    let data = data_service.lock().unwrap();
+
+    match data.region_annotations.get(&region_id) {
+        Some(region) => {
+            let view = data.region_views(vec![region], true);
+            Ok(HttpResponse::Ok().json(&view[0]))
+        }
+        None => {
+            println!("❌ Region '{}' not found", region_id);
+            Ok(region_error_response(&RegionError::RegionNotFound))
+        }
+    }
+}
+
+pub async fn review_region(
+    path: web::Path<String>,
+    req: web::Json<ReviewAnnotationRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let region_id = path.into_inner();
+    println!("👀 Reviewing region '{}' as '{}' by '{}'",
+             region_id, req.status, req.reviewed_by);
+    let mut data = data_service.lock().unwrap();
+
+    match data.review_region(&region_id, &req.status, &req.reviewed_by) {
+        Ok(region) => {
+            let _ = data.save_to_json();
+            println!("✅ Region '{}' marked as {}", region.label, region.status);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "region": region,
+                "message": "Region reviewed successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to review region '{}': {}", region_id, e);
+            Ok(region_error_response(&e))
+        }
+    }
+}
+
+pub async fn delete_region(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let region_id = path.into_inner();
+    println!("🗑️ Removing region '{}'", region_id);
+    let mut data = data_service.lock().unwrap();
+
+    if data.delete_region(&region_id) {
+        let _ = data.save_to_json();
+        println!("✅ Region '{}' removed successfully", region_id);
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": "Region removed successfully"
+        })))
+    } else {
+        println!("❌ Region '{}' not found", region_id);
+        Ok(region_error_response(&RegionError::RegionNotFound))
+    }
+}
@@ -1,8 +1,9 @@
 use actix_web::{web, HttpResponse, Result};
 use serde_json;
-use crate::models::{SuggestTagRequest, ReviewTagRequest, UpvoteTagRequest, TagSuggestion, ApprovedTag, TagUpvote};
+use crate::models::{SuggestTagRequest, ReviewTagRequest, UpvoteTagRequest, ConfirmTagRequest, DeleteTagQuery, UserSuggestionsQuery, SuggestionStatus, TagSuggestion, ApprovedTag, TagUpvote};
 use crate::services::DataService;
-use chrono::Utc;
+use crate::services::tag_review_service::ReviewError;
+use crate::services::vocabulary_service::{LabelOutcome, VocabularyError};
 
 pub async fn suggest_tag(
     path: web::Path<String>,
@@ -14,22 +15,49 @@ pub async fn suggest_tag(
              req.tag, image_id, req.suggested_by);
     let mut data = data_service.lock().unwrap();
     
-    let suggestion = TagSuggestion::new(
-        image_id.clone(),
-        req.tag.clone(),
-        req.suggested_by.clone(),
-    );
-    
-    let suggestion_id = suggestion.id.clone();
-    data.tag_suggestions.insert(suggestion_id.clone(), suggestion);
-    let _ = data.save_to_json();
-    
-    println!("✅ Tag suggestion '{}' created successfully (ID: {})", req.tag, suggestion_id);
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "success": true,
-        "suggestion_id": suggestion_id,
-        "message": "Tag suggestion created successfully"
-    })))
+    match data.submit_tag_suggestion(&image_id, &req.tag, &req.attributes, &req.suggested_by) {
+        Ok(LabelOutcome::Suggested(suggestion)) => {
+            let _ = data.save_to_json();
+            println!("✅ Tag suggestion '{}' created successfully (ID: {})", suggestion.tag, suggestion.id);
+            let auto_approved = suggestion.status == SuggestionStatus::Approved;
+            if auto_approved {
+                println!("🤝 Tag '{}' auto-approved by consensus on image '{}'", suggestion.tag, suggestion.image_id);
+            }
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "suggestion_id": suggestion.id,
+                "tag": suggestion.tag,
+                "attributes": suggestion.attributes,
+                "status": suggestion.status,
+                "auto_approved": auto_approved,
+                "message": "Tag suggestion created successfully"
+            })))
+        }
+        Ok(LabelOutcome::Proposed(proposal)) => {
+            let _ = data.save_to_json();
+            println!("📨 Label '{}' is not in the vocabulary; proposal '{}' queued for admins", proposal.label, proposal.id);
+            Ok(HttpResponse::Accepted().json(serde_json::json!({
+                "success": true,
+                "proposal_id": proposal.id,
+                "message": "Label is not in the group's vocabulary and was sent to admins as a proposal"
+            })))
+        }
+        Err(VocabularyError::NotInVocabulary { label, allowed }) => {
+            println!("❌ Label '{}' rejected: not in the group's vocabulary", label);
+            Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "success": false,
+                "error": format!("Label '{}' is not in the group's vocabulary", label),
+                "allowed_labels": allowed
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to suggest tag: {}", e);
+            Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            })))
+        }
+    }
 }
 
 pub async fn review_tag(
@@ -41,42 +69,66 @@ pub async fn review_tag(
     println!("👀 Reviewing tag suggestion '{}' as '{}' by '{}'", 
              tag_id, req.status, req.reviewed_by);
     let mut data = data_service.lock().unwrap();
-    
-    if let Some(suggestion) = data.tag_suggestions.get_mut(&tag_id) {
-        let tag_text = suggestion.tag.clone();
-        let image_id = suggestion.image_id.clone();
-        
-        suggestion.status = req.status.clone();
-        suggestion.reviewed_by = Some(req.reviewed_by.clone());
-        suggestion.reviewed_at = Some(Utc::now().to_rfc3339());
-        
-        // If approved, create approved tag
-        if req.status == "approved" {
-            let approved_tag = ApprovedTag::new(
-                image_id,
-                tag_text.clone(),
-                req.reviewed_by.clone(),
-            );
-            let tag_id = approved_tag.id.clone();
-            data.approved_tags.insert(tag_id, approved_tag);
-            println!("✅ Tag '{}' approved and added to approved tags", tag_text);
-        } else {
-            println!("❌ Tag '{}' rejected", tag_text);
+
+    let image_id = data.tag_suggestions.get(&tag_id).map(|suggestion| suggestion.image_id.clone());
+    let checkpoint = data.history_checkpoint(image_id.as_deref());
+    let outcome = match data.review_tag_suggestion(&tag_id, &req) {
+        Ok(outcome) => outcome,
+        Err(ReviewError::ExclusiveConflict(conflicts)) => {
+            println!("❌ Tag suggestion '{}' conflicts with the approved class of its image", tag_id);
+            return Ok(HttpResponse::Conflict().json(serde_json::json!({
+                "success": false,
+                "error": ReviewError::ExclusiveConflict(Vec::new()).to_string(),
+                "conflicting_tags": conflicts.iter()
+                    .map(|(id, tag)| serde_json::json!({ "id": id, "tag": tag }))
+                    .collect::<Vec<_>>()
+            })));
         }
-        
-        let _ = data.save_to_json();
-        
-        Ok(HttpResponse::Ok().json(serde_json::json!({
-            "success": true,
-            "message": "Tag suggestion reviewed successfully"
-        })))
+        Err(e) => {
+            println!("❌ Tag suggestion '{}' not reviewed: {}", tag_id, e);
+            let body = serde_json::json!({
+                "success": false,
+                "error": e.to_string()
+            });
+            return Ok(match e {
+                ReviewError::SuggestionNotFound | ReviewError::GroupNotFound => HttpResponse::NotFound().json(body),
+                ReviewError::IllegalTransition { .. } => HttpResponse::Conflict().json(body),
+                ReviewError::Forbidden(_) => HttpResponse::Forbidden().json(body),
+                _ => HttpResponse::BadRequest().json(body),
+            });
+        }
+    };
+
+    if !outcome.changed {
+        println!("ℹ️ Tag suggestion '{}' is already {}", tag_id, outcome.suggestion.status);
     } else {
-        println!("❌ Tag suggestion '{}' not found", tag_id);
-        Ok(HttpResponse::NotFound().json(serde_json::json!({
-            "success": false,
-            "error": "Tag suggestion not found"
-        })))
+        if !outcome.superseded_tags.is_empty() {
+            println!("🔄 Class '{}' supersedes {} approved tag(s) on image '{}'",
+                     outcome.suggestion.tag, outcome.superseded_tags.len(), outcome.suggestion.image_id);
+        }
+        if !outcome.removed_tags.is_empty() {
+            println!("↩️ Approval of '{}' undone ({} approved tag(s) removed)", outcome.suggestion.tag, outcome.removed_tags.len());
+        }
+        match &outcome.suggestion.quorum {
+            Some(quorum) if outcome.suggestion.status.is_open() => println!("🗳️ Tag '{}' has {}/{} reviewer approvals",
+                                                                           outcome.suggestion.tag, quorum.approvals.len(), quorum.required),
+            _ => println!("✅ Tag '{}' is now {}", outcome.suggestion.tag, outcome.suggestion.status),
+        }
+        data.record_history(checkpoint, &req.reviewed_by);
+        let _ = data.save_to_json();
     }
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "status": outcome.suggestion.status,
+        "reject_reason": outcome.suggestion.reject_reason,
+        "changed": outcome.changed,
+        "approved_tag_id": outcome.approved_tag_id,
+        "removed_tags": outcome.removed_tags,
+        "superseded_tags": outcome.superseded_tags,
+        "quorum": outcome.suggestion.quorum,
+        "message": "Tag suggestion reviewed successfully"
+    })))
 }
 
 pub async fn upvote_tag(
@@ -87,6 +139,9 @@ pub async fn upvote_tag(
     let tag_id = path.into_inner();
     println!("👍 Upvoting tag '{}' by user '{}'", tag_id, req.user_id);
     let mut data = data_service.lock().unwrap();
+    let image_id = data.approved_tags.get(&tag_id).map(|tag| tag.image_id.clone())
+        .or_else(|| data.tag_suggestions.get(&tag_id).map(|suggestion| suggestion.image_id.clone()));
+    let checkpoint = data.history_checkpoint(image_id.as_deref());
     
     // Check if user already upvoted this tag
     let existing_upvote = data.tag_upvotes.values()
@@ -116,26 +171,37 @@ pub async fn upvote_tag(
         println!("✅ Upvote added for tag '{}' by user '{}'", tag_id, req.user_id);
     }
     
+    data.record_history(checkpoint, &req.user_id);
+    let auto_approved_tag = data.apply_vote_consensus(&tag_id);
+    if let Some(approved_tag_id) = &auto_approved_tag {
+        println!("🤝 Suggestion '{}' auto-approved by consensus (tag '{}')", tag_id, approved_tag_id);
+    }
     let _ = data.save_to_json();
     
     Ok(HttpResponse::Ok().json(serde_json::json!({
         "success": true,
+        "auto_approved": auto_approved_tag.is_some(),
+        "approved_tag_id": auto_approved_tag,
         "message": "Tag upvote updated successfully"
     })))
 }
 
 pub async fn delete_approved_tag(
     path: web::Path<String>,
+    query: web::Query<DeleteTagQuery>,
     data_service: web::Data<std::sync::Mutex<DataService>>,
 ) -> Result<HttpResponse> {
     let tag_id = path.into_inner();
-    println!("🗑️ Removing approved tag '{}'", tag_id);
+    let requested_by = query.requested_by.as_deref().unwrap_or("unknown");
+    println!("🗑️ Removing approved tag '{}' by '{}'", tag_id, requested_by);
     let mut data = data_service.lock().unwrap();
 
-    let removed_tag = data.approved_tags.remove(&tag_id);
+    let image_id = data.approved_tags.get(&tag_id).map(|tag| tag.image_id.clone());
+    let checkpoint = data.history_checkpoint(image_id.as_deref());
+    let removed_tag = data.delete_approved_tag(&tag_id, requested_by);
 
     if removed_tag.is_some() {
-        data.tag_upvotes.retain(|_, upvote| upvote.tag_id != tag_id);
+        data.record_history(checkpoint, requested_by);
         let _ = data.save_to_json();
         println!("✅ Approved tag '{}' removed successfully", tag_id);
 
@@ -152,6 +218,36 @@ pub async fn delete_approved_tag(
     }
 }
 
+pub async fn confirm_approved_tag(
+    path: web::Path<String>,
+    req: web::Json<ConfirmTagRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let tag_id = path.into_inner();
+    println!("🔁 Confirming approved tag '{}' by '{}'", tag_id, req.confirmed_by);
+    let mut data = data_service.lock().unwrap();
+
+    let image_id = data.approved_tags.get(&tag_id).map(|tag| tag.image_id.clone());
+    let checkpoint = data.history_checkpoint(image_id.as_deref());
+    if let Some(tag) = data.approved_tags.get_mut(&tag_id) {
+        tag.needs_review = false;
+        data.record_history(checkpoint, &req.confirmed_by);
+        let _ = data.save_to_json();
+        println!("✅ Approved tag '{}' confirmed", tag_id);
+
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": "Approved tag confirmed successfully"
+        })))
+    } else {
+        println!("❌ Approved tag '{}' not found", tag_id);
+        Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "success": false,
+            "error": "Approved tag not found"
+        })))
+    }
+}
+
 pub async fn get_all_tags(
     data_service: web::Data<std::sync::Mutex<DataService>>,
 ) -> Result<HttpResponse> {
@@ -215,3 +311,20 @@ pub async fn get_tag_upvotes(
         "upvotes": upvotes
     })))
 }
+
+pub async fn get_user_suggestions(
+    path: web::Path<String>,
+    query: web::Query<UserSuggestionsQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let username = path.into_inner();
+    println!("🏷️ Fetching tag suggestions of user '{}' (status: {:?})", username, query.status);
+    let data = data_service.lock().unwrap();
+    let suggestions = data.user_suggestions(&username, query.status);
+    println!("✅ Retrieved {} suggestions of '{}'", suggestions.len(), username);
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "username": username,
+        "suggestions": suggestions
+    })))
+}
@@ -0,0 +1,299 @@
+use actix_web::{web, HttpResponse, Result};
+use serde_json;
+use crate::models::{
+    AdminQuery, CreateTagAliasRequest, LabelProposalQuery, MergeTagsRequest, ReviewLabelProposalRequest, TagAliasQuery,
+    UpdateTagNormalizationRequest, UpdateVocabularyRequest,
+};
+use crate::services::{DataService, UserService};
+use crate::services::vocabulary_service::VocabularyError;
+
+fn vocabulary_error_response(error: &VocabularyError) -> HttpResponse {
+    let body = serde_json::json!({
+        "success": false,
+        "error": error.to_string()
+    });
+    match error {
+        VocabularyError::GroupNotFound | VocabularyError::ProposalNotFound | VocabularyError::AliasNotFound => {
+            HttpResponse::NotFound().json(body)
+        }
+        VocabularyError::NotInVocabulary { .. } | VocabularyError::Invalid(_) => HttpResponse::BadRequest().json(body),
+    }
+}
+
+fn admin_required() -> HttpResponse {
+    HttpResponse::Forbidden().json(serde_json::json!({
+        "success": false,
+        "error": "Admin access required"
+    }))
+}
+
+pub async fn get_vocabulary(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("📚 Fetching vocabulary of group '{}'", group_id);
+    let data = data_service.lock().unwrap();
+
+    match data.get_group(&group_id) {
+        Some(group) => Ok(HttpResponse::Ok().json(serde_json::json!({
+            "group_id": group_id,
+            "vocabulary": group.vocabulary
+        }))),
+        None => Ok(vocabulary_error_response(&VocabularyError::GroupNotFound)),
+    }
+}
+
+pub async fn update_vocabulary(
+    path: web::Path<String>,
+    req: web::Json<UpdateVocabularyRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("📚 Updating vocabulary of group '{}' by '{}' ({} labels)",
+             group_id, req.updated_by, req.vocabulary.labels.len());
+    if !user_service.is_admin(&req.updated_by) {
+        println!("❌ User '{}' is not allowed to change vocabularies", req.updated_by);
+        return Ok(admin_required());
+    }
+
+    let mut data = data_service.lock().unwrap();
+    match data.set_vocabulary(&group_id, Some(req.vocabulary.clone())) {
+        Ok(()) => {
+            let _ = data.save_to_json();
+            println!("✅ Vocabulary of group '{}' updated successfully", group_id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "message": "Vocabulary updated successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to update vocabulary of group '{}': {}", group_id, e);
+            Ok(vocabulary_error_response(&e))
+        }
+    }
+}
+
+pub async fn delete_vocabulary(
+    path: web::Path<String>,
+    query: web::Query<AdminQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("📚 Removing vocabulary of group '{}' by '{}'", group_id, query.requested_by);
+    if !user_service.is_admin(&query.requested_by) {
+        println!("❌ User '{}' is not allowed to change vocabularies", query.requested_by);
+        return Ok(admin_required());
+    }
+
+    let mut data = data_service.lock().unwrap();
+    match data.set_vocabulary(&group_id, None) {
+        Ok(()) => {
+            let _ = data.save_to_json();
+            println!("✅ Vocabulary removed from group '{}'", group_id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "message": "Vocabulary removed successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to remove vocabulary of group '{}': {}", group_id, e);
+            Ok(vocabulary_error_response(&e))
+        }
+    }
+}
+
+pub async fn list_label_proposals(
+    query: web::Query<LabelProposalQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    println!("📨 Listing label proposals (group: {:?}, status: {:?})", query.group_id, query.status);
+    let data = data_service.lock().unwrap();
+    let proposals = data.list_label_proposals(&query);
+    println!("✅ Found {} label proposals", proposals.len());
+    Ok(HttpResponse::Ok().json(proposals))
+}
+
+pub async fn review_label_proposal(
+    path: web::Path<String>,
+    req: web::Json<ReviewLabelProposalRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let proposal_id = path.into_inner();
+    println!("📨 Reviewing label proposal '{}' as '{}' by '{}'", proposal_id, req.status, req.reviewed_by);
+    if !user_service.is_admin(&req.reviewed_by) {
+        println!("❌ User '{}' is not allowed to review label proposals", req.reviewed_by);
+        return Ok(admin_required());
+    }
+
+    let mut data = data_service.lock().unwrap();
+    match data.review_label_proposal(&proposal_id, &req.status, &req.reviewed_by, &req.description, req.parent.as_deref()) {
+        Ok((proposal_ids, suggestion_ids)) => {
+            let _ = data.save_to_json();
+            println!("✅ {} label proposals marked as {} ({} tag suggestions created)",
+                     proposal_ids.len(), req.status, suggestion_ids.len());
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "proposal_ids": proposal_ids,
+                "suggestion_ids": suggestion_ids,
+                "message": "Label proposal reviewed successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to review label proposal '{}': {}", proposal_id, e);
+            Ok(vocabulary_error_response(&e))
+        }
+    }
+}
+
+pub async fn get_label_stats(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("📊 Computing label statistics for group '{}'", group_id);
+    let data = data_service.lock().unwrap();
+
+    match data.label_stats(&group_id) {
+        Ok(stats) => {
+            println!("✅ Computed statistics for {} labels", stats.len());
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "group_id": group_id,
+                "labels": stats
+            })))
+        }
+        Err(e) => Ok(vocabulary_error_response(&e)),
+    }
+}
+
+pub async fn update_tag_normalization(
+    path: web::Path<String>,
+    req: web::Json<UpdateTagNormalizationRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("🔤 Updating tag normalization of group '{}' by '{}'", group_id, req.updated_by);
+    if !user_service.is_admin(&req.updated_by) {
+        println!("❌ User '{}' is not allowed to change tag normalization", req.updated_by);
+        return Ok(admin_required());
+    }
+
+    let mut data = data_service.lock().unwrap();
+    if let Some(group) = data.get_group_mut(&group_id) {
+        group.tag_normalization = req.normalization.clone();
+        let _ = data.save_to_json();
+        println!("✅ Tag normalization of group '{}' updated successfully", group_id);
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": "Tag normalization updated successfully"
+        })))
+    } else {
+        println!("❌ Group '{}' not found", group_id);
+        Ok(vocabulary_error_response(&VocabularyError::GroupNotFound))
+    }
+}
+
+pub async fn list_tag_aliases(
+    query: web::Query<TagAliasQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    println!("🔁 Listing tag aliases (group: {:?})", query.group_id);
+    let data = data_service.lock().unwrap();
+    let aliases = data.list_tag_aliases(query.group_id.as_deref());
+    println!("✅ Found {} tag aliases", aliases.len());
+    Ok(HttpResponse::Ok().json(aliases))
+}
+
+pub async fn create_tag_alias(
+    req: web::Json<CreateTagAliasRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    println!("🔁 Creating tag alias '{}' → '{}' (group: {:?}) by '{}'",
+             req.alias, req.canonical, req.group_id, req.created_by);
+    if !user_service.is_admin(&req.created_by) {
+        println!("❌ User '{}' is not allowed to manage tag aliases", req.created_by);
+        return Ok(admin_required());
+    }
+
+    let mut data = data_service.lock().unwrap();
+    match data.upsert_tag_alias(&req.alias, &req.canonical, req.group_id.as_deref(), &req.created_by) {
+        Ok(alias) => {
+            let _ = data.save_to_json();
+            println!("✅ Tag alias '{}' saved successfully", alias.id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "alias": alias,
+                "message": "Tag alias saved successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to save tag alias: {}", e);
+            Ok(vocabulary_error_response(&e))
+        }
+    }
+}
+
+pub async fn delete_tag_alias(
+    path: web::Path<String>,
+    query: web::Query<AdminQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let alias_id = path.into_inner();
+    println!("🗑️ Removing tag alias '{}' by '{}'", alias_id, query.requested_by);
+    if !user_service.is_admin(&query.requested_by) {
+        println!("❌ User '{}' is not allowed to manage tag aliases", query.requested_by);
+        return Ok(admin_required());
+    }
+
+    let mut data = data_service.lock().unwrap();
+    if data.tag_aliases.remove(&alias_id).is_some() {
+        let _ = data.save_to_json();
+        println!("✅ Tag alias '{}' removed successfully", alias_id);
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": "Tag alias removed successfully"
+        })))
+    } else {
+        println!("❌ Tag alias '{}' not found", alias_id);
+        Ok(vocabulary_error_response(&VocabularyError::AliasNotFound))
+    }
+}
+
+pub async fn merge_tags(
+    req: web::Json<MergeTagsRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    println!("🔀 {} merge of tags {:?} into '{}' (group: {:?}) by '{}'",
+             if req.preview { "Previewing" } else { "Running" }, req.sources, req.target, req.group_id, req.performed_by);
+    if !user_service.is_admin(&req.performed_by) {
+        println!("❌ User '{}' is not allowed to merge tags", req.performed_by);
+        return Ok(admin_required());
+    }
+
+    let mut data = data_service.lock().unwrap();
+    match data.merge_tags(&req) {
+        Ok(report) => {
+            if !req.preview {
+                let _ = data.save_to_json();
+            }
+            println!("✅ Tag merge {}: {} suggestions and {} approved tags renamed on {} images",
+                     if req.preview { "previewed" } else { "completed" },
+                     report.suggestions_renamed, report.approved_renamed, report.images_affected);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "report": report
+            })))
+        }
+        Err(e) => {
+            println!("❌ Tag merge failed: {}", e);
+            Ok(vocabulary_error_response(&e))
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use actix_web::{web, HttpResponse, Result};
+use serde_json;
+use crate::models::{
+    AdjudicateQuestionRequest, AdminQuery, AnswerQuestionRequest, AskQuestionRequest, ExportQuery, QuestionListQuery,
+    UpdateQuestionTemplatesRequest,
+};
+use crate::services::{DataService, UserService};
+use crate::services::vqa_service::VqaError;
+
+fn vqa_error_response(error: &VqaError) -> HttpResponse {
+    let body = serde_json::json!({
+        "success": false,
+        "error": error.to_string()
+    });
+    match error {
+        VqaError::ImageNotFound | VqaError::GroupNotFound | VqaError::TemplateNotFound
+        | VqaError::QuestionNotFound | VqaError::AnswerNotFound => HttpResponse::NotFound().json(body),
+        VqaError::Invalid(_) => HttpResponse::BadRequest().json(body),
+        VqaError::Conflict(_) => HttpResponse::Conflict().json(body),
+    }
+}
+
+fn admin_required() -> HttpResponse {
+    HttpResponse::Forbidden().json(serde_json::json!({
+        "success": false,
+        "error": "Admin access required"
+    }))
+}
+
+pub async fn update_question_templates(
+    path: web::Path<String>,
+    req: web::Json<UpdateQuestionTemplatesRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let group_id = path.into_inner();
+    println!("❓ Updating question templates of group '{}' by '{}'", group_id, req.updated_by);
+    if !user_service.is_admin(&req.updated_by) {
+        println!("❌ User '{}' is not allowed to change question templates", req.updated_by);
+        return Ok(admin_required());
+    }
+
+    let mut data = data_service.lock().unwrap();
+    match data.set_question_templates(&group_id, req.templates.clone()) {
+        Ok(templates) => {
+            let _ = data.save_to_json();
+            println!("✅ {} question templates set for group '{}'", templates.len(), group_id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "templates": templates,
+                "message": "Question templates updated successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to update question templates of group '{}': {}", group_id, e);
+            Ok(vqa_error_response(&e))
+        }
+    }
+}
+
+pub async fn ask_question(
+    path: web::Path<String>,
+    req: web::Json<AskQuestionRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let image_id = path.into_inner();
+    println!("❓ Asking question on image '{}' by user '{}'", image_id, req.asked_by);
+    let mut data = data_service.lock().unwrap();
+
+    match data.ask_question(&image_id, &req) {
+        Ok(question) => {
+            let _ = data.save_to_json();
+            println!("✅ Question '{}' ready (ID: {})", question.question, question.id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "question": question,
+                "message": "Question created successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to ask question: {}", e);
+            Ok(vqa_error_response(&e))
+        }
+    }
+}
+
+pub async fn get_image_questions(
+    path: web::Path<String>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let image_id = path.into_inner();
+    println!("🔍 Fetching questions for image '{}'", image_id);
+    let data = data_service.lock().unwrap();
+
+    let Some(image) = data.get_image(&image_id) else {
+        return Ok(vqa_error_response(&VqaError::ImageNotFound));
+    };
+    let templates = data.get_group(&image.group_id).map(|group| &group.question_templates);
+    let query = QuestionListQuery { image_id: Some(image_id.clone()), group_id: None, status: None };
+    let questions = data.list_questions(&query);
+
+    println!("✅ Found {} questions for image '{}'", questions.len(), image_id);
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "image_id": image_id,
+        "templates": templates,
+        "questions": questions
+    })))
+}
+
+pub async fn list_questions(
+    query: web::Query<QuestionListQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    println!("🔍 Listing questions (image: {:?}, group: {:?}, status: {:?})",
+             query.image_id, query.group_id, query.status);
+    let data = data_service.lock().unwrap();
+    let questions = data.list_questions(&query);
+    println!("✅ Found {} questions", questions.len());
+    Ok(HttpResponse::Ok().json(questions))
+}
+
+pub async fn answer_question(
+    path: web::Path<String>,
+    req: web::Json<AnswerQuestionRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let question_id = path.into_inner();
+    println!("💬 Answering question '{}' by user '{}'", question_id, req.answered_by);
+    let mut data = data_service.lock().unwrap();
+
+    match data.answer_question(&question_id, &req.answer, &req.answered_by) {
+        Ok(answer) => {
+            let _ = data.save_to_json();
+            println!("✅ Answer '{}' recorded (ID: {})", answer.answer, answer.id);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "answer": answer,
+                "message": "Answer recorded successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to answer question '{}': {}", question_id, e);
+            Ok(vqa_error_response(&e))
+        }
+    }
+}
+
+pub async fn adjudicate_question(
+    path: web::Path<String>,
+    req: web::Json<AdjudicateQuestionRequest>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let question_id = path.into_inner();
+    println!("⚖️ Adjudicating question '{}' by '{}'", question_id, req.adjudicated_by);
+    let mut data = data_service.lock().unwrap();
+
+    match data.adjudicate_question(&question_id, &req) {
+        Ok(question) => {
+            let _ = data.save_to_json();
+            println!("✅ Question '{}' resolved with answer {:?}", question.id, question.final_answer);
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "question": question,
+                "message": "Question adjudicated successfully"
+            })))
+        }
+        Err(e) => {
+            println!("❌ Failed to adjudicate question '{}': {}", question_id, e);
+            Ok(vqa_error_response(&e))
+        }
+    }
+}
+
+pub async fn delete_question(
+    path: web::Path<String>,
+    query: web::Query<AdminQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+    user_service: web::Data<UserService>,
+) -> Result<HttpResponse> {
+    let question_id = path.into_inner();
+    println!("🗑️ Removing question '{}' by '{}'", question_id, query.requested_by);
+    if !user_service.is_admin(&query.requested_by) {
+        println!("❌ User '{}' is not allowed to remove questions", query.requested_by);
+        return Ok(admin_required());
+    }
+
+    let mut data = data_service.lock().unwrap();
+    if data.delete_question(&question_id) {
+        let _ = data.save_to_json();
+        println!("✅ Question '{}' and its answers removed successfully", question_id);
+        Ok(HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "message": "Question removed successfully"
+        })))
+    } else {
+        println!("❌ Question '{}' not found", question_id);
+        Ok(vqa_error_response(&VqaError::QuestionNotFound))
+    }
+}
+
+pub async fn export_vqa(
+    query: web::Query<ExportQuery>,
+    data_service: web::Data<std::sync::Mutex<DataService>>,
+) -> Result<HttpResponse> {
+    let data = data_service.lock().unwrap();
+
+    // Filtro opcional pela linguagem de consulta de tags
+    let image_ids = match query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        Some(q) => match data.search_image_ids(q) {
+            Ok(ids) => Some(ids),
+            Err(e) => {
+                println!("❌ Invalid export query '{}': {}", q, e);
+                return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+                    "success": false,
+                    "error": format!("Invalid query: {}", e)
+                })));
+            }
+        },
+        None => None,
+    };
+    let export = data.export_vqa(image_ids.as_ref());
+    let json = serde_json::to_string_pretty(&export).unwrap_or_else(|_| "{}".to_string());
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .append_header(("Content-Disposition", "attachment; filename=vqa_export.json"))
+        .body(json))
+}
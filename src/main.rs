@@ -7,18 +7,38 @@ mod models;
 mod services;
 mod handlers;
 
-use services::{UserService, DataService};
+use services::{UserService, DataService, HotFolderService};
+use services::hot_folder_service::spawn_watcher;
 use handlers::{
     login, protected_route, admin_only_route, get_users_endpoint,
     get_groups, get_group, create_group, add_user_to_group, remove_user_from_group, update_group, delete_group,
-    upload_image, get_image, get_user_images, delete_image,
-    suggest_tag, get_image_tags, review_tag, upvote_tag, get_all_tags, get_approved_tags, get_tag_upvotes, delete_approved_tag,
-    chat_endpoint, generate_tag_suggestion, export_annotations,
+    upload_image, upload_archive, list_images, search_images, get_image, get_user_images, delete_image,
+    replace_image_file, get_image_versions, get_image_metadata, update_metadata_policy,
+    update_labeling_policy, get_labeling_completeness,
+    suggest_tag, get_image_tags, review_tag, upvote_tag, get_all_tags, get_approved_tags, get_tag_upvotes, delete_approved_tag, confirm_approved_tag,
+    chat_endpoint, generate_tag_suggestion, export_annotations, get_ingestion_status,
+    get_duplicate_clusters, merge_duplicates, batch_images,
+    suggest_region, get_image_regions, list_regions, get_region, review_region, delete_region,
+    update_keypoint_schema, delete_keypoint_schema, suggest_keypoints, get_image_keypoints, list_keypoints,
+    review_keypoints, delete_keypoints,
+    get_vocabulary, update_vocabulary, delete_vocabulary, list_label_proposals, review_label_proposal, get_label_stats,
+    update_tag_normalization, list_tag_aliases, create_tag_alias, delete_tag_alias, merge_tags,
+    update_caption_policy, suggest_caption, get_image_captions, list_captions, get_caption, edit_caption, review_caption,
+    delete_caption,
+    update_question_templates, ask_question, get_image_questions, list_questions, answer_question, adjudicate_question,
+    delete_question, export_vqa,
+    update_rating_criteria, get_next_pair, compare_pair, get_next_rating_image, rate_image, get_criterion_scores,
+    comment_on_image, comment_on_tag, get_image_comments, get_tag_comments, reply_to_comment, edit_comment, resolve_thread,
+    delete_comment, get_unresolved_threads, get_user_mentions,
+    get_image_history, get_image_state_at,
+    update_reject_reasons, get_reject_stats, update_consensus_policy, update_review_quorum, get_user_suggestions,
 };
 
 // Inicializar uploads directory
 fn init_uploads_dir() -> std::io::Result<()> {
     std::fs::create_dir_all("uploads")?;
+    std::fs::create_dir_all(services::exif_service::RESTRICTED_DIR)?;
+    std::fs::create_dir_all(services::region_service::ANNOTATIONS_DIR)?;
     Ok(())
 }
 
@@ -38,6 +58,7 @@ async fn main() -> std::io::Result<()> {
     println!("🔧 Initializing services...");
     let user_service = web::Data::new(UserService::new());
     let data_service = web::Data::new(Mutex::new(DataService::new()));
+    let hot_folders = web::Data::new(Mutex::new(HotFolderService::from_env()));
     println!("✅ Services initialized");
     
     // Load data from JSON
@@ -50,6 +71,10 @@ async fn main() -> std::io::Result<()> {
         } else {
             println!("✅ Data loaded from JSON successfully!");
         }
+        let updated = data.backfill_image_info();
+        if updated > 0 {
+            println!("✅ Computed hashes and dimensions for {} images", updated);
+        }
     }
     
     // Get server configuration from environment variables
@@ -62,6 +87,17 @@ async fn main() -> std::io::Result<()> {
     println!("🔑 OpenAI API Key: {}", if openai_api_key.is_empty() { "Not set" } else { "Set" });
     println!("📁 Uploads directory: ./uploads");
     println!("📄 Data file: ./data.json");
+    
+    // Start hot folder watcher
+    {
+        let service = hot_folders.lock().unwrap();
+        for folder in &service.folders {
+            println!("👀 Watching hot folder '{}' for group '{}'", folder.path, folder.group_id);
+        }
+        if service.is_enabled() {
+            spawn_watcher(hot_folders.clone(), data_service.clone());
+        }
+    }
     println!("🌐 Server starting...");
     
     HttpServer::new(move || {
@@ -76,6 +112,7 @@ async fn main() -> std::io::Result<()> {
             .wrap(Logger::default())
             .app_data(user_service.clone())
             .app_data(data_service.clone())
+            .app_data(hot_folders.clone())
             // Serve static files from uploads directory
             .service(fs::Files::new("/uploads", "./uploads").show_files_listing())
             // Auth routes
@@ -83,6 +120,7 @@ async fn main() -> std::io::Result<()> {
             .route("/protected", web::get().to(protected_route))
             .route("/admin", web::get().to(admin_only_route))
             .route("/users", web::get().to(get_users_endpoint))
+            .route("/admin/ingestion", web::get().to(get_ingestion_status))
             
             // Group routes - RESTful
             .route("/groups", web::get().to(get_groups))                    // GET /groups
@@ -92,13 +130,53 @@ async fn main() -> std::io::Result<()> {
             .route("/groups/{id}", web::delete().to(delete_group))          // DELETE /groups/{id}
             .route("/groups/{id}/members", web::post().to(add_user_to_group))    // POST /groups/{id}/members
             .route("/groups/{id}/members/{username}", web::delete().to(remove_user_from_group)) // DELETE /groups/{id}/members/{username}
+            .route("/groups/{id}/metadata-policy", web::put().to(update_metadata_policy)) // PUT /groups/{id}/metadata-policy
+            .route("/groups/{id}/labeling-mode", web::put().to(update_labeling_policy)) // PUT /groups/{id}/labeling-mode
+            .route("/groups/{id}/completeness", web::get().to(get_labeling_completeness)) // GET /groups/{id}/completeness
+            .route("/groups/{id}/reject-reasons", web::put().to(update_reject_reasons)) // PUT /groups/{id}/reject-reasons
+            .route("/groups/{id}/consensus-policy", web::put().to(update_consensus_policy)) // PUT /groups/{id}/consensus-policy
+            .route("/groups/{id}/review-quorum", web::put().to(update_review_quorum)) // PUT /groups/{id}/review-quorum
+            .route("/groups/{id}/reject-stats", web::get().to(get_reject_stats)) // GET /groups/{id}/reject-stats
+            .route("/groups/{id}/keypoint-schema", web::put().to(update_keypoint_schema)) // PUT /groups/{id}/keypoint-schema
+            .route("/groups/{id}/keypoint-schema", web::delete().to(delete_keypoint_schema)) // DELETE /groups/{id}/keypoint-schema
+            .service(
+                web::resource("/groups/{id}/vocabulary")
+                    .route(web::get().to(get_vocabulary))
+                    .route(web::put().to(update_vocabulary))
+                    .route(web::delete().to(delete_vocabulary))
+            )
+            .route("/groups/{id}/caption-policy", web::put().to(update_caption_policy)) // PUT /groups/{id}/caption-policy
+            .route("/groups/{id}/question-templates", web::put().to(update_question_templates)) // PUT /groups/{id}/question-templates
+            .route("/groups/{id}/rating-criteria", web::put().to(update_rating_criteria)) // PUT /groups/{id}/rating-criteria
+            .route("/groups/{id}/criteria/{criterion}/next-pair", web::get().to(get_next_pair)) // GET /groups/{id}/criteria/{criterion}/next-pair
+            .route("/groups/{id}/criteria/{criterion}/comparisons", web::post().to(compare_pair)) // POST /groups/{id}/criteria/{criterion}/comparisons
+            .route("/groups/{id}/criteria/{criterion}/next-image", web::get().to(get_next_rating_image)) // GET /groups/{id}/criteria/{criterion}/next-image
+            .route("/groups/{id}/criteria/{criterion}/ratings", web::post().to(rate_image)) // POST /groups/{id}/criteria/{criterion}/ratings
+            .route("/groups/{id}/criteria/{criterion}/scores", web::get().to(get_criterion_scores)) // GET /groups/{id}/criteria/{criterion}/scores
+            .route("/groups/{id}/unresolved-threads", web::get().to(get_unresolved_threads)) // GET /groups/{id}/unresolved-threads
+            .route("/groups/{id}/label-stats", web::get().to(get_label_stats)) // GET /groups/{id}/label-stats
+            .route("/groups/{id}/tag-normalization", web::put().to(update_tag_normalization)) // PUT /groups/{id}/tag-normalization
+            .route("/label-proposals", web::get().to(list_label_proposals))   // GET /label-proposals
+            .route("/label-proposals/{id}", web::put().to(review_label_proposal)) // PUT /label-proposals/{id}
             
             
             // Image routes - RESTful
+            .route("/images", web::get().to(list_images))                      // GET /images
             .route("/images", web::post().to(upload_image))                    // POST /images
+            .route("/images/archive", web::post().to(upload_archive))          // POST /images/archive
+            .route("/images/batch", web::post().to(batch_images))              // POST /images/batch
+            .route("/images/search", web::get().to(search_images))             // GET /images/search
+            .route("/images/duplicates", web::get().to(get_duplicate_clusters)) // GET /images/duplicates
+            .route("/images/duplicates/merge", web::post().to(merge_duplicates)) // POST /images/duplicates/merge
             .route("/images/{id}", web::get().to(get_image))                   // GET /images/{id}
             .route("/images/{id}", web::delete().to(delete_image))             // DELETE /images/{id}
+            .route("/images/{id}/file", web::put().to(replace_image_file))     // PUT /images/{id}/file
+            .route("/images/{id}/versions", web::get().to(get_image_versions)) // GET /images/{id}/versions
+            .route("/images/{id}/metadata", web::get().to(get_image_metadata)) // GET /images/{id}/metadata
+            .route("/images/{id}/history", web::get().to(get_image_history))  // GET /images/{id}/history
+            .route("/images/{id}/history/state", web::get().to(get_image_state_at)) // GET /images/{id}/history/state?at=
             .route("/users/{username}/images", web::get().to(get_user_images)) // GET /users/{username}/images
+            .route("/users/{username}/suggestions", web::get().to(get_user_suggestions)) // GET /users/{username}/suggestions
             
             // Tag routes - RESTful
             .service(
@@ -114,8 +192,75 @@ async fn main() -> std::io::Result<()> {
                     .route(web::get().to(get_tag_upvotes))
             )
             .route("/tags/{tag_id}", web::delete().to(delete_approved_tag))
+            .route("/tags/{tag_id}/confirmations", web::post().to(confirm_approved_tag))
             .route("/tags/approved", web::get().to(get_approved_tags))         // GET /tags/approved
+            .route("/tags/merge", web::post().to(merge_tags))                  // POST /tags/merge
+            .route("/tag-aliases", web::get().to(list_tag_aliases))            // GET /tag-aliases
+            .route("/tag-aliases", web::post().to(create_tag_alias))           // POST /tag-aliases
+            .route("/tag-aliases/{id}", web::delete().to(delete_tag_alias))    // DELETE /tag-aliases/{id}
             .route("/annotations/export", web::get().to(export_annotations))
+            .route("/annotations/export/vqa", web::get().to(export_vqa))       // GET /annotations/export/vqa
+
+            // Region routes - RESTful
+            .service(
+                web::resource("/images/{image_id}/regions")
+                    .route(web::post().to(suggest_region))
+                    .route(web::get().to(get_image_regions))
+            )
+            .route("/regions", web::get().to(list_regions))                    // GET /regions
+            .route("/regions/{id}", web::get().to(get_region))                 // GET /regions/{id}
+            .route("/regions/{id}", web::put().to(review_region))              // PUT /regions/{id}
+            .route("/regions/{id}", web::delete().to(delete_region))           // DELETE /regions/{id}
+
+            // Keypoint routes - RESTful
+            .service(
+                web::resource("/images/{image_id}/keypoints")
+                    .route(web::post().to(suggest_keypoints))
+                    .route(web::get().to(get_image_keypoints))
+            )
+            .route("/keypoints", web::get().to(list_keypoints))                // GET /keypoints
+            .route("/keypoints/{id}", web::put().to(review_keypoints))         // PUT /keypoints/{id}
+            .route("/keypoints/{id}", web::delete().to(delete_keypoints))      // DELETE /keypoints/{id}
+
+            // Caption routes - RESTful
+            .service(
+                web::resource("/images/{image_id}/captions")
+                    .route(web::post().to(suggest_caption))
+                    .route(web::get().to(get_image_captions))
+            )
+            .route("/captions", web::get().to(list_captions))                  // GET /captions
+            .route("/captions/{id}", web::get().to(get_caption))               // GET /captions/{id}
+            .route("/captions/{id}", web::put().to(review_caption))            // PUT /captions/{id}
+            .route("/captions/{id}", web::delete().to(delete_caption))         // DELETE /captions/{id}
+            .route("/captions/{id}/text", web::put().to(edit_caption))         // PUT /captions/{id}/text
+
+            // VQA routes - RESTful
+            .service(
+                web::resource("/images/{image_id}/questions")
+                    .route(web::post().to(ask_question))
+                    .route(web::get().to(get_image_questions))
+            )
+            .route("/questions", web::get().to(list_questions))                // GET /questions
+            .route("/questions/{id}", web::delete().to(delete_question))       // DELETE /questions/{id}
+            .route("/questions/{id}/answers", web::post().to(answer_question)) // POST /questions/{id}/answers
+            .route("/questions/{id}/adjudication", web::put().to(adjudicate_question)) // PUT /questions/{id}/adjudication
+
+            // Comment routes - RESTful
+            .service(
+                web::resource("/images/{image_id}/comments")
+                    .route(web::post().to(comment_on_image))
+                    .route(web::get().to(get_image_comments))
+            )
+            .service(
+                web::resource("/tags/{tag_id}/comments")
+                    .route(web::post().to(comment_on_tag))
+                    .route(web::get().to(get_tag_comments))
+            )
+            .route("/comments/{id}", web::put().to(edit_comment))              // PUT /comments/{id}
+            .route("/comments/{id}", web::delete().to(delete_comment))         // DELETE /comments/{id}
+            .route("/comments/{id}/replies", web::post().to(reply_to_comment)) // POST /comments/{id}/replies
+            .route("/comments/{id}/resolution", web::put().to(resolve_thread)) // PUT /comments/{id}/resolution
+            .route("/users/{username}/mentions", web::get().to(get_user_mentions)) // GET /users/{username}/mentions
             
             // Chat routes - RESTful
             .route("/conversations", web::post().to(chat_endpoint))             // POST /conversations
@@ -1,14 +1,35 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
-use super::{Group, Image, TagSuggestion, ApprovedTag, TagUpvote};
+use super::{Group, Image, TagSuggestion, ApprovedTag, TagUpvote, RegionAnnotation, RegionGeometry, KeypointAnnotation, Caption, ImageTextPair, VqaQuestion, VqaAnswer, PairwiseComparison, LikertRating, CriterionScores};
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    pub q: Option<String>,
+    // "leaf" (padrão) exporta os rótulos como aprovados; "depth" troca cada rótulo pelo ancestral na profundidade `depth`
+    pub labels: Option<String>,
+    pub depth: Option<usize>,
+}
 
 #[derive(Serialize)]
 pub struct AnnotationsExport<'a> {
-    pub groups: &'a HashMap<String, Group>,
-    pub images: &'a HashMap<String, Image>,
-    pub tag_suggestions: &'a HashMap<String, TagSuggestion>,
-    pub approved_tags: &'a HashMap<String, ApprovedTag>,
-    pub tag_upvotes: &'a HashMap<String, TagUpvote>,
+    pub groups: HashMap<&'a str, &'a Group>,
+    pub images: HashMap<&'a str, &'a Image>,
+    pub tag_suggestions: HashMap<&'a str, Cow<'a, TagSuggestion>>,
+    pub approved_tags: HashMap<&'a str, Cow<'a, ApprovedTag>>,
+    pub tag_upvotes: HashMap<&'a str, &'a TagUpvote>,
+    pub region_annotations: HashMap<&'a str, &'a RegionAnnotation>,
+    // Polígonos e máscaras, lidos dos arquivos auxiliares
+    pub region_geometries: HashMap<&'a str, RegionGeometry>,
+    pub keypoint_annotations: HashMap<&'a str, &'a KeypointAnnotation>,
+    pub captions: HashMap<&'a str, &'a Caption>,
+    // Legendas aprovadas como pares imagem-texto
+    pub image_text_pairs: Vec<ImageTextPair<'a>>,
+    pub vqa_questions: HashMap<&'a str, &'a VqaQuestion>,
+    pub vqa_answers: HashMap<&'a str, &'a VqaAnswer>,
+    pub pairwise_comparisons: HashMap<&'a str, &'a PairwiseComparison>,
+    pub likert_ratings: HashMap<&'a str, &'a LikertRating>,
+    // Bradley–Terry ou média/mediana por critério de cada grupo exportado
+    pub rating_scores: Vec<CriterionScores>,
 }
-
@@ -0,0 +1,185 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use serde::{Deserialize, Serialize};
+
+use super::label_key;
+
+// Valores de atributos de uma tag, por nome do atributo (por exemplo color=red, occluded=true)
+pub type AttributeMap = BTreeMap<String, AttributeValue>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum AttributeValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+impl fmt::Display for AttributeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttributeValue::Bool(value) => write!(f, "{}", value),
+            AttributeValue::Number(value) => write!(f, "{}", value),
+            AttributeValue::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AttributeKind {
+    Enum { values: Vec<String> },
+    Bool,
+    Number {
+        #[serde(default)]
+        min: Option<f64>,
+        #[serde(default)]
+        max: Option<f64>,
+    },
+    Text {
+        #[serde(default)]
+        max_length: Option<usize>,
+    },
+}
+
+// Atributo tipado de um rótulo do vocabulário
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AttributeDefinition {
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: AttributeKind,
+    #[serde(default)]
+    pub required: bool, // exigido na aprovação; quem sugere pode deixar para o revisor
+}
+
+impl AttributeDefinition {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Attribute names cannot be empty".to_string());
+        }
+        match &self.kind {
+            AttributeKind::Enum { values } => {
+                if values.is_empty() {
+                    return Err(format!("Enum attribute '{}' needs at least one value", self.name));
+                }
+                let mut seen = HashSet::new();
+                for value in values {
+                    if value.trim().is_empty() || !seen.insert(label_key(value)) {
+                        return Err(format!("Enum attribute '{}' has an empty or duplicate value '{}'", self.name, value));
+                    }
+                }
+            }
+            AttributeKind::Number { min: Some(min), max: Some(max) } if min > max => {
+                return Err(format!("Number attribute '{}' has min greater than max", self.name));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    // Confere o tipo do valor e devolve a forma canônica (valor do enum como definido, texto sem espaços nas pontas)
+    pub fn coerce(&self, value: &AttributeValue) -> Result<AttributeValue, String> {
+        let mismatch = |expected: &str| format!("Attribute '{}' expects {}, got '{}'", self.name, expected, value);
+        match (&self.kind, value) {
+            (AttributeKind::Enum { values }, AttributeValue::Text(text)) => values.iter()
+                .find(|allowed| label_key(allowed) == label_key(text))
+                .map(|allowed| AttributeValue::Text(allowed.clone()))
+                .ok_or_else(|| format!("Attribute '{}' must be one of: {}", self.name, values.join(", "))),
+            (AttributeKind::Enum { .. }, _) => Err(mismatch("one of its enum values")),
+            (AttributeKind::Bool, AttributeValue::Bool(flag)) => Ok(AttributeValue::Bool(*flag)),
+            (AttributeKind::Bool, _) => Err(mismatch("true or false")),
+            (AttributeKind::Number { min, max }, AttributeValue::Number(number)) => {
+                if !number.is_finite() || min.is_some_and(|min| *number < min) || max.is_some_and(|max| *number > max) {
+                    return Err(format!("Attribute '{}' is out of range", self.name));
+                }
+                Ok(AttributeValue::Number(*number))
+            }
+            (AttributeKind::Number { .. }, _) => Err(mismatch("a number")),
+            (AttributeKind::Text { max_length }, AttributeValue::Text(text)) => {
+                let text = text.trim();
+                if let Some(max) = max_length.filter(|max| text.chars().count() > *max) {
+                    return Err(format!("Attribute '{}' is longer than {} characters", self.name, max));
+                }
+                Ok(AttributeValue::Text(text.to_string()))
+            }
+            (AttributeKind::Text { .. }, _) => Err(mismatch("text")),
+        }
+    }
+}
+
+// Valida os atributos enviados contra as definições do rótulo; `complete` exige os obrigatórios
+pub fn validate_attributes(label: &str, definitions: &[AttributeDefinition], values: &AttributeMap, complete: bool) -> Result<AttributeMap, String> {
+    let mut validated = AttributeMap::new();
+    for (name, value) in values {
+        let definition = definitions.iter()
+            .find(|definition| definition.name.eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| format!("Label '{}' has no attribute '{}'", label, name))?;
+        validated.insert(definition.name.clone(), definition.coerce(value)?);
+    }
+    if complete {
+        if let Some(missing) = definitions.iter().find(|definition| definition.required && !validated.contains_key(&definition.name)) {
+            return Err(format!("Attribute '{}' is required for label '{}'", missing.name, label));
+        }
+    }
+    Ok(validated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definitions() -> Vec<AttributeDefinition> {
+        vec![
+            AttributeDefinition { name: "color".to_string(), kind: AttributeKind::Enum { values: vec!["Red".to_string(), "Blue".to_string()] }, required: true },
+            AttributeDefinition { name: "occluded".to_string(), kind: AttributeKind::Bool, required: false },
+            AttributeDefinition { name: "speed".to_string(), kind: AttributeKind::Number { min: Some(0.0), max: Some(300.0) }, required: false },
+            AttributeDefinition { name: "plate".to_string(), kind: AttributeKind::Text { max_length: Some(8) }, required: false },
+        ]
+    }
+
+    fn values(pairs: &[(&str, AttributeValue)]) -> AttributeMap {
+        pairs.iter().map(|(name, value)| (name.to_string(), value.clone())).collect()
+    }
+
+    #[test]
+    fn coerces_values_to_their_canonical_form() {
+        let input = values(&[
+            ("Color", AttributeValue::Text(" red ".to_string())),
+            ("speed", AttributeValue::Number(120.0)),
+            ("plate", AttributeValue::Text(" ABC1234 ".to_string())),
+        ]);
+        let validated = validate_attributes("car", &definitions(), &input, true).unwrap();
+        assert_eq!(validated["color"], AttributeValue::Text("Red".to_string()));
+        assert_eq!(validated["plate"], AttributeValue::Text("ABC1234".to_string()));
+    }
+
+    #[test]
+    fn rejects_wrong_types_ranges_and_unknown_attributes() {
+        let definitions = definitions();
+        let check = |pairs: &[(&str, AttributeValue)]| validate_attributes("car", &definitions, &values(pairs), false);
+        assert!(check(&[("color", AttributeValue::Text("green".to_string()))]).is_err());
+        assert!(check(&[("occluded", AttributeValue::Text("yes".to_string()))]).is_err());
+        assert!(check(&[("speed", AttributeValue::Number(301.0))]).is_err());
+        assert!(check(&[("speed", AttributeValue::Number(f64::NAN))]).is_err());
+        assert!(check(&[("plate", AttributeValue::Text("TOO-LONG-1".to_string()))]).is_err());
+        assert!(check(&[("wheels", AttributeValue::Number(4.0))]).is_err());
+    }
+
+    #[test]
+    fn required_attributes_are_only_enforced_when_complete() {
+        let input = values(&[("occluded", AttributeValue::Bool(true))]);
+        assert!(validate_attributes("car", &definitions(), &input, false).is_ok());
+        assert!(validate_attributes("car", &definitions(), &input, true).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_definitions() {
+        let empty_enum = AttributeDefinition { name: "color".to_string(), kind: AttributeKind::Enum { values: vec![] }, required: false };
+        let duplicate = AttributeDefinition { name: "color".to_string(), kind: AttributeKind::Enum { values: vec!["Red".to_string(), "red".to_string()] }, required: false };
+        let range = AttributeDefinition { name: "speed".to_string(), kind: AttributeKind::Number { min: Some(10.0), max: Some(1.0) }, required: false };
+        assert!(empty_enum.validate().is_err());
+        assert!(duplicate.validate().is_err());
+        assert!(range.validate().is_err());
+        assert!(definitions().iter().all(|definition| definition.validate().is_ok()));
+    }
+}
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::Utc;
+
+// Versão anterior de uma legenda, guardada a cada edição
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CaptionRevision {
+    pub text: String,
+    pub language: Option<String>,
+    pub edited_by: String,
+    pub edited_at: String,
+}
+
+// Legenda candidata (texto livre) de uma imagem; uma imagem pode ter várias
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Caption {
+    pub id: String,
+    pub image_id: String,
+    pub text: String,
+    pub language: Option<String>,
+    pub suggested_by: String,
+    pub suggested_at: String,
+    pub status: String, // "pending", "approved", "rejected"
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<String>,
+    #[serde(default)]
+    pub history: Vec<CaptionRevision>, // da mais antiga para a mais recente
+}
+
+// Restrições das legendas do grupo; tamanhos em caracteres
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct CaptionPolicy {
+    #[serde(default)]
+    pub min_length: Option<usize>,
+    #[serde(default)]
+    pub max_length: Option<usize>,
+    #[serde(default)]
+    pub languages: Vec<String>, // códigos permitidos (por exemplo "pt-br", "en"); vazio aceita qualquer um
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCaptionPolicyRequest {
+    pub policy: CaptionPolicy,
+    pub updated_by: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuggestCaptionRequest {
+    pub text: String,
+    pub language: Option<String>,
+    pub suggested_by: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EditCaptionRequest {
+    pub text: String,
+    pub language: Option<String>, // mantém o idioma atual quando omitido
+    pub edited_by: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CaptionListQuery {
+    pub image_id: Option<String>,
+    pub group_id: Option<String>,
+    pub status: Option<String>,
+    pub language: Option<String>,
+}
+
+// Par imagem-texto das legendas aprovadas, usado na exportação
+#[derive(Debug, Serialize)]
+pub struct ImageTextPair<'a> {
+    pub caption_id: &'a str,
+    pub image_id: &'a str,
+    pub file_name: &'a str,
+    pub text: &'a str,
+    pub language: Option<&'a str>,
+}
+
+impl Caption {
+    pub fn new(image_id: String, text: String, language: Option<String>, suggested_by: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            image_id,
+            text,
+            language,
+            suggested_by,
+            suggested_at: Utc::now().to_rfc3339(),
+            status: "pending".to_string(),
+            reviewed_by: None,
+            reviewed_at: None,
+            history: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::Utc;
+
+// Onde a conversa acontece: na imagem, numa sugestão de tag ou numa tag aprovada
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentTarget {
+    Image,
+    Suggestion,
+    Tag,
+}
+
+// Um tópico é um comentário raiz (parent_id vazio) mais as respostas a ele
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Comment {
+    pub id: String,
+    pub image_id: String,
+    pub target: CommentTarget,
+    pub target_id: String, // o próprio image_id quando target = image
+    pub parent_id: Option<String>,
+    pub author: String,
+    pub body: String,
+    pub mentions: Vec<String>, // usernames citados com @, sem repetição
+    pub created_at: String,
+    pub edited_at: Option<String>,
+    // Resolução vale só para o comentário raiz
+    #[serde(default)]
+    pub resolved: bool,
+    pub resolved_by: Option<String>,
+    pub resolved_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostCommentRequest {
+    pub body: String,
+    pub author: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EditCommentRequest {
+    pub body: String,
+    pub edited_by: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResolveThreadRequest {
+    pub resolved: bool,
+    pub resolved_by: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommentThread<'a> {
+    #[serde(flatten)]
+    pub comment: &'a Comment,
+    pub replies: Vec<&'a Comment>,
+}
+
+impl Comment {
+    pub fn new(
+        image_id: String,
+        target: CommentTarget,
+        target_id: String,
+        parent_id: Option<String>,
+        author: String,
+        body: String,
+        mentions: Vec<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            image_id,
+            target,
+            target_id,
+            parent_id,
+            author,
+            body,
+            mentions,
+            created_at: Utc::now().to_rfc3339(),
+            edited_at: None,
+            resolved: false,
+            resolved_by: None,
+            resolved_at: None,
+        }
+    }
+}
@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::Utc;
 
+use super::{CaptionPolicy, LabelVocabulary, QuestionTemplate, RatingCriterion, TagNormalization};
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Group {
     pub id: String,
@@ -10,6 +12,239 @@ pub struct Group {
     pub created_at: String,
     pub created_by: String,
     pub members: Vec<String>,
+    #[serde(default)]
+    pub metadata_policy: MetadataPolicy,
+    #[serde(default)]
+    pub keypoint_schema: Option<KeypointSchema>,
+    #[serde(default)]
+    pub vocabulary: Option<LabelVocabulary>,
+    #[serde(default)]
+    pub tag_normalization: TagNormalization,
+    #[serde(default)]
+    pub labeling: LabelingPolicy,
+    #[serde(default)]
+    pub caption_policy: CaptionPolicy,
+    #[serde(default)]
+    pub question_templates: Vec<QuestionTemplate>,
+    #[serde(default)]
+    pub rating_criteria: Vec<RatingCriterion>,
+    #[serde(default = "default_reject_reasons")]
+    pub reject_reasons: Vec<String>, // motivos aceitos ao rejeitar uma sugestão de tag
+    #[serde(default)]
+    pub consensus: ConsensusPolicy,
+    #[serde(default)]
+    pub review_quorum: ReviewQuorum,
+}
+
+// Multi: várias classes aprovadas por imagem. Exclusive: exatamente uma (classificação)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelingMode {
+    #[default]
+    Multi,
+    Exclusive,
+}
+
+// O que acontece ao aprovar uma segunda classe em modo exclusivo
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    #[default]
+    Reject,
+    Supersede, // a classe nova substitui a anterior
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct LabelingPolicy {
+    #[serde(default)]
+    pub mode: LabelingMode,
+    #[serde(default)]
+    pub on_conflict: ConflictResolution,
+}
+
+// Aprovação automática de sugestões de tag; cada regra fica desligada quando vazia
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct ConsensusPolicy {
+    #[serde(default)]
+    pub min_suggesters: Option<usize>, // usuários distintos sugerindo o mesmo rótulo na imagem
+    #[serde(default)]
+    pub min_upvotes: Option<usize>, // votos numa sugestão ainda não revisada
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateConsensusPolicyRequest {
+    pub policy: ConsensusPolicy,
+    pub updated_by: String,
+}
+
+// K de N revisores precisam aprovar uma sugestão antes de ela virar tag aprovada.
+// Com reviewers vazio qualquer usuário pode revisar
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReviewQuorum {
+    #[serde(default = "default_approvals_required")]
+    pub approvals_required: usize,
+    #[serde(default)]
+    pub reviewers: Vec<String>,
+}
+
+fn default_approvals_required() -> usize {
+    1
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateReviewQuorumRequest {
+    pub quorum: ReviewQuorum,
+    pub updated_by: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateLabelingPolicyRequest {
+    pub policy: LabelingPolicy,
+    pub updated_by: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImageClasses {
+    pub image_id: String,
+    pub labels: Vec<String>,
+}
+
+// Imagens do grupo sem classe ou com mais de uma classe aprovada
+#[derive(Debug, Serialize)]
+pub struct CompletenessReport {
+    pub group_id: String,
+    pub mode: LabelingMode,
+    pub total_images: usize,
+    pub complete_images: usize,
+    pub unlabeled: Vec<String>,
+    pub multiple_labels: Vec<ImageClasses>,
+}
+
+// Tratamento dos metadados EXIF das imagens enviadas ao grupo
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MetadataPolicy {
+    #[serde(default = "default_true")]
+    pub normalize_orientation: bool,
+    #[serde(default)]
+    pub strip_location: bool,
+    #[serde(default)]
+    pub strip_device: bool,
+    #[serde(default)]
+    pub retain_original: bool, // guarda os metadados removidos em armazenamento restrito (somente admin)
+}
+
+pub(super) fn default_true() -> bool {
+    true
+}
+
+pub const DEFAULT_REJECT_REASONS: [&str; 4] = ["wrong_label", "too_vague", "duplicate", "typo"];
+
+fn default_reject_reasons() -> Vec<String> {
+    DEFAULT_REJECT_REASONS.iter().map(|reason| reason.to_string()).collect()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateRejectReasonsRequest {
+    pub reasons: Vec<String>,
+    pub updated_by: String,
+}
+
+impl Default for ReviewQuorum {
+    fn default() -> Self {
+        Self {
+            approvals_required: default_approvals_required(),
+            reviewers: Vec::new(),
+        }
+    }
+}
+
+impl Default for MetadataPolicy {
+    fn default() -> Self {
+        Self {
+            normalize_orientation: true,
+            strip_location: false,
+            strip_device: false,
+            retain_original: false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateMetadataPolicyRequest {
+    pub policy: MetadataPolicy,
+    pub updated_by: String,
+}
+
+// Esqueleto de pose: nomes dos pontos e ligações entre eles (pares de nomes)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct KeypointSchema {
+    pub name: String,
+    pub keypoints: Vec<String>,
+    #[serde(default)]
+    pub skeleton: Vec<[String; 2]>,
+}
+
+const COCO_KEYPOINTS: [&str; 17] = [
+    "nose", "left_eye", "right_eye", "left_ear", "right_ear",
+    "left_shoulder", "right_shoulder", "left_elbow", "right_elbow",
+    "left_wrist", "right_wrist", "left_hip", "right_hip",
+    "left_knee", "right_knee", "left_ankle", "right_ankle",
+];
+
+// Ligações do esqueleto COCO (índices 1-based da especificação original)
+const COCO_SKELETON: [[usize; 2]; 19] = [
+    [16, 14], [14, 12], [17, 15], [15, 13], [12, 13], [6, 12], [7, 13], [6, 7], [6, 8],
+    [7, 9], [8, 10], [9, 11], [2, 3], [1, 2], [1, 3], [2, 4], [3, 5], [4, 6], [5, 7],
+];
+
+impl KeypointSchema {
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "coco-17" | "coco17" | "coco" => Some(Self {
+                name: "coco-17".to_string(),
+                keypoints: COCO_KEYPOINTS.iter().map(|name| name.to_string()).collect(),
+                skeleton: COCO_SKELETON.iter()
+                    .map(|[a, b]| [COCO_KEYPOINTS[a - 1].to_string(), COCO_KEYPOINTS[b - 1].to_string()])
+                    .collect(),
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Schema name cannot be empty".to_string());
+        }
+        if self.keypoints.is_empty() {
+            return Err("Schema must define at least one keypoint".to_string());
+        }
+        let mut seen = std::collections::HashSet::new();
+        for keypoint in &self.keypoints {
+            if keypoint.trim().is_empty() {
+                return Err("Keypoint names cannot be empty".to_string());
+            }
+            if !seen.insert(keypoint.as_str()) {
+                return Err(format!("Duplicate keypoint '{}'", keypoint));
+            }
+        }
+        for [a, b] in &self.skeleton {
+            if !seen.contains(a.as_str()) || !seen.contains(b.as_str()) {
+                return Err(format!("Skeleton edge '{}'-'{}' references an unknown keypoint", a, b));
+            }
+            if a == b {
+                return Err(format!("Skeleton edge '{}'-'{}' connects a keypoint to itself", a, b));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Informe um preset conhecido (por exemplo "coco-17") ou um schema completo
+#[derive(Debug, Deserialize)]
+pub struct UpdateKeypointSchemaRequest {
+    pub preset: Option<String>,
+    pub schema: Option<KeypointSchema>,
+    pub updated_by: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -39,6 +274,17 @@ impl Group {
             created_at: Utc::now().to_rfc3339(),
             created_by,
             members: vec![created_by_clone],
+            metadata_policy: MetadataPolicy::default(),
+            keypoint_schema: None,
+            vocabulary: None,
+            tag_normalization: TagNormalization::default(),
+            labeling: LabelingPolicy::default(),
+            caption_policy: CaptionPolicy::default(),
+            question_templates: Vec::new(),
+            rating_criteria: Vec::new(),
+            reject_reasons: default_reject_reasons(),
+            consensus: ConsensusPolicy::default(),
+            review_quorum: ReviewQuorum::default(),
         }
     }
 
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::Utc;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryEntity {
+    TagSuggestion,
+    ApprovedTag,
+    TagUpvote,
+}
+
+// Mudança em um registro de anotação da imagem; before/after são cópias completas do registro
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AnnotationEvent {
+    pub id: String,
+    pub image_id: String,
+    pub entity: HistoryEntity,
+    pub entity_id: String,
+    pub action: String, // "suggested", "reviewed", "approved", "removed", "voted", "unvoted", "updated", "copied"
+    pub actor: String,
+    pub at: String,
+    pub before: Option<serde_json::Value>, // None: o registro foi criado
+    pub after: Option<serde_json::Value>,  // None: o registro foi removido
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    pub since: Option<String>,
+    pub until: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StateAtQuery {
+    pub at: String,
+}
+
+// Estado das anotações de tags da imagem reconstruído num instante do passado
+#[derive(Debug, Serialize)]
+pub struct ImageStateAt {
+    pub image_id: String,
+    pub at: String,
+    pub tag_suggestions: Vec<serde_json::Value>,
+    pub approved_tags: Vec<serde_json::Value>,
+    pub tag_upvotes: Vec<serde_json::Value>,
+    pub complete: bool, // false quando o instante é anterior a eventos já descartados do histórico
+}
+
+impl AnnotationEvent {
+    pub fn new(
+        image_id: String,
+        entity: HistoryEntity,
+        entity_id: String,
+        action: &str,
+        actor: String,
+        before: Option<serde_json::Value>,
+        after: Option<serde_json::Value>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            image_id,
+            entity,
+            entity_id,
+            action: action.to_string(),
+            actor,
+            at: Utc::now().to_rfc3339(),
+            before,
+            after,
+        }
+    }
+}
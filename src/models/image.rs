@@ -10,6 +10,102 @@ pub struct Image {
     pub group_id: String,
     pub uploaded_at: String,
     pub uploaded_by: String,
+    #[serde(default)]
+    pub phash: Option<String>,
+    #[serde(default)]
+    pub versions: Vec<ImageVersion>,
+    #[serde(default)]
+    pub restricted_metadata: bool,
+    #[serde(default)]
+    pub width: Option<u32>,
+    #[serde(default)]
+    pub height: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_compacted_at: Option<String>, // instante do evento mais recente descartado do histórico
+}
+
+// Arquivo anterior de uma imagem substituída via PUT /images/{id}/file
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImageVersion {
+    pub filename: String,
+    pub original_name: String,
+    pub phash: Option<String>,
+    pub replaced_at: String,
+    pub replaced_by: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImageListQuery {
+    pub q: Option<String>,
+    pub username: Option<String>,
+    pub group_id: Option<String>,
+    pub uploaded_by: Option<String>,
+    pub uploaded_from: Option<String>,
+    pub uploaded_to: Option<String>,
+    pub has_tags: Option<String>,    // comma separated, all required
+    pub missing_tags: Option<String>, // comma separated, none allowed
+    pub pending_review: Option<bool>,
+    pub min_upvotes: Option<i32>,
+    pub sort: Option<String>,  // "uploaded_at", "name" or "tag_count"
+    pub order: Option<String>, // "asc" or "desc"
+    pub limit: Option<usize>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchImageRequest {
+    pub operation: String, // "move", "copy", "delete" or "suggest_tag"
+    pub image_ids: Vec<String>,
+    pub target_group_id: Option<String>,
+    pub tag: Option<String>,
+    pub performed_by: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchItemResult {
+    pub image_id: String,
+    pub status: String, // "ok", "failed" or "rolled_back"
+    pub new_image_id: Option<String>,
+    pub suggestion_id: Option<String>,
+    pub proposal_id: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DuplicatesQuery {
+    pub group_id: Option<String>,
+    pub max_distance: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergeDuplicatesRequest {
+    pub keep_image_id: String,
+    pub image_ids: Vec<String>,
+    pub merged_by: String,
+    pub max_distance: Option<u32>, // mesma distância usada para listar os grupos de duplicatas
+    #[serde(default)]
+    pub supersede: bool, // em grupo exclusivo, substitui tags de outra classe mesmo com a política "reject"
+}
+
+// Anotações marcadas para conferência depois de uma substituição de arquivo
+#[derive(Debug, Serialize, Default)]
+pub struct FlaggedAnnotations {
+    pub approved_tags: usize,
+    pub tag_suggestions: usize,
+    pub regions: usize,
+    pub keypoints: usize,
+    pub out_of_bounds: usize, // regiões e pontos fora das novas dimensões, marcados mesmo sem flag_for_review
+}
+
+#[derive(Debug, Serialize)]
+pub struct MergeDuplicatesResult {
+    pub kept_image_id: String,
+    pub removed_image_ids: Vec<String>,
+    pub moved_tags: usize,
+    pub merged_tags: usize,
+    pub superseded_tags: Vec<String>,
+    #[serde(skip)]
+    pub removed_files: Vec<String>,
 }
 
 impl Image {
@@ -26,6 +122,31 @@ impl Image {
             group_id,
             uploaded_at: Utc::now().to_rfc3339(),
             uploaded_by,
+            phash: None,
+            versions: Vec::new(),
+            restricted_metadata: false,
+            width: None,
+            height: None,
+            history_compacted_at: None,
+        }
+    }
+
+    // Arquivo atual e de todas as versões anteriores
+    pub fn all_filenames(&self) -> Vec<String> {
+        std::iter::once(self.filename.clone())
+            .chain(self.versions.iter().map(|version| version.filename.clone()))
+            .collect()
+    }
+}
+
+impl ImageVersion {
+    pub fn from_image(image: &Image, replaced_by: String) -> Self {
+        Self {
+            filename: image.filename.clone(),
+            original_name: image.original_name.clone(),
+            phash: image.phash.clone(),
+            replaced_at: Utc::now().to_rfc3339(),
+            replaced_by,
         }
     }
 }
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::Utc;
+
+use super::BoundingBox;
+
+// Visibilidade no padrão COCO: 0 = não anotado, 1 = anotado mas oculto, 2 = visível
+pub const VISIBILITY_UNLABELED: u8 = 0;
+pub const VISIBILITY_VISIBLE: u8 = 2;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Keypoint {
+    pub name: String,
+    #[serde(default)]
+    pub x: f64,
+    #[serde(default)]
+    pub y: f64,
+    pub visibility: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KeypointAnnotation {
+    pub id: String,
+    pub image_id: String,
+    pub schema: String, // nome do schema do grupo no momento da anotação
+    pub keypoints: Vec<Keypoint>, // na ordem do schema, em pixels
+    pub bbox: Option<BoundingBox>, // caixa envolvente dos pontos anotados
+    pub suggested_by: String,
+    pub suggested_at: String,
+    pub status: String, // "pending", "approved", "rejected"
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<String>,
+    #[serde(default)]
+    pub needs_review: bool, // a imagem foi substituída depois da anotação
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SuggestKeypointsRequest {
+    pub keypoints: Vec<Keypoint>,
+    // Quando verdadeiro as coordenadas estão em [0, 1] relativas ao tamanho da imagem
+    #[serde(default)]
+    pub normalized: bool,
+    pub suggested_by: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KeypointListQuery {
+    pub image_id: Option<String>,
+    pub group_id: Option<String>,
+    pub status: Option<String>,
+}
+
+impl KeypointAnnotation {
+    pub fn new(image_id: String, schema: String, keypoints: Vec<Keypoint>, bbox: Option<BoundingBox>, suggested_by: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            image_id,
+            schema,
+            keypoints,
+            bbox,
+            suggested_by,
+            suggested_at: Utc::now().to_rfc3339(),
+            status: "pending".to_string(),
+            reviewed_by: None,
+            reviewed_at: None,
+            needs_review: false,
+        }
+    }
+}
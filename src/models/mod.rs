@@ -3,9 +3,27 @@ pub mod image;
 pub mod group;
 pub mod tag;
 pub mod annotations;
+pub mod region;
+pub mod keypoint;
+pub mod vocabulary;
+pub mod attribute;
+pub mod caption;
+pub mod vqa;
+pub mod rating;
+pub mod comment;
+pub mod history;
 
 pub use user::*;
 pub use image::*;
 pub use group::*;
 pub use tag::*;
 pub use annotations::*;
+pub use region::*;
+pub use keypoint::*;
+pub use vocabulary::*;
+pub use attribute::*;
+pub use caption::*;
+pub use vqa::*;
+pub use rating::*;
+pub use comment::*;
+pub use history::*;
@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::Utc;
+
+pub const DEFAULT_LIKERT_SCALE: u8 = 5;
+
+fn default_scale() -> u8 {
+    DEFAULT_LIKERT_SCALE
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CriterionKind {
+    Pairwise, // qual das duas imagens é melhor no critério
+    Likert {
+        #[serde(default = "default_scale")]
+        scale: u8, // notas de 1 a scale
+    },
+}
+
+// Critério de avaliação do grupo, por exemplo "sharpness" → "Which image is sharper?"
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RatingCriterion {
+    pub id: String, // usado nas rotas
+    pub prompt: String,
+    #[serde(flatten)]
+    pub kind: CriterionKind,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PairwiseComparison {
+    pub id: String,
+    pub group_id: String,
+    pub criterion: String,
+    pub image_a: String,
+    pub image_b: String,
+    pub winner_id: Option<String>, // None = empate
+    pub annotator: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LikertRating {
+    pub id: String,
+    pub group_id: String,
+    pub criterion: String,
+    pub image_id: String,
+    pub score: u8,
+    pub annotator: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateRatingCriteriaRequest {
+    pub criteria: Vec<RatingCriterion>,
+    pub updated_by: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NextItemQuery {
+    pub annotator: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ComparePairRequest {
+    pub image_a: String,
+    pub image_b: String,
+    pub winner: String, // "a", "b" or "tie"
+    pub annotator: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RateImageRequest {
+    pub image_id: String,
+    pub score: u8,
+    pub annotator: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PairwiseScore {
+    pub image_id: String,
+    pub score: f64, // log da força de Bradley–Terry (0 = média)
+    pub wins: f64, // empates contam meia vitória
+    pub comparisons: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LikertScore {
+    pub image_id: String,
+    pub mean: f64,
+    pub median: f64,
+    pub ratings: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", content = "scores", rename_all = "snake_case")]
+pub enum ScoreTable {
+    Pairwise(Vec<PairwiseScore>),
+    Likert(Vec<LikertScore>),
+}
+
+#[derive(Debug, Serialize)]
+pub struct CriterionScores {
+    pub group_id: String,
+    pub criterion: String,
+    #[serde(flatten)]
+    pub table: ScoreTable,
+}
+
+impl PairwiseComparison {
+    pub fn new(group_id: String, criterion: String, image_a: String, image_b: String, winner_id: Option<String>, annotator: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            group_id,
+            criterion,
+            image_a,
+            image_b,
+            winner_id,
+            annotator,
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+impl LikertRating {
+    pub fn new(group_id: String, criterion: String, image_id: String, score: u8, annotator: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            group_id,
+            criterion,
+            image_id,
+            score,
+            annotator,
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::Utc;
+
+// Caixa delimitadora em pixels, com origem no canto superior esquerdo
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RegionKind {
+    #[default]
+    Box,
+    Polygon,
+    Mask,
+}
+
+// Máscara RLE não comprimida no formato COCO: size = [altura, largura], contagens em ordem de coluna
+// alternando fundo e primeiro plano, começando pelo fundo
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RleMask {
+    pub size: [u32; 2],
+    pub counts: Vec<u32>,
+}
+
+// Anel de pontos [x, y]
+pub type Polygon = Vec<[f64; 2]>;
+
+// Geometria detalhada, guardada fora do data.json em annotations/<id>.json
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RegionGeometry {
+    Polygon { polygons: Vec<Polygon> },
+    Mask(RleMask),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RegionAnnotation {
+    pub id: String,
+    pub image_id: String,
+    pub label: String,
+    #[serde(default)]
+    pub kind: RegionKind,
+    // Para polígonos e máscaras é a caixa envolvente da geometria
+    pub bbox: BoundingBox,
+    #[serde(default)]
+    pub area: f64,
+    pub suggested_by: String,
+    pub suggested_at: String,
+    pub status: String, // "pending", "approved", "rejected"
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<String>,
+    #[serde(default)]
+    pub needs_review: bool, // a imagem foi substituída depois da anotação
+}
+
+// Informe exatamente um entre bbox, polygon e mask
+#[derive(Debug, Deserialize)]
+pub struct SuggestRegionRequest {
+    pub label: String,
+    pub bbox: Option<BoundingBox>,
+    pub polygon: Option<Vec<Polygon>>,
+    pub mask: Option<RleMask>,
+    // Quando verdadeiro as coordenadas de bbox e polygon estão em [0, 1] relativas ao tamanho da imagem
+    #[serde(default)]
+    pub normalized: bool,
+    pub suggested_by: String,
+}
+
+// Revisão de regiões, pontos-chave e legendas
+#[derive(Debug, Deserialize)]
+pub struct ReviewAnnotationRequest {
+    pub status: String, // "approved" or "rejected"
+    pub reviewed_by: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegionListQuery {
+    pub image_id: Option<String>,
+    pub group_id: Option<String>,
+    pub status: Option<String>,
+    pub label: Option<String>,
+    pub kind: Option<RegionKind>,
+    #[serde(default)]
+    pub include_geometry: bool,
+}
+
+impl RegionAnnotation {
+    pub fn new(image_id: String, label: String, kind: RegionKind, bbox: BoundingBox, area: f64, suggested_by: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            image_id,
+            label,
+            kind,
+            bbox,
+            area,
+            suggested_by,
+            suggested_at: Utc::now().to_rfc3339(),
+            status: "pending".to_string(),
+            reviewed_by: None,
+            reviewed_at: None,
+            needs_review: false,
+        }
+    }
+}
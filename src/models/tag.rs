@@ -1,7 +1,23 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 use chrono::Utc;
 
+use super::AttributeMap;
+
+// Ciclo de vida da sugestão: pending → approved/rejected → reopened → approved/rejected …
+// "superseded" só é atribuído pelo sistema (modo exclusivo) e sai dele reabrindo a sugestão
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionStatus {
+    #[default]
+    Pending,
+    Approved,
+    Rejected,
+    Reopened,
+    Superseded,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TagSuggestion {
     pub id: String,
@@ -9,9 +25,35 @@ pub struct TagSuggestion {
     pub tag: String,
     pub suggested_by: String,
     pub suggested_at: String,
-    pub status: String, // "pending", "approved", "rejected"
+    pub status: SuggestionStatus,
     pub reviewed_by: Option<String>,
     pub reviewed_at: Option<String>,
+    #[serde(default, skip_serializing_if = "AttributeMap::is_empty")]
+    pub attributes: AttributeMap,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reject_reason: Option<String>, // um dos reject_reasons do grupo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review_note: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quorum: Option<QuorumState>, // aprovações já recebidas quando o grupo exige mais de um revisor
+    #[serde(default)]
+    pub needs_review: bool, // a imagem foi substituída depois da sugestão
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approved_with: Option<String>, // sugestão principal de uma aprovação por consenso; acompanha a revisão dela
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReviewApproval {
+    pub reviewer: String,
+    pub approved_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct QuorumState {
+    pub required: usize,
+    pub approvals: Vec<ReviewApproval>,
+    #[serde(default, skip_serializing_if = "AttributeMap::is_empty")]
+    pub attributes: AttributeMap, // atributos validados na aprovação mais recente; vão para a tag ao fechar o quórum
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -22,6 +64,12 @@ pub struct ApprovedTag {
     pub approved_by: String,
     pub approved_at: String,
     pub upvotes: i32,
+    #[serde(default)]
+    pub needs_review: bool,
+    #[serde(default, skip_serializing_if = "AttributeMap::is_empty")]
+    pub attributes: AttributeMap,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion_id: Option<String>, // sugestão que originou a tag
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -36,12 +84,56 @@ pub struct TagUpvote {
 pub struct SuggestTagRequest {
     pub tag: String,
     pub suggested_by: String,
+    #[serde(default)]
+    pub attributes: AttributeMap,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ReviewTagRequest {
-    pub status: String, // "approved" or "rejected"
+    pub status: SuggestionStatus, // "approved", "rejected" ou "reopened"
     pub reviewed_by: String,
+    #[serde(default)]
+    pub attributes: Option<AttributeMap>, // substitui os atributos da sugestão na aprovação
+    #[serde(default)]
+    pub supersede: bool, // em grupos exclusivos, substitui a classe aprovada mesmo com on_conflict = reject
+    #[serde(default)]
+    pub reason: Option<String>, // obrigatório ao rejeitar
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserSuggestionsQuery {
+    pub status: Option<SuggestionStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnnotatorRejectStats {
+    pub annotator: String,
+    pub suggestions: usize,
+    pub approved: usize,
+    pub rejected: usize,
+    pub rejection_rate: f64, // rejeitadas / (aprovadas + rejeitadas)
+    pub reasons: BTreeMap<String, usize>,
+}
+
+// Motivos de rejeição do grupo, no total e por autor das sugestões
+#[derive(Debug, Serialize)]
+pub struct RejectStatsReport {
+    pub group_id: String,
+    pub reasons: BTreeMap<String, usize>,
+    pub annotators: Vec<AnnotatorRejectStats>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConfirmTagRequest {
+    pub confirmed_by: String,
+}
+
+// Quem removeu fica no histórico da imagem; opcional para manter a rota compatível
+#[derive(Debug, Deserialize)]
+pub struct DeleteTagQuery {
+    pub requested_by: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -57,9 +149,15 @@ impl TagSuggestion {
             tag,
             suggested_by,
             suggested_at: Utc::now().to_rfc3339(),
-            status: "pending".to_string(),
+            status: SuggestionStatus::Pending,
             reviewed_by: None,
             reviewed_at: None,
+            attributes: AttributeMap::new(),
+            reject_reason: None,
+            review_note: None,
+            quorum: None,
+            needs_review: false,
+            approved_with: None,
         }
     }
 }
@@ -77,6 +175,9 @@ impl ApprovedTag {
             approved_by,
             approved_at: Utc::now().to_rfc3339(),
             upvotes: 0,
+            needs_review: false,
+            attributes: AttributeMap::new(),
+            suggestion_id: None,
         }
     }
 }
@@ -14,6 +14,11 @@ pub struct LoginResponse {
     pub message: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AdminQuery {
+    pub requested_by: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct User {
     pub username: String,
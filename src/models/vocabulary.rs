@@ -0,0 +1,406 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::Utc;
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+use super::AttributeDefinition;
+
+pub fn collapse_whitespace(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub fn strip_accents(value: &str) -> String {
+    value.nfd().filter(|c| !is_combining_mark(*c)).nfc().collect()
+}
+
+// Chave de comparação de rótulos: sem acentos, minúsculas e espaços colapsados
+pub fn label_key(value: &str) -> String {
+    strip_accents(&collapse_whitespace(value)).to_lowercase()
+}
+
+// Regras de plural do português e do inglês, aplicadas na ordem (heurística; aliases cobrem as exceções).
+// Só entram terminações sem colisão com o inglês: "-res"/"-zes"/"-ns" em geral estragariam pictures, sizes, pens
+const PLURAL_RULES: [(&str, &str); 12] = [
+    ("ões", "ão"), ("ães", "ão"), ("ais", "al"), ("éis", "el"), ("óis", "ol"),
+    ("gens", "gem"), ("lheres", "lher"), ("uzes", "uz"), ("ozes", "oz"),
+    ("sses", "ss"), ("ies", "y"), ("xes", "x"),
+];
+
+// Substantivos comuns cujo singular termina em "ie"; para eles "-ies" vira "-ie", não "-y"
+const IE_PLURALS: [&str; 18] = [
+    "movies", "cookies", "pies", "ties", "lies", "zombies", "selfies", "hoodies", "brownies",
+    "calories", "smoothies", "veggies", "rookies", "goalies", "hippies", "prairies", "birdies", "magpies",
+];
+
+// Plurais comuns do português em "-res"/"-zes"/"-ns"/"-ães" que as regras acima não alcançam
+const PORTUGUESE_PLURALS: [(&str, &str); 18] = [
+    ("flores", "flor"), ("cores", "cor"), ("mares", "mar"), ("lugares", "lugar"), ("motores", "motor"),
+    ("luzes", "luz"), ("rapazes", "rapaz"), ("vezes", "vez"),
+    ("homens", "homem"), ("nuvens", "nuvem"), ("jardins", "jardim"), ("trens", "trem"), ("bens", "bem"),
+    ("sons", "som"), ("atuns", "atum"),
+    ("mães", "mãe"), ("alemães", "alemão"), ("capitães", "capitão"),
+];
+
+// Palavras que terminam em "s" também no singular
+const INVARIANT_WORDS: [&str; 14] = [
+    "series", "species", "news", "lens", "atlas",
+    "país", "lápis", "ônibus", "vírus", "tênis", "pires", "óculos", "gás", "mês",
+];
+
+fn singularize(word: &str) -> String {
+    let lower = word.to_lowercase();
+    if lower.chars().count() <= 3 || lower.len() != word.len() || INVARIANT_WORDS.contains(&lower.as_str()) {
+        return word.to_string();
+    }
+    if IE_PLURALS.contains(&lower.as_str()) {
+        return word[..word.len() - 1].to_string();
+    }
+    if let Some((_, singular)) = PORTUGUESE_PLURALS.iter().find(|(plural, _)| *plural == lower) {
+        // Mantém a caixa do trecho em comum ("Flores" → "Flor")
+        let mut shared = lower.bytes().zip(singular.bytes()).take_while(|(a, b)| a == b).count();
+        while !word.is_char_boundary(shared) {
+            shared -= 1;
+        }
+        return format!("{}{}", &word[..shared], &singular[shared..]);
+    }
+    for (plural, singular) in PLURAL_RULES {
+        if lower.ends_with(plural) && word.is_char_boundary(word.len() - plural.len()) {
+            return format!("{}{}", &word[..word.len() - plural.len()], singular);
+        }
+    }
+    if lower.ends_with('s') && !lower.ends_with("ss") && !lower.ends_with("us") && !lower.ends_with("is") {
+        return word[..word.len() - 1].to_string();
+    }
+    word.to_string()
+}
+
+// Normalização aplicada às tags sugeridas no grupo
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct TagNormalization {
+    #[serde(default)]
+    pub lowercase: bool,
+    #[serde(default)]
+    pub strip_accents: bool,
+    #[serde(default)]
+    pub singularize: bool,
+}
+
+impl TagNormalization {
+    pub fn apply(&self, tag: &str) -> String {
+        let mut tag = collapse_whitespace(tag);
+        // Plural antes dos acentos: as regras dependem de "ões", "éis" etc.
+        if self.singularize {
+            tag = tag.split(' ').map(singularize).collect::<Vec<_>>().join(" ");
+        }
+        if self.strip_accents {
+            tag = strip_accents(&tag);
+        }
+        if self.lowercase {
+            tag = tag.to_lowercase();
+        }
+        tag
+    }
+}
+
+// Variante de tag mapeada para o rótulo canônico; sem grupo vale para todos
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TagAlias {
+    pub id: String,
+    pub alias: String,
+    pub canonical: String,
+    pub group_id: Option<String>,
+    pub created_by: String,
+    pub created_at: String,
+}
+
+impl TagAlias {
+    pub fn new(alias: String, canonical: String, group_id: Option<String>, created_by: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            alias,
+            canonical,
+            group_id,
+            created_by,
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTagAliasRequest {
+    pub alias: String,
+    pub canonical: String,
+    pub group_id: Option<String>,
+    pub created_by: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TagAliasQuery {
+    pub group_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTagNormalizationRequest {
+    pub normalization: TagNormalization,
+    pub updated_by: String,
+}
+
+// Renomeia/mescla as tags `sources` em `target` (em um grupo ou no dataset inteiro)
+#[derive(Debug, Deserialize)]
+pub struct MergeTagsRequest {
+    pub sources: Vec<String>,
+    pub target: String,
+    pub group_id: Option<String>,
+    #[serde(default)]
+    pub preview: bool,
+    #[serde(default = "super::group::default_true")]
+    pub create_aliases: bool,
+    pub performed_by: String,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct MergeTagsReport {
+    pub preview: bool,
+    pub target: String,
+    pub images_affected: usize,
+    pub suggestions_renamed: usize,
+    pub suggestions_merged: usize,
+    pub approved_renamed: usize,
+    pub approved_merged: usize,
+    pub upvotes_moved: usize,
+    pub upvotes_dropped: usize,
+    pub vocabularies_updated: usize,
+    pub aliases_created: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LabelDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    // Rótulo pai na hierarquia (por exemplo husky → dog → mammal → animal)
+    #[serde(default)]
+    pub parent: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeDefinition>,
+}
+
+// O que fazer com rótulos fora do vocabulário
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum UnknownLabelMode {
+    #[default]
+    Reject,
+    Propose, // vira uma proposta de novo rótulo para os admins
+}
+
+// Vocabulário controlado de rótulos de um grupo
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LabelVocabulary {
+    pub labels: Vec<LabelDefinition>,
+    #[serde(default)]
+    pub unknown_labels: UnknownLabelMode,
+}
+
+impl LabelVocabulary {
+    pub fn find(&self, label: &str) -> Option<&LabelDefinition> {
+        let key = label_key(label);
+        self.labels.iter().find(|definition| label_key(&definition.name) == key)
+    }
+
+    // Ancestrais do rótulo, do pai até a raiz, com os nomes canônicos
+    pub fn ancestors(&self, label: &str) -> Vec<&str> {
+        let mut ancestors = Vec::new();
+        let mut current = self.find(label);
+        while let Some(parent) = current.and_then(|definition| definition.parent.as_deref()).and_then(|parent| self.find(parent)) {
+            // Proteção contra ciclos em vocabulários salvos antes da validação
+            if ancestors.len() >= self.labels.len() {
+                break;
+            }
+            ancestors.push(parent.name.as_str());
+            current = Some(parent);
+        }
+        ancestors
+    }
+
+    // Profundidade na árvore (raízes = 0); None para rótulos fora do vocabulário
+    pub fn depth(&self, label: &str) -> Option<usize> {
+        self.find(label).map(|_| self.ancestors(label).len())
+    }
+
+    // Rótulo equivalente na profundidade pedida; rótulos mais rasos são mantidos
+    pub fn label_at_depth(&self, label: &str, depth: usize) -> String {
+        let Some(definition) = self.find(label) else {
+            return label.to_string();
+        };
+        let ancestors = self.ancestors(label);
+        if depth >= ancestors.len() {
+            return definition.name.clone();
+        }
+        ancestors[ancestors.len() - 1 - depth].to_string()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let mut seen = std::collections::HashSet::new();
+        for definition in &self.labels {
+            let name = definition.name.trim();
+            if name.is_empty() {
+                return Err("Label names cannot be empty".to_string());
+            }
+            if !seen.insert(label_key(name)) {
+                return Err(format!("Duplicate label '{}'", name));
+            }
+            let mut attribute_names = std::collections::HashSet::new();
+            for attribute in &definition.attributes {
+                attribute.validate()?;
+                if !attribute_names.insert(attribute.name.trim().to_lowercase()) {
+                    return Err(format!("Duplicate attribute '{}' on label '{}'", attribute.name, name));
+                }
+            }
+        }
+        for definition in &self.labels {
+            let Some(parent) = &definition.parent else {
+                continue;
+            };
+            if self.find(parent).is_none() {
+                return Err(format!("Parent '{}' of label '{}' is not in the vocabulary", parent, definition.name));
+            }
+            // Percorre a cadeia de pais; mais passos que rótulos indica ciclo
+            let mut current = Some(parent.as_str());
+            let mut steps = 0;
+            while let Some(name) = current {
+                if label_key(name) == label_key(&definition.name) || steps > self.labels.len() {
+                    return Err(format!("Label '{}' is part of a parent cycle", definition.name));
+                }
+                current = self.find(name).and_then(|parent| parent.parent.as_deref());
+                steps += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LabelProposal {
+    pub id: String,
+    pub group_id: String,
+    pub label: String,
+    pub image_id: String, // imagem em que o rótulo foi sugerido; recebe a sugestão se a proposta for aceita
+    pub proposed_by: String,
+    pub proposed_at: String,
+    pub status: String, // "pending", "accepted", "rejected"
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateVocabularyRequest {
+    pub vocabulary: LabelVocabulary,
+    pub updated_by: String,
+}
+
+// Contagem de imagens por rótulo, com e sem os descendentes
+#[derive(Debug, Serialize)]
+pub struct LabelStats {
+    pub label: String,
+    pub path: String, // ancestrais separados por "/", da raiz ao rótulo
+    pub parent: Option<String>,
+    pub depth: Option<usize>,
+    pub in_vocabulary: bool,
+    pub direct_images: usize,
+    pub total_images: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LabelProposalQuery {
+    pub group_id: Option<String>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReviewLabelProposalRequest {
+    pub status: String, // "accepted" or "rejected"
+    pub reviewed_by: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub parent: Option<String>, // pai do novo rótulo quando a proposta é aceita
+}
+
+impl LabelProposal {
+    pub fn new(group_id: String, label: String, image_id: String, proposed_by: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            group_id,
+            label,
+            image_id,
+            proposed_by,
+            proposed_at: Utc::now().to_rfc3339(),
+            status: "pending".to_string(),
+            reviewed_by: None,
+            reviewed_at: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn singularizes_portuguese_plurals() {
+        assert_eq!(singularize("botões"), "botão");
+        assert_eq!(singularize("pães"), "pão");
+        assert_eq!(singularize("animais"), "animal");
+        assert_eq!(singularize("papéis"), "papel");
+        assert_eq!(singularize("faróis"), "farol");
+        assert_eq!(singularize("carros"), "carro");
+        assert_eq!(singularize("flores"), "flor");
+        assert_eq!(singularize("cores"), "cor");
+        assert_eq!(singularize("Luzes"), "Luz");
+        assert_eq!(singularize("vozes"), "voz");
+        assert_eq!(singularize("homens"), "homem");
+        assert_eq!(singularize("imagens"), "imagem");
+        assert_eq!(singularize("mulheres"), "mulher");
+        assert_eq!(singularize("mães"), "mãe");
+        assert_eq!(singularize("cães"), "cão");
+    }
+
+    #[test]
+    fn keeps_invariant_words_intact() {
+        for word in ["series", "species", "news", "país", "lápis", "ônibus", "tênis", "óculos"] {
+            assert_eq!(singularize(word), word);
+        }
+    }
+
+    #[test]
+    fn singularizes_english_plurals() {
+        assert_eq!(singularize("cities"), "city");
+        assert_eq!(singularize("boxes"), "box");
+        assert_eq!(singularize("glasses"), "glass");
+        assert_eq!(singularize("dogs"), "dog");
+    }
+
+    #[test]
+    fn keeps_common_english_words_intact() {
+        assert_eq!(singularize("pictures"), "picture");
+        assert_eq!(singularize("tires"), "tire");
+        assert_eq!(singularize("sizes"), "size");
+        assert_eq!(singularize("movies"), "movie");
+        assert_eq!(singularize("Cookies"), "Cookie");
+        assert_eq!(singularize("bus"), "bus");
+        assert_eq!(singularize("status"), "status");
+        assert_eq!(singularize("analysis"), "analysis");
+        assert_eq!(singularize("pens"), "pen");
+        assert_eq!(singularize("lions"), "lion");
+        assert_eq!(singularize("stores"), "store");
+    }
+
+    #[test]
+    fn normalization_applies_every_word() {
+        let normalization = TagNormalization { lowercase: true, strip_accents: true, singularize: true };
+        assert_eq!(normalization.apply("  Cães   Pretos "), "cao preto");
+        assert_eq!(normalization.apply("Movie Pictures"), "movie picture");
+    }
+}
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::Utc;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AnswerType {
+    #[default]
+    Text,
+    YesNo,
+    Number,
+    Choice { options: Vec<String> },
+}
+
+// Pergunta pré-definida do grupo, oferecida para todas as imagens
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct QuestionTemplate {
+    #[serde(default)]
+    pub id: String, // gerado quando omitido
+    pub question: String,
+    #[serde(default)]
+    pub answer_type: AnswerType,
+}
+
+// Pergunta feita sobre uma imagem; o texto e o tipo são copiados do modelo para sobreviver a mudanças nele
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VqaQuestion {
+    pub id: String,
+    pub image_id: String,
+    pub template_id: Option<String>,
+    pub question: String,
+    pub answer_type: AnswerType,
+    pub asked_by: String,
+    pub asked_at: String,
+    pub status: String, // "open", "resolved"
+    pub final_answer: Option<String>, // resposta escolhida na adjudicação
+    pub adjudicated_by: Option<String>,
+    pub adjudicated_at: Option<String>,
+}
+
+// Resposta de um anotador; cada anotador tem no máximo uma resposta por pergunta
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VqaAnswer {
+    pub id: String,
+    pub question_id: String,
+    pub answer: String,
+    pub answered_by: String,
+    pub answered_at: String,
+    pub status: String, // "pending", "accepted", "rejected"
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateQuestionTemplatesRequest {
+    pub templates: Vec<QuestionTemplate>,
+    pub updated_by: String,
+}
+
+// Informe template_id ou uma pergunta livre (com answer_type opcional)
+#[derive(Debug, Deserialize)]
+pub struct AskQuestionRequest {
+    pub template_id: Option<String>,
+    pub question: Option<String>,
+    pub answer_type: Option<AnswerType>,
+    pub asked_by: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AnswerQuestionRequest {
+    pub answer: String,
+    pub answered_by: String,
+}
+
+// O revisor escolhe uma resposta existente (answer_id) ou informa a resposta final
+#[derive(Debug, Deserialize)]
+pub struct AdjudicateQuestionRequest {
+    pub answer_id: Option<String>,
+    pub answer: Option<String>,
+    pub adjudicated_by: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QuestionListQuery {
+    pub image_id: Option<String>,
+    pub group_id: Option<String>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QuestionView<'a> {
+    #[serde(flatten)]
+    pub question: &'a VqaQuestion,
+    pub answers: Vec<&'a VqaAnswer>,
+}
+
+impl VqaQuestion {
+    pub fn new(image_id: String, template_id: Option<String>, question: String, answer_type: AnswerType, asked_by: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            image_id,
+            template_id,
+            question,
+            answer_type,
+            asked_by,
+            asked_at: Utc::now().to_rfc3339(),
+            status: "open".to_string(),
+            final_answer: None,
+            adjudicated_by: None,
+            adjudicated_at: None,
+        }
+    }
+}
+
+impl VqaAnswer {
+    pub fn new(question_id: String, answer: String, answered_by: String) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            question_id,
+            answer,
+            answered_by,
+            answered_at: Utc::now().to_rfc3339(),
+            status: "pending".to_string(),
+        }
+    }
+}
@@ -0,0 +1,284 @@
+use std::io::{Cursor, Read};
+use std::path::{Component, Path};
+use flate2::read::GzDecoder;
+
+use super::ingest_service::is_supported_image;
+
+// Limites contra zip bombs e arquivos gigantes. As entradas são entregues uma a uma,
+// então a memória fica no arquivo enviado mais uma entrada; o total conta bytes realmente descompactados
+pub const MAX_ARCHIVE_BYTES: usize = 256 * 1024 * 1024;
+pub const MAX_ARCHIVE_ENTRIES: usize = 10_000;
+pub const MAX_ENTRY_BYTES: u64 = 50 * 1024 * 1024;
+pub const MAX_TOTAL_EXTRACTED_BYTES: u64 = 1024 * 1024 * 1024;
+pub const MAX_COMPRESSION_RATIO: u64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArchiveKind {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveKind {
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        let lower = name.to_lowercase();
+        if lower.ends_with(".zip") {
+            Some(ArchiveKind::Zip)
+        } else if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+            Some(ArchiveKind::TarGz)
+        } else if lower.ends_with(".tar") {
+            Some(ArchiveKind::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ArchiveEntry {
+    pub path: String,
+    pub file_name: String,
+    pub folder: Option<String>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct SkippedEntry {
+    pub path: String,
+    pub reason: String,
+}
+
+// Contabiliza o total extraído e rejeita entradas suspeitas
+struct ExtractionBudget {
+    entries_seen: usize,
+    total_bytes: u64,
+    max_total_bytes: u64, // o menor entre o limite fixo e a taxa de compressão máxima sobre o arquivo enviado
+}
+
+impl ExtractionBudget {
+    fn new(archive_bytes: usize) -> Self {
+        let by_ratio = (archive_bytes as u64).max(1).saturating_mul(MAX_COMPRESSION_RATIO);
+        Self { entries_seen: 0, total_bytes: 0, max_total_bytes: by_ratio.min(MAX_TOTAL_EXTRACTED_BYTES) }
+    }
+
+    fn next_entry(&mut self) -> Result<(), String> {
+        self.entries_seen += 1;
+        if self.entries_seen > MAX_ARCHIVE_ENTRIES {
+            return Err(format!("Archive has more than {} entries", MAX_ARCHIVE_ENTRIES));
+        }
+        Ok(())
+    }
+
+    fn read_entry<R: Read>(&mut self, reader: R) -> Result<Vec<u8>, String> {
+        let mut data = Vec::new();
+        reader
+            .take(MAX_ENTRY_BYTES + 1)
+            .read_to_end(&mut data)
+            .map_err(|e| format!("Failed to read entry: {}", e))?;
+
+        if data.len() as u64 > MAX_ENTRY_BYTES {
+            return Err(format!("Entry exceeds {} bytes", MAX_ENTRY_BYTES));
+        }
+
+        self.total_bytes += data.len() as u64;
+        if self.total_bytes > self.max_total_bytes {
+            return Err(format!("Archive expands to more than {} bytes", self.max_total_bytes));
+        }
+        Ok(data)
+    }
+}
+
+// Normaliza o caminho da entrada, rejeitando caminhos absolutos e "..".
+// Retorna (caminho relativo, nome do arquivo, pasta imediata)
+pub fn sanitize_entry_path(raw: &str) -> Option<(String, String, Option<String>)> {
+    let raw = raw.replace('\\', "/");
+    let mut parts = Vec::new();
+    for component in Path::new(&raw).components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_str()?.to_string()),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+
+    let file_name = parts.pop()?;
+    let folder = parts.last().cloned();
+    parts.push(file_name.clone());
+    Some((parts.join("/"), file_name, folder))
+}
+
+// Entradas de metadados (macOS, arquivos ocultos) não são imagens do dataset
+fn is_hidden_entry(path: &str) -> bool {
+    path.split('/').any(|part| part.starts_with('.') || part == "__MACOSX")
+}
+
+// Entrega cada imagem a on_entry assim que é lida e devolve as entradas ignoradas.
+// Em caso de erro as entradas já entregues continuam com quem as recebeu
+pub fn extract_archive(kind: ArchiveKind, bytes: &[u8], on_entry: impl FnMut(ArchiveEntry)) -> Result<Vec<SkippedEntry>, String> {
+    let budget = ExtractionBudget::new(bytes.len());
+    match kind {
+        ArchiveKind::Zip => extract_zip(bytes, budget, on_entry),
+        ArchiveKind::Tar => extract_tar(Cursor::new(bytes), budget, on_entry),
+        ArchiveKind::TarGz => extract_tar(GzDecoder::new(Cursor::new(bytes)), budget, on_entry),
+    }
+}
+
+fn extract_zip(bytes: &[u8], mut budget: ExtractionBudget, mut on_entry: impl FnMut(ArchiveEntry)) -> Result<Vec<SkippedEntry>, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| format!("Invalid ZIP archive: {}", e))?;
+    let mut skipped = Vec::new();
+
+    for index in 0..archive.len() {
+        budget.next_entry()?;
+        let entry = archive
+            .by_index(index)
+            .map_err(|e| format!("Invalid ZIP entry: {}", e))?;
+        if entry.is_dir() {
+            continue;
+        }
+
+        let raw_name = entry.name().to_string();
+        let Some((path, file_name, folder)) = sanitize_entry_path(&raw_name) else {
+            skipped.push(SkippedEntry { path: raw_name, reason: "Unsafe path".to_string() });
+            continue;
+        };
+        if is_hidden_entry(&path) {
+            continue;
+        }
+        if !is_supported_image(&file_name) {
+            skipped.push(SkippedEntry { path, reason: "Unsupported file type".to_string() });
+            continue;
+        }
+        if entry.size() > MAX_ENTRY_BYTES {
+            skipped.push(SkippedEntry { path, reason: format!("Entry exceeds {} bytes", MAX_ENTRY_BYTES) });
+            continue;
+        }
+        // O tamanho no cabeçalho pode mentir: a taxa é conferida com os bytes realmente lidos
+        let compressed_size = entry.compressed_size().max(1);
+        let data = budget.read_entry(entry)?;
+        if data.len() as u64 > compressed_size * MAX_COMPRESSION_RATIO {
+            return Err(format!("Entry '{}' has a suspicious compression ratio", path));
+        }
+        on_entry(ArchiveEntry { path, file_name, folder, data });
+    }
+
+    Ok(skipped)
+}
+
+fn extract_tar<R: Read>(reader: R, mut budget: ExtractionBudget, mut on_entry: impl FnMut(ArchiveEntry)) -> Result<Vec<SkippedEntry>, String> {
+    let mut archive = tar::Archive::new(reader);
+    let mut skipped = Vec::new();
+
+    let entries = archive.entries().map_err(|e| format!("Invalid TAR archive: {}", e))?;
+    for entry in entries {
+        budget.next_entry()?;
+        let entry = entry.map_err(|e| format!("Invalid TAR entry: {}", e))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let raw_name = String::from_utf8_lossy(&entry.path_bytes()).into_owned();
+        let Some((path, file_name, folder)) = sanitize_entry_path(&raw_name) else {
+            skipped.push(SkippedEntry { path: raw_name, reason: "Unsafe path".to_string() });
+            continue;
+        };
+        if is_hidden_entry(&path) {
+            continue;
+        }
+        if !is_supported_image(&file_name) {
+            skipped.push(SkippedEntry { path, reason: "Unsupported file type".to_string() });
+            continue;
+        }
+        if entry.size() > MAX_ENTRY_BYTES {
+            skipped.push(SkippedEntry { path, reason: format!("Entry exceeds {} bytes", MAX_ENTRY_BYTES) });
+            continue;
+        }
+
+        let data = budget.read_entry(entry)?;
+        on_entry(ArchiveEntry { path, file_name, folder, data });
+    }
+
+    Ok(skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use flate2::{write::GzEncoder, Compression};
+
+    fn tar_bytes(files: &[(&str, Vec<u8>)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, data) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, data.as_slice()).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn collect(kind: ArchiveKind, bytes: &[u8]) -> Result<(Vec<ArchiveEntry>, Vec<SkippedEntry>), String> {
+        let mut entries = Vec::new();
+        let skipped = extract_archive(kind, bytes, |entry| entries.push(entry))?;
+        Ok((entries, skipped))
+    }
+
+    #[test]
+    fn rejects_unsafe_entry_paths() {
+        assert_eq!(sanitize_entry_path("cats/a.png"), Some(("cats/a.png".to_string(), "a.png".to_string(), Some("cats".to_string()))));
+        assert_eq!(sanitize_entry_path("../a.png"), None);
+        assert_eq!(sanitize_entry_path("/etc/a.png"), None);
+        assert_eq!(sanitize_entry_path("cats\\..\\..\\a.png"), None);
+    }
+
+    #[test]
+    fn tar_entries_are_delivered_and_other_files_skipped() {
+        let bytes = tar_bytes(&[
+            ("cats/a.png", vec![1; 64]),
+            ("notes.txt", vec![2; 16]),
+            ("__MACOSX/cats/._a.png", vec![3; 16]),
+        ]);
+        let (entries, skipped) = collect(ArchiveKind::Tar, &bytes).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].folder.as_deref(), Some("cats"));
+        assert_eq!(entries[0].data.len(), 64);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].path, "notes.txt");
+    }
+
+    #[test]
+    fn zip_ratio_is_checked_on_decompressed_bytes() {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        writer.start_file("bomb.png", options).unwrap();
+        writer.write_all(&vec![0; 1024 * 1024]).unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        let error = collect(ArchiveKind::Zip, &bytes).unwrap_err();
+        assert!(error.contains("compression ratio") || error.contains("expands"), "{}", error);
+    }
+
+    #[test]
+    fn tar_gz_total_is_bounded_by_the_compression_ratio() {
+        let tar = tar_bytes(&[("a.png", vec![0; 2 * 1024 * 1024]), ("b.png", vec![0; 2 * 1024 * 1024])]);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&tar).unwrap();
+        let bytes = encoder.finish().unwrap();
+
+        let error = collect(ArchiveKind::TarGz, &bytes).unwrap_err();
+        assert!(error.contains("expands"), "{}", error);
+    }
+
+    #[test]
+    fn compressible_but_reasonable_tar_gz_is_accepted() {
+        let tar = tar_bytes(&[("a.png", (0..4096u32).map(|i| (i * 31 % 251) as u8).collect())]);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&tar).unwrap();
+        let bytes = encoder.finish().unwrap();
+
+        let (entries, _) = collect(ArchiveKind::TarGz, &bytes).unwrap();
+        assert_eq!(entries.len(), 1);
+    }
+}
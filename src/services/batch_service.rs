@@ -0,0 +1,392 @@
+use std::collections::HashMap;
+use std::fs;
+use uuid::Uuid;
+
+use crate::models::{AttributeMap, BatchImageRequest, BatchItemResult, ConflictResolution, Image, RegionKind};
+use super::DataService;
+use super::ingest_service::{remove_stored_file, store_upload, UPLOADS_DIR};
+use super::keypoint_service::RemappedKeypoints;
+use super::region_service::{load_geometry, remove_geometry, store_geometry};
+use super::vocabulary_service::LabelOutcome;
+
+#[derive(Debug, Clone)]
+pub enum BatchOperation {
+    Move { target_group_id: String },
+    Copy { target_group_id: String },
+    Delete,
+    SuggestTag { tag: String },
+}
+
+impl BatchOperation {
+    pub fn from_request(req: &BatchImageRequest) -> Result<Self, String> {
+        let target_group_id = || {
+            req.target_group_id.clone()
+                .filter(|id| !id.trim().is_empty())
+                .ok_or_else(|| format!("Operation '{}' requires target_group_id", req.operation))
+        };
+        match req.operation.as_str() {
+            "move" => Ok(BatchOperation::Move { target_group_id: target_group_id()? }),
+            "copy" => Ok(BatchOperation::Copy { target_group_id: target_group_id()? }),
+            "delete" => Ok(BatchOperation::Delete),
+            "suggest_tag" => {
+                let tag = req.tag.as_deref().map(str::trim).filter(|tag| !tag.is_empty())
+                    .ok_or("Operation 'suggest_tag' requires a tag")?;
+                Ok(BatchOperation::SuggestTag { tag: tag.to_string() })
+            }
+            other => Err(format!("Unknown batch operation '{}'", other)),
+        }
+    }
+}
+
+pub struct BatchOutcome {
+    pub committed: bool,
+    pub results: Vec<BatchItemResult>,
+}
+
+impl BatchItemResult {
+    fn ok(image_id: &str) -> Self {
+        Self {
+            image_id: image_id.to_string(),
+            status: "ok".to_string(),
+            new_image_id: None,
+            suggestion_id: None,
+            proposal_id: None,
+            error: None,
+        }
+    }
+
+    fn failed(image_id: &str, error: String) -> Self {
+        Self {
+            error: Some(error),
+            status: "failed".to_string(),
+            ..Self::ok(image_id)
+        }
+    }
+}
+
+impl DataService {
+    fn can_edit_group(&self, group_id: &str, username: &str, is_admin: bool) -> bool {
+        is_admin || self.groups.get(group_id).is_some_and(|group| group.members.iter().any(|member| member == username))
+    }
+
+    // Executa a operação para todas as imagens ou para nenhuma: qualquer falha desfaz o lote inteiro
+    pub fn run_batch(
+        &mut self,
+        operation: &BatchOperation,
+        image_ids: &[String],
+        performed_by: &str,
+        is_admin: bool,
+    ) -> BatchOutcome {
+        let snapshot = self.snapshot();
+        // Mover pode substituir tags em grupo exclusivo; sugestões registram o próprio histórico
+        let checkpoint = matches!(operation, BatchOperation::Move { .. })
+            .then(|| self.history_checkpoint(image_ids.iter().map(String::as_str)));
+        let mut created_files = Vec::new();
+        let mut deleted_files = Vec::new();
+        let mut results = Vec::new();
+
+        let mut seen = std::collections::HashSet::new();
+        for image_id in image_ids.iter().filter(|id| seen.insert(id.as_str())) {
+            let result = self.apply_batch_item(operation, image_id, performed_by, is_admin, &mut created_files, &mut deleted_files);
+            results.push(match result {
+                Ok(result) => result,
+                Err(e) => BatchItemResult::failed(image_id, e),
+            });
+        }
+
+        let committed = results.iter().all(|result| result.status == "ok");
+        if committed {
+            for filename in deleted_files {
+                remove_stored_file(&filename);
+            }
+            if let Some(checkpoint) = checkpoint {
+                self.record_history(checkpoint, performed_by);
+            }
+            let copies: Vec<String> = results.iter().filter_map(|result| result.new_image_id.clone()).collect();
+            self.record_copy_history(&copies, performed_by);
+            let _ = self.save_to_json();
+            self.prune_region_geometries();
+        } else {
+            // Só as geometrias das regiões copiadas no lote foram gravadas; as demais continuam em disco
+            let created_regions: Vec<String> = self.region_annotations.keys()
+                .filter(|id| !snapshot.region_annotations.contains_key(*id))
+                .cloned()
+                .collect();
+            self.restore(snapshot);
+            for filename in created_files {
+                remove_stored_file(&filename);
+            }
+            for region_id in created_regions {
+                remove_geometry(&region_id);
+            }
+            for result in results.iter_mut().filter(|result| result.status == "ok") {
+                result.status = "rolled_back".to_string();
+                result.new_image_id = None;
+                result.suggestion_id = None;
+                result.proposal_id = None;
+            }
+        }
+
+        BatchOutcome { committed, results }
+    }
+
+    fn apply_batch_item(
+        &mut self,
+        operation: &BatchOperation,
+        image_id: &str,
+        performed_by: &str,
+        is_admin: bool,
+        created_files: &mut Vec<String>,
+        deleted_files: &mut Vec<String>,
+    ) -> Result<BatchItemResult, String> {
+        let image = self.images.get(image_id).cloned().ok_or("Image not found")?;
+        if !self.can_edit_group(&image.group_id, performed_by, is_admin) {
+            return Err("User is not a member of the image's group".to_string());
+        }
+
+        match operation {
+            BatchOperation::Move { target_group_id } | BatchOperation::Copy { target_group_id } => {
+                if !self.groups.contains_key(target_group_id) {
+                    return Err("Target group not found".to_string());
+                }
+                if !self.can_edit_group(target_group_id, performed_by, is_admin) {
+                    return Err("User is not a member of the target group".to_string());
+                }
+                let keypoints = self.remap_keypoints_to_group(image_id, target_group_id)?;
+
+                if let BatchOperation::Move { .. } = operation {
+                    if let Some(image) = self.images.get_mut(image_id) {
+                        image.group_id = target_group_id.clone();
+                    }
+                    for (annotation_id, (schema, points)) in keypoints {
+                        if let Some(annotation) = self.keypoint_annotations.get_mut(&annotation_id) {
+                            annotation.schema = schema;
+                            annotation.keypoints = points;
+                        }
+                    }
+                    self.enforce_exclusive_classes(image_id, target_group_id)?;
+                    return Ok(BatchItemResult::ok(image_id));
+                }
+
+                let new_image_id = self.copy_image(&image, target_group_id, &keypoints, created_files)?;
+                self.enforce_exclusive_classes(&new_image_id, target_group_id)?;
+                Ok(BatchItemResult { new_image_id: Some(new_image_id), ..BatchItemResult::ok(image_id) })
+            }
+            BatchOperation::Delete => {
+                self.images.remove(image_id);
+                self.remove_image_annotations(image_id);
+                deleted_files.extend(image.all_filenames());
+                Ok(BatchItemResult::ok(image_id))
+            }
+            BatchOperation::SuggestTag { tag } => match self.submit_tag_suggestion(image_id, tag, &AttributeMap::new(), performed_by) {
+                Ok(LabelOutcome::Suggested(suggestion)) => {
+                    Ok(BatchItemResult { suggestion_id: Some(suggestion.id), ..BatchItemResult::ok(image_id) })
+                }
+                Ok(LabelOutcome::Proposed(proposal)) => {
+                    Ok(BatchItemResult { proposal_id: Some(proposal.id), ..BatchItemResult::ok(image_id) })
+                }
+                Err(e) => Err(e.to_string()),
+            },
+        }
+    }
+
+    // Imagem que chega a um grupo exclusivo com tags de várias classes: falha pela política "reject"
+    // ou mantém só a classe aprovada mais recentemente
+    fn enforce_exclusive_classes(&mut self, image_id: &str, group_id: &str) -> Result<(), String> {
+        let conflicts = self.exclusive_losers(group_id, &self.ranked_approved_tags(image_id));
+        if conflicts.is_empty() {
+            return Ok(());
+        }
+        if self.labeling_policy_for_image(image_id).on_conflict == ConflictResolution::Reject {
+            let labels: Vec<String> = conflicts.into_iter().map(|(_, label)| label).collect();
+            return Err(format!("Target group is exclusive and the image has approved tags of other classes: {}", labels.join(", ")));
+        }
+        let tag_ids: Vec<String> = conflicts.into_iter().map(|(id, _)| id).collect();
+        self.supersede_approved_tags(&tag_ids);
+        Ok(())
+    }
+
+    // Duplica arquivo e todas as anotações da imagem no grupo de destino
+    // (comparações, notas e comentários ficam de fora: pertencem ao grupo de origem)
+    fn copy_image(
+        &mut self,
+        source: &Image,
+        target_group_id: &str,
+        keypoints: &RemappedKeypoints,
+        created_files: &mut Vec<String>,
+    ) -> Result<String, String> {
+        let bytes = fs::read(format!("{}/{}", UPLOADS_DIR, source.filename))
+            .map_err(|e| format!("Failed to read image file: {}", e))?;
+        let filename = store_upload(&source.original_name, &bytes)
+            .map_err(|e| format!("Failed to save file: {}", e))?;
+        created_files.push(filename.clone());
+
+        let mut copy = Image::new(filename, source.original_name.clone(), target_group_id.to_string(), source.uploaded_by.clone());
+        copy.phash = source.phash.clone();
+        copy.width = source.width;
+        copy.height = source.height;
+        let copy_id = copy.id.clone();
+        self.images.insert(copy_id.clone(), copy);
+
+        let mut id_map: HashMap<String, String> = HashMap::new();
+        let suggestions: Vec<_> = self.tag_suggestions.values()
+            .filter(|suggestion| suggestion.image_id == source.id)
+            .cloned()
+            .collect();
+        for mut suggestion in suggestions {
+            let new_id = Uuid::new_v4().to_string();
+            id_map.insert(suggestion.id.clone(), new_id.clone());
+            suggestion.id = new_id.clone();
+            suggestion.image_id = copy_id.clone();
+            self.tag_suggestions.insert(new_id, suggestion);
+        }
+
+        for suggestion in self.tag_suggestions.values_mut().filter(|suggestion| suggestion.image_id == copy_id) {
+            suggestion.approved_with = suggestion.approved_with.take().and_then(|id| id_map.get(&id).cloned());
+        }
+
+        let approved: Vec<_> = self.approved_tags.values()
+            .filter(|tag| tag.image_id == source.id)
+            .cloned()
+            .collect();
+        for mut tag in approved {
+            let new_id = Uuid::new_v4().to_string();
+            id_map.insert(tag.id.clone(), new_id.clone());
+            tag.id = new_id.clone();
+            tag.image_id = copy_id.clone();
+            tag.suggestion_id = tag.suggestion_id.and_then(|id| id_map.get(&id).cloned());
+            self.approved_tags.insert(new_id, tag);
+        }
+
+        let upvotes: Vec<_> = self.tag_upvotes.values()
+            .filter(|upvote| id_map.contains_key(&upvote.tag_id))
+            .cloned()
+            .collect();
+        for mut upvote in upvotes {
+            upvote.id = Uuid::new_v4().to_string();
+            upvote.tag_id = id_map[&upvote.tag_id].clone();
+            self.tag_upvotes.insert(upvote.id.clone(), upvote);
+        }
+
+        let regions: Vec<_> = self.region_annotations.values()
+            .filter(|region| region.image_id == source.id)
+            .cloned()
+            .collect();
+        for mut region in regions {
+            let new_id = Uuid::new_v4().to_string();
+            if region.kind != RegionKind::Box {
+                let geometry = load_geometry(&region.id).ok_or("Failed to read region geometry")?;
+                store_geometry(&new_id, &geometry).map_err(|e| format!("Failed to copy region geometry: {}", e))?;
+            }
+            region.id = new_id;
+            region.image_id = copy_id.clone();
+            self.region_annotations.insert(region.id.clone(), region);
+        }
+
+        let annotations: Vec<_> = self.keypoint_annotations.values()
+            .filter(|annotation| annotation.image_id == source.id)
+            .cloned()
+            .collect();
+        for mut annotation in annotations {
+            if let Some((schema, points)) = keypoints.get(&annotation.id) {
+                annotation.schema = schema.clone();
+                annotation.keypoints = points.clone();
+            }
+            annotation.id = Uuid::new_v4().to_string();
+            annotation.image_id = copy_id.clone();
+            self.keypoint_annotations.insert(annotation.id.clone(), annotation);
+        }
+
+        let captions: Vec<_> = self.captions.values()
+            .filter(|caption| caption.image_id == source.id)
+            .cloned()
+            .collect();
+        for mut caption in captions {
+            caption.id = Uuid::new_v4().to_string();
+            caption.image_id = copy_id.clone();
+            self.captions.insert(caption.id.clone(), caption);
+        }
+
+        let questions: Vec<_> = self.vqa_questions.values()
+            .filter(|question| question.image_id == source.id)
+            .cloned()
+            .collect();
+        for mut question in questions {
+            let new_id = Uuid::new_v4().to_string();
+            let answers: Vec<_> = self.vqa_answers.values()
+                .filter(|answer| answer.question_id == question.id)
+                .cloned()
+                .collect();
+            for mut answer in answers {
+                answer.id = Uuid::new_v4().to_string();
+                answer.question_id = new_id.clone();
+                self.vqa_answers.insert(answer.id.clone(), answer);
+            }
+            question.id = new_id;
+            question.image_id = copy_id.clone();
+            self.vqa_questions.insert(question.id.clone(), question);
+        }
+
+        Ok(copy_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Group, TagSuggestion};
+
+    // Grupos "A" e "B" com alice como membro e duas imagens em "A", uma delas com a sugestão "cat"
+    fn fixture() -> (DataService, String, String, Vec<String>) {
+        let mut data = DataService::new();
+        let mut source = Group::new("A".to_string(), String::new(), "admin".to_string());
+        let mut target = Group::new("B".to_string(), String::new(), "admin".to_string());
+        source.members.push("alice".to_string());
+        target.members.push("alice".to_string());
+        let images: Vec<Image> = ["a.png", "b.png"].iter()
+            .map(|name| Image::new(name.to_string(), name.to_string(), source.id.clone(), "alice".to_string()))
+            .collect();
+        let suggestion = TagSuggestion::new(images[0].id.clone(), "cat".to_string(), "alice".to_string());
+        let (source_id, target_id) = (source.id.clone(), target.id.clone());
+        let image_ids = images.iter().map(|image| image.id.clone()).collect();
+        data.groups.insert(source.id.clone(), source);
+        data.groups.insert(target.id.clone(), target);
+        for image in images {
+            data.images.insert(image.id.clone(), image);
+        }
+        data.tag_suggestions.insert(suggestion.id.clone(), suggestion);
+        (data, source_id, target_id, image_ids)
+    }
+
+    #[test]
+    fn a_failed_item_rolls_back_the_whole_move() {
+        let (mut data, source_id, target_id, mut image_ids) = fixture();
+        image_ids.push("missing".to_string());
+        let outcome = data.run_batch(&BatchOperation::Move { target_group_id: target_id }, &image_ids, "alice", false);
+
+        assert!(!outcome.committed);
+        let statuses: Vec<&str> = outcome.results.iter().map(|result| result.status.as_str()).collect();
+        assert_eq!(statuses, vec!["rolled_back", "rolled_back", "failed"]);
+        assert!(image_ids[..2].iter().all(|id| data.images[id].group_id == source_id));
+    }
+
+    #[test]
+    fn a_failed_item_restores_deleted_images_and_annotations() {
+        let (mut data, _, _, image_ids) = fixture();
+        let ids = vec![image_ids[0].clone(), "missing".to_string()];
+        let outcome = data.run_batch(&BatchOperation::Delete, &ids, "alice", false);
+
+        assert!(!outcome.committed);
+        assert!(data.images.contains_key(&image_ids[0]));
+        assert_eq!(data.tag_suggestions.len(), 1);
+    }
+
+    #[test]
+    fn outsiders_cannot_touch_the_group() {
+        let (mut data, _, target_id, image_ids) = fixture();
+        let outcome = data.run_batch(&BatchOperation::Move { target_group_id: target_id }, &image_ids, "mallory", false);
+
+        assert!(!outcome.committed);
+        assert!(outcome.results.iter().all(|result| result.status == "failed"));
+    }
+}
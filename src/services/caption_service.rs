@@ -0,0 +1,186 @@
+use std::collections::HashSet;
+use std::fmt;
+use chrono::Utc;
+
+use crate::models::{Caption, CaptionListQuery, CaptionPolicy, CaptionRevision, EditCaptionRequest, ImageTextPair, SuggestCaptionRequest};
+use super::DataService;
+use super::region_service::validate_review_status;
+
+// Limite absoluto, independente da política do grupo
+pub const MAX_CAPTION_LENGTH: usize = 4000;
+
+#[derive(Debug)]
+pub enum CaptionError {
+    ImageNotFound,
+    GroupNotFound,
+    CaptionNotFound,
+    Forbidden,
+    Invalid(String),
+}
+
+impl fmt::Display for CaptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaptionError::ImageNotFound => write!(f, "Image not found"),
+            CaptionError::GroupNotFound => write!(f, "Group not found"),
+            CaptionError::CaptionNotFound => write!(f, "Caption not found"),
+            CaptionError::Forbidden => write!(f, "Only the author or an admin can edit this caption"),
+            CaptionError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+// Código de idioma no formato BCP 47 simplificado ("pt", "pt-br", "zh-hant"), em minúsculas
+pub fn normalize_language(language: &str) -> Result<String, String> {
+    let code = language.trim().replace('_', "-").to_lowercase();
+    let mut parts = code.split('-');
+    let primary = parts.next().unwrap_or("");
+    let valid = (2..=3).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && parts.all(|part| (1..=8).contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphanumeric()));
+    if !valid {
+        return Err(format!("Invalid language code '{}'", language.trim()));
+    }
+    Ok(code)
+}
+
+impl CaptionPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if let (Some(min), Some(max)) = (self.min_length, self.max_length) {
+            if min > max {
+                return Err("min_length cannot be greater than max_length".to_string());
+            }
+        }
+        if self.max_length.is_some_and(|max| max == 0 || max > MAX_CAPTION_LENGTH) {
+            return Err(format!("max_length must be between 1 and {}", MAX_CAPTION_LENGTH));
+        }
+        for language in &self.languages {
+            normalize_language(language)?;
+        }
+        Ok(())
+    }
+
+    // Devolve o texto sem espaços nas pontas e o idioma normalizado
+    pub fn check(&self, text: &str, language: Option<&str>) -> Result<(String, Option<String>), String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err("Caption cannot be empty".to_string());
+        }
+        let length = text.chars().count();
+        if let Some(min) = self.min_length.filter(|min| length < *min) {
+            return Err(format!("Caption must have at least {} characters", min));
+        }
+        let max = self.max_length.unwrap_or(MAX_CAPTION_LENGTH);
+        if length > max {
+            return Err(format!("Caption must have at most {} characters", max));
+        }
+
+        let language = language.map(str::trim).filter(|language| !language.is_empty()).map(normalize_language).transpose()?;
+        if !self.languages.is_empty() {
+            let Some(code) = &language else {
+                return Err(format!("Caption language is required; allowed: {}", self.languages.join(", ")));
+            };
+            let allowed = self.languages.iter().any(|allowed| normalize_language(allowed).is_ok_and(|allowed| &allowed == code));
+            if !allowed {
+                return Err(format!("Language '{}' is not allowed; allowed: {}", code, self.languages.join(", ")));
+            }
+        }
+        Ok((text.to_string(), language))
+    }
+}
+
+impl DataService {
+    fn caption_policy_for_image(&self, image_id: &str) -> Result<CaptionPolicy, CaptionError> {
+        let image = self.images.get(image_id).ok_or(CaptionError::ImageNotFound)?;
+        let group = self.groups.get(&image.group_id).ok_or(CaptionError::GroupNotFound)?;
+        Ok(group.caption_policy.clone())
+    }
+
+    pub fn suggest_caption(&mut self, image_id: &str, req: &SuggestCaptionRequest) -> Result<Caption, CaptionError> {
+        let policy = self.caption_policy_for_image(image_id)?;
+        let (text, language) = policy.check(&req.text, req.language.as_deref()).map_err(CaptionError::Invalid)?;
+        let caption = Caption::new(image_id.to_string(), text, language, req.suggested_by.clone());
+        self.captions.insert(caption.id.clone(), caption.clone());
+        Ok(caption)
+    }
+
+    // Guarda a versão atual no histórico; legendas já revisadas voltam para "pending"
+    pub fn edit_caption(&mut self, caption_id: &str, req: &EditCaptionRequest, is_admin: bool) -> Result<Caption, CaptionError> {
+        let caption = self.captions.get(caption_id).ok_or(CaptionError::CaptionNotFound)?;
+        if !is_admin && caption.suggested_by != req.edited_by {
+            return Err(CaptionError::Forbidden);
+        }
+        let policy = self.caption_policy_for_image(&caption.image_id)?;
+        let language = req.language.as_deref().or(caption.language.as_deref());
+        let (text, language) = policy.check(&req.text, language).map_err(CaptionError::Invalid)?;
+
+        let caption = self.captions.get_mut(caption_id).ok_or(CaptionError::CaptionNotFound)?;
+        if caption.text == text && caption.language == language {
+            return Ok(caption.clone());
+        }
+        let now = Utc::now().to_rfc3339();
+        let previous_text = std::mem::replace(&mut caption.text, text);
+        let previous_language = std::mem::replace(&mut caption.language, language);
+        caption.history.push(CaptionRevision {
+            text: previous_text,
+            language: previous_language,
+            edited_by: req.edited_by.clone(),
+            edited_at: now,
+        });
+        caption.status = "pending".to_string();
+        caption.reviewed_by = None;
+        caption.reviewed_at = None;
+        Ok(caption.clone())
+    }
+
+    pub fn review_caption(&mut self, caption_id: &str, status: &str, reviewed_by: &str) -> Result<Caption, CaptionError> {
+        validate_review_status(status).map_err(CaptionError::Invalid)?;
+        let caption = self.captions.get_mut(caption_id).ok_or(CaptionError::CaptionNotFound)?;
+        caption.status = status.to_string();
+        caption.reviewed_by = Some(reviewed_by.to_string());
+        caption.reviewed_at = Some(Utc::now().to_rfc3339());
+        Ok(caption.clone())
+    }
+
+    pub fn list_captions(&self, query: &CaptionListQuery) -> Vec<&Caption> {
+        let language = query.language.as_deref().map(|language| language.trim().replace('_', "-").to_lowercase());
+        let mut captions: Vec<&Caption> = self.captions.values()
+            .filter(|caption| query.image_id.as_ref().is_none_or(|id| &caption.image_id == id))
+            .filter(|caption| query.status.as_ref().is_none_or(|status| &caption.status == status))
+            .filter(|caption| language.as_ref().is_none_or(|language| caption.language.as_ref() == Some(language)))
+            .filter(|caption| query.group_id.as_ref().is_none_or(|group_id| {
+                self.images.get(&caption.image_id).is_some_and(|image| &image.group_id == group_id)
+            }))
+            .collect();
+        captions.sort_by(|a, b| a.suggested_at.cmp(&b.suggested_at).then_with(|| a.id.cmp(&b.id)));
+        captions
+    }
+
+    pub fn set_caption_policy(&mut self, group_id: &str, mut policy: CaptionPolicy) -> Result<(), CaptionError> {
+        policy.validate().map_err(CaptionError::Invalid)?;
+        policy.languages = policy.languages.iter().filter_map(|language| normalize_language(language).ok()).collect();
+        let group = self.groups.get_mut(group_id).ok_or(CaptionError::GroupNotFound)?;
+        group.caption_policy = policy;
+        Ok(())
+    }
+
+    // Pares imagem-texto das legendas aprovadas, em ordem estável
+    pub fn image_text_pairs(&self, image_ids: Option<&HashSet<String>>) -> Vec<ImageTextPair<'_>> {
+        let mut pairs: Vec<ImageTextPair> = self.captions.values()
+            .filter(|caption| caption.status == "approved")
+            .filter(|caption| image_ids.is_none_or(|ids| ids.contains(&caption.image_id)))
+            .filter_map(|caption| {
+                let image = self.images.get(&caption.image_id)?;
+                Some(ImageTextPair {
+                    caption_id: &caption.id,
+                    image_id: &image.id,
+                    file_name: &image.filename,
+                    text: &caption.text,
+                    language: caption.language.as_deref(),
+                })
+            })
+            .collect();
+        pairs.sort_by(|a, b| a.image_id.cmp(b.image_id).then_with(|| a.caption_id.cmp(b.caption_id)));
+        pairs
+    }
+}
@@ -0,0 +1,200 @@
+use std::fmt;
+use chrono::Utc;
+
+use crate::models::{Comment, CommentTarget, CommentThread};
+use super::DataService;
+
+pub const MAX_COMMENT_LENGTH: usize = 2000;
+
+#[derive(Debug)]
+pub enum CommentError {
+    ImageNotFound,
+    TagNotFound,
+    CommentNotFound,
+    Forbidden(&'static str),
+    Invalid(String),
+}
+
+impl fmt::Display for CommentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommentError::ImageNotFound => write!(f, "Image not found"),
+            CommentError::TagNotFound => write!(f, "Tag suggestion or approved tag not found"),
+            CommentError::CommentNotFound => write!(f, "Comment not found"),
+            CommentError::Forbidden(message) => write!(f, "{}", message),
+            CommentError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+// Usernames citados com @ no início de uma palavra ("@bob," → "bob"; e-mails não contam)
+pub fn extract_mentions(body: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    let mut previous: Option<char> = None;
+    for (index, c) in body.char_indices() {
+        let at_word_start = previous.is_none_or(|p| !p.is_alphanumeric() && p != '_' && p != '@');
+        if c == '@' && at_word_start {
+            let rest = &body[index + 1..];
+            let end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.' || c == '-')).unwrap_or(rest.len());
+            let name = rest[..end].trim_end_matches(['.', '-']);
+            if !name.is_empty() && !mentions.iter().any(|m| m == name) {
+                mentions.push(name.to_string());
+            }
+        }
+        previous = Some(c);
+    }
+    mentions
+}
+
+impl DataService {
+    // Texto sem espaços nas pontas e menções validadas contra os membros do grupo da imagem
+    fn check_comment(&self, image_id: &str, body: &str) -> Result<(String, Vec<String>), CommentError> {
+        let body = body.trim();
+        if body.is_empty() {
+            return Err(CommentError::Invalid("Comment cannot be empty".to_string()));
+        }
+        if body.chars().count() > MAX_COMMENT_LENGTH {
+            return Err(CommentError::Invalid(format!("Comment must have at most {} characters", MAX_COMMENT_LENGTH)));
+        }
+        let image = self.images.get(image_id).ok_or(CommentError::ImageNotFound)?;
+        let members = self.groups.get(&image.group_id).map(|group| &group.members);
+        let mentions = extract_mentions(body);
+        let unknown: Vec<&String> = mentions.iter()
+            .filter(|mention| members.is_none_or(|members| !members.contains(mention)))
+            .collect();
+        if !unknown.is_empty() {
+            let names: Vec<String> = unknown.iter().map(|name| format!("@{}", name)).collect();
+            return Err(CommentError::Invalid(format!("Mentioned users are not members of the group: {}", names.join(", "))));
+        }
+        Ok((body.to_string(), mentions))
+    }
+
+    // Sugestões e tags aprovadas compartilham a rota /tags/{tag_id}
+    pub fn resolve_tag_target(&self, tag_id: &str) -> Result<(CommentTarget, String), CommentError> {
+        if let Some(suggestion) = self.tag_suggestions.get(tag_id) {
+            return Ok((CommentTarget::Suggestion, suggestion.image_id.clone()));
+        }
+        if let Some(tag) = self.approved_tags.get(tag_id) {
+            return Ok((CommentTarget::Tag, tag.image_id.clone()));
+        }
+        Err(CommentError::TagNotFound)
+    }
+
+    pub fn start_thread(&mut self, image_id: &str, target: CommentTarget, target_id: &str, body: &str, author: &str) -> Result<Comment, CommentError> {
+        let (body, mentions) = self.check_comment(image_id, body)?;
+        let comment = Comment::new(image_id.to_string(), target, target_id.to_string(), None, author.to_string(), body, mentions);
+        self.comments.insert(comment.id.clone(), comment.clone());
+        Ok(comment)
+    }
+
+    // Resposta a uma resposta entra no mesmo tópico; responder reabre um tópico resolvido
+    pub fn reply_to_comment(&mut self, comment_id: &str, body: &str, author: &str) -> Result<Comment, CommentError> {
+        let parent = self.comments.get(comment_id).ok_or(CommentError::CommentNotFound)?;
+        let root_id = parent.parent_id.clone().unwrap_or_else(|| parent.id.clone());
+        let (image_id, target, target_id) = (parent.image_id.clone(), parent.target, parent.target_id.clone());
+        let (body, mentions) = self.check_comment(&image_id, body)?;
+
+        let reply = Comment::new(image_id, target, target_id, Some(root_id.clone()), author.to_string(), body, mentions);
+        self.comments.insert(reply.id.clone(), reply.clone());
+        if let Some(root) = self.comments.get_mut(&root_id) {
+            root.resolved = false;
+            root.resolved_by = None;
+            root.resolved_at = None;
+        }
+        Ok(reply)
+    }
+
+    pub fn edit_comment(&mut self, comment_id: &str, body: &str, edited_by: &str) -> Result<Comment, CommentError> {
+        let comment = self.comments.get(comment_id).ok_or(CommentError::CommentNotFound)?;
+        if comment.author != edited_by {
+            return Err(CommentError::Forbidden("Only the author can edit this comment"));
+        }
+        let (body, mentions) = self.check_comment(&comment.image_id.clone(), body)?;
+
+        let comment = self.comments.get_mut(comment_id).ok_or(CommentError::CommentNotFound)?;
+        if comment.body != body {
+            comment.body = body;
+            comment.mentions = mentions;
+            comment.edited_at = Some(Utc::now().to_rfc3339());
+        }
+        Ok(comment.clone())
+    }
+
+    // Só o autor do tópico ou um admin resolve ou reabre
+    pub fn resolve_thread(&mut self, comment_id: &str, resolved: bool, resolved_by: &str, is_admin: bool) -> Result<Comment, CommentError> {
+        let comment = self.comments.get(comment_id).ok_or(CommentError::CommentNotFound)?;
+        let root_id = comment.parent_id.clone().unwrap_or_else(|| comment.id.clone());
+        let root = self.comments.get_mut(&root_id).ok_or(CommentError::CommentNotFound)?;
+        if !is_admin && root.author != resolved_by {
+            return Err(CommentError::Forbidden("Only the thread author or an admin can resolve this thread"));
+        }
+        root.resolved = resolved;
+        root.resolved_by = resolved.then(|| resolved_by.to_string());
+        root.resolved_at = resolved.then(|| Utc::now().to_rfc3339());
+        Ok(root.clone())
+    }
+
+    // Apagar o comentário raiz apaga o tópico inteiro; devolve quantos comentários saíram
+    pub fn delete_comment(&mut self, comment_id: &str, requested_by: &str, is_admin: bool) -> Result<usize, CommentError> {
+        let comment = self.comments.get(comment_id).ok_or(CommentError::CommentNotFound)?;
+        if !is_admin && comment.author != requested_by {
+            return Err(CommentError::Forbidden("Only the author or an admin can delete this comment"));
+        }
+        let before = self.comments.len();
+        let comment_id = comment_id.to_string();
+        self.comments.retain(|id, comment| *id != comment_id && comment.parent_id.as_ref() != Some(&comment_id));
+        Ok(before - self.comments.len())
+    }
+
+    // Leva as conversas de uma sugestão ou tag fundida para a que ficou
+    pub fn retarget_comments(&mut self, from_id: &str, into_id: &str, image_id: &str) {
+        for comment in self.comments.values_mut().filter(|comment| comment.target != CommentTarget::Image && comment.target_id == from_id) {
+            comment.target_id = into_id.to_string();
+            comment.image_id = image_id.to_string();
+        }
+    }
+
+    // Remove as conversas de uma sugestão ou tag que deixou de existir
+    pub fn remove_target_comments(&mut self, target_id: &str) {
+        self.comments.retain(|_, comment| comment.target == CommentTarget::Image || comment.target_id != target_id);
+    }
+
+    fn build_threads<'a>(&'a self, roots: impl Iterator<Item = &'a Comment>) -> Vec<CommentThread<'a>> {
+        let mut threads: Vec<CommentThread> = roots
+            .filter(|comment| comment.parent_id.is_none())
+            .map(|root| {
+                let mut replies: Vec<&Comment> = self.comments.values()
+                    .filter(|reply| reply.parent_id.as_ref() == Some(&root.id))
+                    .collect();
+                replies.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+                CommentThread { comment: root, replies }
+            })
+            .collect();
+        threads.sort_by(|a, b| a.comment.created_at.cmp(&b.comment.created_at).then_with(|| a.comment.id.cmp(&b.comment.id)));
+        threads
+    }
+
+    // Todos os tópicos da imagem, inclusive os das suas sugestões e tags
+    pub fn image_threads(&self, image_id: &str) -> Vec<CommentThread<'_>> {
+        self.build_threads(self.comments.values().filter(|comment| comment.image_id == image_id))
+    }
+
+    pub fn target_threads(&self, target_id: &str) -> Vec<CommentThread<'_>> {
+        self.build_threads(self.comments.values().filter(|comment| comment.target != CommentTarget::Image && comment.target_id == target_id))
+    }
+
+    pub fn unresolved_threads(&self, group_id: &str) -> Vec<CommentThread<'_>> {
+        self.build_threads(self.comments.values().filter(|comment| {
+            !comment.resolved && self.images.get(&comment.image_id).is_some_and(|image| image.group_id == group_id)
+        }))
+    }
+
+    // Comentários que citam o usuário, do mais recente para o mais antigo
+    pub fn user_mentions(&self, username: &str) -> Vec<&Comment> {
+        let mut comments: Vec<&Comment> = self.comments.values()
+            .filter(|comment| comment.mentions.iter().any(|mention| mention == username))
+            .collect();
+        comments.sort_by(|a, b| b.created_at.cmp(&a.created_at).then_with(|| a.id.cmp(&b.id)));
+        comments
+    }
+}
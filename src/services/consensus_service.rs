@@ -0,0 +1,241 @@
+use std::collections::HashSet;
+use chrono::Utc;
+
+use crate::models::{label_key, ConsensusPolicy, ReviewTagRequest, SuggestionStatus};
+use super::DataService;
+use super::tag_review_service::ReviewError;
+
+// Revisor registrado nas aprovações automáticas
+pub const SYSTEM_REVIEWER: &str = "system";
+
+impl ConsensusPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_suggesters.is_some_and(|min| min < 2) {
+            return Err("min_suggesters must be at least 2".to_string());
+        }
+        if self.min_upvotes.is_some_and(|min| min < 1) {
+            return Err("min_upvotes must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl DataService {
+    fn consensus_policy_for_image(&self, image_id: &str) -> ConsensusPolicy {
+        self.images.get(image_id)
+            .and_then(|image| self.groups.get(&image.group_id))
+            .map(|group| group.consensus)
+            .unwrap_or_default()
+    }
+
+    pub fn set_consensus_policy(&mut self, group_id: &str, policy: ConsensusPolicy) -> Result<(), ReviewError> {
+        policy.validate().map_err(ReviewError::Invalid)?;
+        let group = self.groups.get_mut(group_id).ok_or(ReviewError::GroupNotFound)?;
+        group.consensus = policy;
+        Ok(())
+    }
+
+    // Sugestões ainda não revisadas do rótulo na imagem, da mais antiga para a mais recente
+    fn open_suggestions_for_label(&self, image_id: &str, label: &str) -> Vec<(String, String)> {
+        let key = label_key(label);
+        let mut open: Vec<(String, String, String)> = self.tag_suggestions.values()
+            .filter(|suggestion| suggestion.image_id == image_id && suggestion.status.is_open() && label_key(&suggestion.tag) == key)
+            .map(|suggestion| (suggestion.suggested_at.clone(), suggestion.id.clone(), suggestion.suggested_by.clone()))
+            .collect();
+        open.sort();
+        open.into_iter().map(|(_, id, suggested_by)| (id, suggested_by)).collect()
+    }
+
+    fn label_already_approved(&self, image_id: &str, label: &str) -> bool {
+        let key = label_key(label);
+        self.approved_tags.values().any(|tag| tag.image_id == image_id && label_key(&tag.tag) == key)
+    }
+
+    // Aprova a sugestão como o sistema; as outras sugestões abertas do mesmo rótulo são aprovadas junto,
+    // sem criar outra tag, e ficam ligadas à principal (approved_with) para acompanhar a revisão dela.
+    // Conflitos de classe exclusiva ou atributos incompletos deixam tudo pendente.
+    // Com quórum de revisores o sistema conta como um voto (mesmo fora da lista de revisores) e a tag só é criada
+    // quando os revisores completam o quórum; as duplicadas só acompanham quando a tag nasce do consenso.
+    // Devolve o id da tag aprovada
+    fn approve_by_consensus(&mut self, image_id: &str, suggestion_id: &str, label: &str, note: String) -> Option<String> {
+        let checkpoint = self.history_checkpoint([image_id]);
+        let req = ReviewTagRequest {
+            status: SuggestionStatus::Approved,
+            reviewed_by: SYSTEM_REVIEWER.to_string(),
+            attributes: None,
+            supersede: false,
+            reason: None,
+            note: Some(note),
+        };
+        let outcome = match self.review_tag_suggestion(suggestion_id, &req) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                println!("⚠️ Consensus could not approve suggestion '{}': {}", suggestion_id, e);
+                return None;
+            }
+        };
+        if outcome.approved_tag_id.is_none() {
+            self.record_history(checkpoint, SYSTEM_REVIEWER);
+            return None;
+        }
+
+        let now = Utc::now().to_rfc3339();
+        for (duplicate_id, _) in self.open_suggestions_for_label(image_id, label) {
+            if let Some(duplicate) = self.tag_suggestions.get_mut(&duplicate_id) {
+                duplicate.status = SuggestionStatus::Approved;
+                duplicate.reviewed_by = Some(SYSTEM_REVIEWER.to_string());
+                duplicate.reviewed_at = Some(now.clone());
+                duplicate.review_note = Some(format!("Approved by consensus together with suggestion {}", suggestion_id));
+                duplicate.approved_with = Some(suggestion_id.to_string());
+            }
+        }
+        self.record_history(checkpoint, SYSTEM_REVIEWER);
+        outcome.approved_tag_id
+    }
+
+    // Quando a principal deixa de estar aprovada, as duplicadas ligadas a ela vão para o mesmo estado
+    // (sem revisor conhecido, como na substituição por outra classe, mantêm o anterior)
+    pub fn cascade_to_duplicates(&mut self, primary_id: &str, status: SuggestionStatus, reviewed_by: Option<&str>, reject_reason: Option<&str>) -> usize {
+        let now = Utc::now().to_rfc3339();
+        let verb = match status {
+            SuggestionStatus::Rejected => "Rejected",
+            SuggestionStatus::Superseded => "Superseded",
+            _ => "Reopened",
+        };
+        let mut cascaded = 0;
+        for duplicate in self.tag_suggestions.values_mut()
+            .filter(|suggestion| suggestion.approved_with.as_deref() == Some(primary_id) && suggestion.status == SuggestionStatus::Approved)
+        {
+            duplicate.status = status;
+            if let Some(reviewed_by) = reviewed_by {
+                duplicate.reviewed_by = Some(reviewed_by.to_string());
+                duplicate.reviewed_at = Some(now.clone());
+            }
+            duplicate.reject_reason = reject_reason.map(str::to_string);
+            duplicate.review_note = Some(format!("{} together with suggestion {}", verb, primary_id));
+            duplicate.approved_with = None;
+            duplicate.quorum = None;
+            cascaded += 1;
+        }
+        cascaded
+    }
+
+    // Chamado a cada nova sugestão: N usuários distintos sugerindo o mesmo rótulo aprovam a mais antiga
+    pub fn apply_suggestion_consensus(&mut self, image_id: &str, label: &str) -> Option<String> {
+        let min_suggesters = self.consensus_policy_for_image(image_id).min_suggesters?;
+        if self.label_already_approved(image_id, label) {
+            return None;
+        }
+        let open = self.open_suggestions_for_label(image_id, label);
+        let suggesters: HashSet<&str> = open.iter().map(|(_, suggested_by)| suggested_by.as_str()).collect();
+        if suggesters.len() < min_suggesters {
+            return None;
+        }
+        let note = format!("Auto-approved by consensus: {} distinct users suggested '{}'", suggesters.len(), label);
+        let (first_id, _) = open.first()?.clone();
+        self.approve_by_consensus(image_id, &first_id, label, note)
+    }
+
+    // Chamado a cada voto: uma sugestão aberta com M votos de outros usuários é aprovada
+    // (o voto de quem sugeriu não conta, senão bastaria sugerir e votar para dispensar a revisão)
+    pub fn apply_vote_consensus(&mut self, suggestion_id: &str) -> Option<String> {
+        let suggestion = self.tag_suggestions.get(suggestion_id)?;
+        if !suggestion.status.is_open() {
+            return None;
+        }
+        let (image_id, label) = (suggestion.image_id.clone(), suggestion.tag.clone());
+        let min_upvotes = self.consensus_policy_for_image(&image_id).min_upvotes?;
+        let upvotes = self.tag_upvotes.values()
+            .filter(|upvote| upvote.tag_id == suggestion_id && upvote.user_id != suggestion.suggested_by)
+            .count();
+        if upvotes < min_upvotes || self.label_already_approved(&image_id, &label) {
+            return None;
+        }
+        let note = format!("Auto-approved by consensus: {} upvotes", upvotes);
+        self.approve_by_consensus(&image_id, suggestion_id, &label, note)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Group, Image, TagSuggestion, TagUpvote};
+
+    fn fixture(policy: ConsensusPolicy) -> (DataService, String) {
+        let mut data = DataService::new();
+        let mut group = Group::new("Pets".to_string(), String::new(), "admin".to_string());
+        group.members.extend(["alice".to_string(), "bob".to_string(), "carol".to_string()]);
+        group.consensus = policy;
+        let image = Image::new("a.png".to_string(), "a.png".to_string(), group.id.clone(), "alice".to_string());
+        let image_id = image.id.clone();
+        data.groups.insert(group.id.clone(), group);
+        data.images.insert(image_id.clone(), image);
+        (data, image_id)
+    }
+
+    fn suggest(data: &mut DataService, image_id: &str, label: &str, user: &str) -> String {
+        let suggestion = TagSuggestion::new(image_id.to_string(), label.to_string(), user.to_string());
+        let id = suggestion.id.clone();
+        data.tag_suggestions.insert(id.clone(), suggestion);
+        id
+    }
+
+    fn upvote(data: &mut DataService, suggestion_id: &str, user: &str) {
+        let upvote = TagUpvote::new(suggestion_id.to_string(), user.to_string());
+        data.tag_upvotes.insert(upvote.id.clone(), upvote);
+    }
+
+    #[test]
+    fn rejects_thresholds_that_bypass_agreement() {
+        assert!(ConsensusPolicy { min_suggesters: Some(1), min_upvotes: None }.validate().is_err());
+        assert!(ConsensusPolicy { min_suggesters: None, min_upvotes: Some(0) }.validate().is_err());
+        assert!(ConsensusPolicy { min_suggesters: Some(2), min_upvotes: Some(1) }.validate().is_ok());
+    }
+
+    #[test]
+    fn approves_once_enough_distinct_users_suggest_the_label() {
+        let (mut data, image_id) = fixture(ConsensusPolicy { min_suggesters: Some(3), min_upvotes: None });
+        let first = suggest(&mut data, &image_id, "Cat", "alice");
+        suggest(&mut data, &image_id, "cat", "alice");
+        suggest(&mut data, &image_id, "cat", "bob");
+        assert!(data.apply_suggestion_consensus(&image_id, "cat").is_none());
+
+        let last = suggest(&mut data, &image_id, "cats", "carol");
+        let second = suggest(&mut data, &image_id, "cat", "carol");
+        assert!(data.tag_suggestions[&last].status.is_open());
+        let tag_id = data.apply_suggestion_consensus(&image_id, "cat").unwrap();
+
+        assert_eq!(data.approved_tags[&tag_id].suggestion_id.as_deref(), Some(first.as_str()));
+        assert_eq!(data.approved_tags.len(), 1);
+        assert_eq!(data.tag_suggestions[&second].status, SuggestionStatus::Approved);
+        assert_eq!(data.tag_suggestions[&second].approved_with.as_deref(), Some(first.as_str()));
+        assert!(data.tag_suggestions[&last].status.is_open(), "another label is not a duplicate");
+    }
+
+    #[test]
+    fn self_upvotes_do_not_count() {
+        let (mut data, image_id) = fixture(ConsensusPolicy { min_suggesters: None, min_upvotes: Some(1) });
+        let id = suggest(&mut data, &image_id, "cat", "alice");
+        upvote(&mut data, &id, "alice");
+        assert!(data.apply_vote_consensus(&id).is_none());
+        assert_eq!(data.tag_suggestions[&id].status, SuggestionStatus::Pending);
+
+        upvote(&mut data, &id, "bob");
+        assert!(data.apply_vote_consensus(&id).is_some());
+        assert_eq!(data.tag_suggestions[&id].reviewed_by.as_deref(), Some(SYSTEM_REVIEWER));
+    }
+
+    #[test]
+    fn reopening_the_primary_reopens_its_duplicates() {
+        let (mut data, image_id) = fixture(ConsensusPolicy { min_suggesters: Some(2), min_upvotes: None });
+        let primary = suggest(&mut data, &image_id, "cat", "alice");
+        let duplicate = suggest(&mut data, &image_id, "cat", "bob");
+        let tag_id = data.apply_suggestion_consensus(&image_id, "cat").unwrap();
+
+        data.delete_approved_tag(&tag_id, "admin");
+        for id in [&primary, &duplicate] {
+            assert_eq!(data.tag_suggestions[id].status, SuggestionStatus::Reopened);
+        }
+        assert!(data.tag_suggestions[&duplicate].approved_with.is_none());
+    }
+}
@@ -1,27 +1,58 @@
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use serde::{Deserialize, Serialize};
-use crate::models::{Group, Image, TagSuggestion, ApprovedTag, TagUpvote, AnnotationsExport};
+use crate::models::{AttributeMap, Group, Image, TagSuggestion, ApprovedTag, TagUpvote, RegionAnnotation, RegionGeometry, RegionKind, KeypointAnnotation, Caption, VqaQuestion, VqaAnswer, PairwiseComparison, LikertRating, CriterionScores, Comment, AnnotationEvent, LabelProposal, TagAlias, AnnotationsExport, MergeDuplicatesResult, ConflictResolution};
+use super::phash_service::{self, cluster_hashes, hamming_distance, parse_phash};
+use super::ingest_service::read_dimensions;
+use super::region_service::load_geometry;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug)]
+pub enum MergeError {
+    ImageNotFound(String),
+    Invalid(String),
+    ExclusiveConflict(Vec<(String, String)>), // (id, rótulo) das tags que trariam outra classe
+}
+
+impl std::fmt::Display for MergeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeError::ImageNotFound(id) => write!(f, "Image '{}' not found", id),
+            MergeError::Invalid(message) => write!(f, "{}", message),
+            MergeError::ExclusiveConflict(_) => write!(f, "Merged images carry approved tags of another class"),
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct AppData {
     pub groups: HashMap<String, Group>,
     pub images: HashMap<String, Image>,
     pub tag_suggestions: HashMap<String, TagSuggestion>,
     pub approved_tags: HashMap<String, ApprovedTag>,
     pub tag_upvotes: HashMap<String, TagUpvote>,
-}
-
-impl Default for AppData {
-    fn default() -> Self {
-        Self {
-            groups: HashMap::new(),
-            images: HashMap::new(),
-            tag_suggestions: HashMap::new(),
-            approved_tags: HashMap::new(),
-            tag_upvotes: HashMap::new(),
-        }
-    }
+    #[serde(default)]
+    pub region_annotations: HashMap<String, RegionAnnotation>,
+    #[serde(default)]
+    pub keypoint_annotations: HashMap<String, KeypointAnnotation>,
+    #[serde(default)]
+    pub label_proposals: HashMap<String, LabelProposal>,
+    #[serde(default)]
+    pub tag_aliases: HashMap<String, TagAlias>,
+    #[serde(default)]
+    pub captions: HashMap<String, Caption>,
+    #[serde(default)]
+    pub vqa_questions: HashMap<String, VqaQuestion>,
+    #[serde(default)]
+    pub vqa_answers: HashMap<String, VqaAnswer>,
+    #[serde(default)]
+    pub pairwise_comparisons: HashMap<String, PairwiseComparison>,
+    #[serde(default)]
+    pub likert_ratings: HashMap<String, LikertRating>,
+    #[serde(default)]
+    pub comments: HashMap<String, Comment>,
+    #[serde(default)]
+    pub annotation_history: Vec<AnnotationEvent>, // em ordem de gravação
 }
 
 pub struct DataService {
@@ -30,6 +61,17 @@ pub struct DataService {
     pub tag_suggestions: HashMap<String, TagSuggestion>,
     pub approved_tags: HashMap<String, ApprovedTag>,
     pub tag_upvotes: HashMap<String, TagUpvote>,
+    pub region_annotations: HashMap<String, RegionAnnotation>,
+    pub keypoint_annotations: HashMap<String, KeypointAnnotation>,
+    pub label_proposals: HashMap<String, LabelProposal>,
+    pub tag_aliases: HashMap<String, TagAlias>,
+    pub captions: HashMap<String, Caption>,
+    pub vqa_questions: HashMap<String, VqaQuestion>,
+    pub vqa_answers: HashMap<String, VqaAnswer>,
+    pub pairwise_comparisons: HashMap<String, PairwiseComparison>,
+    pub likert_ratings: HashMap<String, LikertRating>,
+    pub comments: HashMap<String, Comment>,
+    pub annotation_history: Vec<AnnotationEvent>,
 }
 
 impl DataService {
@@ -40,11 +82,22 @@ impl DataService {
             tag_suggestions: HashMap::new(),
             approved_tags: HashMap::new(),
             tag_upvotes: HashMap::new(),
+            region_annotations: HashMap::new(),
+            keypoint_annotations: HashMap::new(),
+            label_proposals: HashMap::new(),
+            tag_aliases: HashMap::new(),
+            captions: HashMap::new(),
+            vqa_questions: HashMap::new(),
+            vqa_answers: HashMap::new(),
+            pairwise_comparisons: HashMap::new(),
+            likert_ratings: HashMap::new(),
+            comments: HashMap::new(),
+            annotation_history: Vec::new(),
         }
     }
 
     pub fn load_from_json(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        if !fs::metadata("data.json").is_ok() {
+        if fs::metadata("data.json").is_err() {
             println!("No data.json found, using empty data");
             return Ok(());
         }
@@ -52,25 +105,55 @@ impl DataService {
         println!("Loading data from data.json...");
         let json_data = fs::read_to_string("data.json")?;
         let app_data: AppData = serde_json::from_str(&json_data)?;
-        
-        self.groups = app_data.groups;
-        self.images = app_data.images;
-        self.tag_suggestions = app_data.tag_suggestions;
-        self.approved_tags = app_data.approved_tags;
-        self.tag_upvotes = app_data.tag_upvotes;
+        self.restore(app_data);
         
         println!("Data loaded successfully!");
         Ok(())
     }
 
-    pub fn save_to_json(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let app_data = AppData {
+    // Cópia completa do estado, usada para persistir e para desfazer operações em lote
+    pub fn snapshot(&self) -> AppData {
+        AppData {
             groups: self.groups.clone(),
             images: self.images.clone(),
             tag_suggestions: self.tag_suggestions.clone(),
             approved_tags: self.approved_tags.clone(),
             tag_upvotes: self.tag_upvotes.clone(),
-        };
+            region_annotations: self.region_annotations.clone(),
+            keypoint_annotations: self.keypoint_annotations.clone(),
+            label_proposals: self.label_proposals.clone(),
+            tag_aliases: self.tag_aliases.clone(),
+            captions: self.captions.clone(),
+            vqa_questions: self.vqa_questions.clone(),
+            vqa_answers: self.vqa_answers.clone(),
+            pairwise_comparisons: self.pairwise_comparisons.clone(),
+            likert_ratings: self.likert_ratings.clone(),
+            comments: self.comments.clone(),
+            annotation_history: self.annotation_history.clone(),
+        }
+    }
+
+    pub fn restore(&mut self, app_data: AppData) {
+        self.groups = app_data.groups;
+        self.images = app_data.images;
+        self.tag_suggestions = app_data.tag_suggestions;
+        self.approved_tags = app_data.approved_tags;
+        self.tag_upvotes = app_data.tag_upvotes;
+        self.region_annotations = app_data.region_annotations;
+        self.keypoint_annotations = app_data.keypoint_annotations;
+        self.label_proposals = app_data.label_proposals;
+        self.tag_aliases = app_data.tag_aliases;
+        self.captions = app_data.captions;
+        self.vqa_questions = app_data.vqa_questions;
+        self.vqa_answers = app_data.vqa_answers;
+        self.pairwise_comparisons = app_data.pairwise_comparisons;
+        self.likert_ratings = app_data.likert_ratings;
+        self.comments = app_data.comments;
+        self.annotation_history = app_data.annotation_history;
+    }
+
+    pub fn save_to_json(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let app_data = self.snapshot();
         
         let json_data = serde_json::to_string_pretty(&app_data)?;
         fs::write("data.json", json_data)?;
@@ -101,9 +184,17 @@ impl DataService {
 
     pub fn delete_group(&mut self, id: &str) -> bool {
         if self.groups.remove(id).is_some() {
-            // Remove related images
-            self.images.retain(|_, image| image.group_id != id);
+            // Remove related images e tudo que foi anotado nelas
+            let image_ids: Vec<String> = self.images.values()
+                .filter(|image| image.group_id == id)
+                .map(|image| image.id.clone())
+                .collect();
+            for image_id in &image_ids {
+                self.images.remove(image_id);
+                self.remove_image_annotations(image_id);
+            }
             let _ = self.save_to_json();
+            self.prune_region_geometries();
             true
         } else {
             false
@@ -111,13 +202,6 @@ impl DataService {
     }
 
     // Métodos para gerenciar imagens
-    pub fn create_image(&mut self, image: Image) -> String {
-        let id = image.id.clone();
-        self.images.insert(id.clone(), image);
-        let _ = self.save_to_json();
-        id
-    }
-
     pub fn get_image(&self, id: &str) -> Option<&Image> {
         self.images.get(id)
     }
@@ -137,23 +221,323 @@ impl DataService {
 
     pub fn delete_image(&mut self, id: &str) -> bool {
         if let Some(_image) = self.images.remove(id) {
-            // Remove related tag suggestions and approved tags
-            self.tag_suggestions.retain(|_, suggestion| suggestion.image_id != id);
-            self.approved_tags.retain(|_, tag| tag.image_id != id);
+            self.remove_image_annotations(id);
             let _ = self.save_to_json();
+            self.prune_region_geometries();
             true
         } else {
             false
         }
     }
 
-    pub fn export_annotations(&self) -> serde_json::Value {
+    // Remove todas as anotações ligadas à imagem (sugestões, tags aprovadas, votos, regiões, pontos, legendas, perguntas, avaliações, comentários e histórico)
+    pub fn remove_image_annotations(&mut self, image_id: &str) {
+        let tag_ids: HashSet<String> = self.tag_suggestions.values()
+            .filter(|suggestion| suggestion.image_id == image_id)
+            .map(|suggestion| suggestion.id.clone())
+            .chain(self.approved_tags.values()
+                .filter(|tag| tag.image_id == image_id)
+                .map(|tag| tag.id.clone()))
+            .collect();
+        self.tag_suggestions.retain(|_, suggestion| suggestion.image_id != image_id);
+        self.approved_tags.retain(|_, tag| tag.image_id != image_id);
+        self.tag_upvotes.retain(|_, upvote| !tag_ids.contains(&upvote.tag_id));
+        self.region_annotations.retain(|_, region| region.image_id != image_id);
+        self.keypoint_annotations.retain(|_, annotation| annotation.image_id != image_id);
+        self.captions.retain(|_, caption| caption.image_id != image_id);
+        let question_ids: HashSet<String> = self.vqa_questions.values()
+            .filter(|question| question.image_id == image_id)
+            .map(|question| question.id.clone())
+            .collect();
+        self.vqa_questions.retain(|id, _| !question_ids.contains(id));
+        self.vqa_answers.retain(|_, answer| !question_ids.contains(&answer.question_id));
+        self.pairwise_comparisons.retain(|_, comparison| comparison.image_a != image_id && comparison.image_b != image_id);
+        self.likert_ratings.retain(|_, rating| rating.image_id != image_id);
+        self.comments.retain(|_, comment| comment.image_id != image_id);
+        self.annotation_history.retain(|event| event.image_id != image_id);
+    }
+
+    // Calcula hash perceptual e dimensões das imagens que ainda não os possuem
+    pub fn backfill_image_info(&mut self) -> usize {
+        let mut updated = 0;
+        for image in self.images.values_mut().filter(|image| image.phash.is_none() || image.width.is_none()) {
+            if let Ok(bytes) = fs::read(format!("uploads/{}", image.filename)) {
+                image.phash = phash_service::compute_phash(&bytes);
+                if let Some((width, height)) = read_dimensions(&bytes) {
+                    image.width = Some(width);
+                    image.height = Some(height);
+                }
+                if image.phash.is_some() || image.width.is_some() {
+                    updated += 1;
+                }
+            }
+        }
+        if updated > 0 {
+            let _ = self.save_to_json();
+        }
+        updated
+    }
+
+    pub fn find_near_duplicates(&self, image_id: &str, max_distance: u32) -> Vec<(&Image, u32)> {
+        let Some(hash) = self.images.get(image_id)
+            .and_then(|image| image.phash.as_deref())
+            .and_then(parse_phash) else {
+            return Vec::new();
+        };
+
+        let mut matches: Vec<(&Image, u32)> = self.images.values()
+            .filter(|image| image.id != image_id)
+            .filter_map(|image| {
+                let distance = hamming_distance(hash, parse_phash(image.phash.as_deref()?)?);
+                (distance <= max_distance).then_some((image, distance))
+            })
+            .collect();
+        matches.sort_by_key(|(_, distance)| *distance);
+        matches
+    }
+
+    pub fn duplicate_clusters(&self, group_id: Option<&str>, max_distance: u32) -> Vec<Vec<(String, u32)>> {
+        let hashes: Vec<(String, u64)> = self.images.values()
+            .filter(|image| group_id.is_none_or(|group_id| image.group_id == group_id))
+            .filter_map(|image| Some((image.id.clone(), parse_phash(image.phash.as_deref()?)?)))
+            .collect();
+        cluster_hashes(&hashes, max_distance)
+    }
+
+    // Move os votos de uma tag para outra, descartando votos repetidos do mesmo usuário.
+    // Retorna (votos movidos, votos descartados).
+    pub fn fold_tag_upvotes(&mut self, from_id: &str, into_id: &str) -> (usize, usize) {
+        let voters: HashSet<String> = self.tag_upvotes.values()
+            .filter(|upvote| upvote.tag_id == into_id)
+            .map(|upvote| upvote.user_id.clone())
+            .collect();
+        let before = self.tag_upvotes.len();
+        self.tag_upvotes.retain(|_, upvote| upvote.tag_id != from_id || !voters.contains(&upvote.user_id));
+        let dropped = before - self.tag_upvotes.len();
+        let mut moved = 0;
+        for upvote in self.tag_upvotes.values_mut().filter(|upvote| upvote.tag_id == from_id) {
+            upvote.tag_id = into_id.to_string();
+            moved += 1;
+        }
+        (moved, dropped)
+    }
+
+    pub fn recount_upvotes(&mut self, tag_id: &str) {
+        let count = self.tag_upvotes.values().filter(|upvote| upvote.tag_id == tag_id).count();
+        if let Some(tag) = self.approved_tags.get_mut(tag_id) {
+            tag.upvotes = count as i32;
+        }
+    }
+
+    // Mantém uma imagem e transfere para ela a união das tags aprovadas das demais.
+    // Em grupo exclusivo a classe da imagem mantida prevalece; tags de outra classe são recusadas ou substituídas
+    pub fn merge_duplicates(&mut self, keep_id: &str, image_ids: &[String], max_distance: u32, supersede: bool) -> Result<MergeDuplicatesResult, MergeError> {
+        let group_id = self.images.get(keep_id)
+            .map(|image| image.group_id.clone())
+            .ok_or_else(|| MergeError::ImageNotFound(keep_id.to_string()))?;
+        if image_ids.is_empty() {
+            return Err(MergeError::Invalid("image_ids cannot be empty".to_string()));
+        }
+        if image_ids.iter().any(|id| id == keep_id) {
+            return Err(MergeError::Invalid(format!("Image '{}' cannot be both kept and removed", keep_id)));
+        }
+        let mut removed_ids: Vec<String> = Vec::new();
+        for id in image_ids {
+            let image = self.images.get(id).ok_or_else(|| MergeError::ImageNotFound(id.clone()))?;
+            if image.group_id != group_id {
+                return Err(MergeError::Invalid(format!("Image '{}' belongs to another group", id)));
+            }
+            if !removed_ids.contains(id) {
+                removed_ids.push(id.clone());
+            }
+        }
+
+        // Só imagens do mesmo grupo de duplicatas da imagem mantida podem ser fundidas
+        let cluster: HashSet<String> = self.duplicate_clusters(Some(&group_id), max_distance)
+            .into_iter()
+            .find(|members| members.iter().any(|(id, _)| id == keep_id))
+            .map(|members| members.into_iter().map(|(id, _)| id).collect())
+            .unwrap_or_default();
+        if let Some(outsider) = removed_ids.iter().find(|id| !cluster.contains(id.as_str())) {
+            return Err(MergeError::Invalid(format!(
+                "Image '{}' is not a near-duplicate of '{}' (max distance {})", outsider, keep_id, max_distance
+            )));
+        }
+
+        let mut ranked = self.ranked_approved_tags(keep_id);
+        let mut incoming: Vec<(String, String)> = removed_ids.iter().flat_map(|id| self.ranked_approved_tags(id)).collect();
+        incoming.sort_by(|a, b| self.approved_tags[&b.0].approved_at.cmp(&self.approved_tags[&a.0].approved_at));
+        let incoming_ids: HashSet<String> = incoming.iter().map(|(id, _)| id.clone()).collect();
+        ranked.extend(incoming);
+        let conflicts: Vec<(String, String)> = self.exclusive_losers(&group_id, &ranked)
+            .into_iter()
+            .filter(|(id, _)| incoming_ids.contains(id))
+            .collect();
+        if !conflicts.is_empty() && self.labeling_policy_for_image(keep_id).on_conflict == ConflictResolution::Reject && !supersede {
+            return Err(MergeError::ExclusiveConflict(conflicts));
+        }
+        let superseded_tags: Vec<String> = conflicts.into_iter().map(|(id, _)| id).collect();
+        self.supersede_approved_tags(&superseded_tags);
+
+        let mut keeper_tags: HashMap<String, String> = self.approved_tags.values()
+            .filter(|tag| tag.image_id == keep_id)
+            .map(|tag| (tag.tag.trim().to_lowercase(), tag.id.clone()))
+            .collect();
+
+        let (mut moved_tags, mut merged_tags) = (0, 0);
+        let merged_tag_ids: Vec<String> = self.approved_tags.values()
+            .filter(|tag| removed_ids.contains(&tag.image_id))
+            .map(|tag| tag.id.clone())
+            .collect();
+        for tag_id in merged_tag_ids {
+            let key = self.approved_tags[&tag_id].tag.trim().to_lowercase();
+            match keeper_tags.get(&key).cloned() {
+                Some(target_id) => {
+                    // Tag repetida: os votos migram para a tag da imagem mantida
+                    self.fold_tag_upvotes(&tag_id, &target_id);
+                    self.retarget_comments(&tag_id, &target_id, keep_id);
+                    self.approved_tags.remove(&tag_id);
+                    merged_tags += 1;
+                }
+                None => {
+                    // A sugestão de origem some com a imagem removida
+                    if let Some(tag) = self.approved_tags.get_mut(&tag_id) {
+                        tag.image_id = keep_id.to_string();
+                        tag.suggestion_id = None;
+                    }
+                    self.retarget_comments(&tag_id, &tag_id, keep_id);
+                    keeper_tags.insert(key, tag_id);
+                    moved_tags += 1;
+                }
+            }
+        }
+
+        for tag_id in keeper_tags.values() {
+            self.recount_upvotes(tag_id);
+        }
+
+        let mut removed_files = Vec::new();
+        for id in &removed_ids {
+            if let Some(image) = self.images.remove(id) {
+                removed_files.extend(image.all_filenames());
+            }
+            self.remove_image_annotations(id);
+        }
+        self.prune_region_geometries();
+
+        Ok(MergeDuplicatesResult {
+            kept_image_id: keep_id.to_string(),
+            removed_image_ids: removed_ids,
+            moved_tags,
+            merged_tags,
+            superseded_tags,
+            removed_files,
+        })
+    }
+
+    // Exporta tudo ou apenas as imagens informadas (com seus grupos, tags e votos)
+    // label_depth: troca cada rótulo pelo ancestral nessa profundidade do vocabulário do grupo
+    // (os atributos pertencem ao rótulo original e são descartados na troca)
+    pub fn export_annotations(&self, image_ids: Option<&HashSet<String>>, label_depth: Option<usize>) -> serde_json::Value {
+        let includes = |image_id: &str| image_ids.is_none_or(|ids| ids.contains(image_id));
+        let relabel = |image_id: &str, tag: &str| -> Option<String> {
+            let depth = label_depth?;
+            let group_id = &self.images.get(image_id)?.group_id;
+            let vocabulary = self.groups.get(group_id)?.vocabulary.as_ref()?;
+            let label = vocabulary.label_at_depth(tag, depth);
+            (label != tag).then_some(label)
+        };
+
+        let images: HashMap<&str, &Image> = self.images.iter()
+            .filter(|(id, _)| includes(id))
+            .map(|(id, image)| (id.as_str(), image))
+            .collect();
+        let groups: HashMap<&str, &Group> = self.groups.iter()
+            .filter(|(id, _)| image_ids.is_none() || images.values().any(|image| &image.group_id == *id))
+            .map(|(id, group)| (id.as_str(), group))
+            .collect();
+        let tag_suggestions: HashMap<&str, Cow<TagSuggestion>> = self.tag_suggestions.iter()
+            .filter(|(_, suggestion)| includes(&suggestion.image_id))
+            .map(|(id, suggestion)| (id.as_str(), match relabel(&suggestion.image_id, &suggestion.tag) {
+                Some(tag) => Cow::Owned(TagSuggestion { tag, attributes: AttributeMap::new(), ..suggestion.clone() }),
+                None => Cow::Borrowed(suggestion),
+            }))
+            .collect();
+        // Em profundidade fixa, vários rótulos da mesma imagem podem virar o mesmo ancestral: mantém o primeiro aprovado
+        let mut approved: Vec<(&String, &ApprovedTag)> = self.approved_tags.iter()
+            .filter(|(_, tag)| includes(&tag.image_id))
+            .collect();
+        approved.sort_by(|(a_id, a), (b_id, b)| a.approved_at.cmp(&b.approved_at).then_with(|| a_id.cmp(b_id)));
+        let mut seen = HashSet::new();
+        let approved_tags: HashMap<&str, Cow<ApprovedTag>> = approved.into_iter()
+            .map(|(id, tag)| (id.as_str(), match relabel(&tag.image_id, &tag.tag) {
+                Some(label) => Cow::Owned(ApprovedTag { tag: label, attributes: AttributeMap::new(), ..tag.clone() }),
+                None => Cow::Borrowed(tag),
+            }))
+            .filter(|(_, tag)| label_depth.is_none() || seen.insert((tag.image_id.clone(), tag.tag.to_lowercase())))
+            .collect();
+        let tag_upvotes: HashMap<&str, &TagUpvote> = self.tag_upvotes.iter()
+            .filter(|(_, upvote)| {
+                image_ids.is_none()
+                    || approved_tags.contains_key(upvote.tag_id.as_str())
+                    || tag_suggestions.contains_key(upvote.tag_id.as_str())
+            })
+            .map(|(id, upvote)| (id.as_str(), upvote))
+            .collect();
+        let region_annotations: HashMap<&str, &RegionAnnotation> = self.region_annotations.iter()
+            .filter(|(_, region)| includes(&region.image_id))
+            .map(|(id, region)| (id.as_str(), region))
+            .collect();
+        let region_geometries: HashMap<&str, RegionGeometry> = region_annotations.iter()
+            .filter(|(_, region)| region.kind != RegionKind::Box)
+            .filter_map(|(id, _)| Some((*id, load_geometry(id)?)))
+            .collect();
+        let keypoint_annotations: HashMap<&str, &KeypointAnnotation> = self.keypoint_annotations.iter()
+            .filter(|(_, annotation)| includes(&annotation.image_id))
+            .map(|(id, annotation)| (id.as_str(), annotation))
+            .collect();
+        let captions: HashMap<&str, &Caption> = self.captions.iter()
+            .filter(|(_, caption)| includes(&caption.image_id))
+            .map(|(id, caption)| (id.as_str(), caption))
+            .collect();
+        let vqa_questions: HashMap<&str, &VqaQuestion> = self.vqa_questions.iter()
+            .filter(|(_, question)| includes(&question.image_id))
+            .map(|(id, question)| (id.as_str(), question))
+            .collect();
+        let vqa_answers: HashMap<&str, &VqaAnswer> = self.vqa_answers.iter()
+            .filter(|(_, answer)| vqa_questions.contains_key(answer.question_id.as_str()))
+            .map(|(id, answer)| (id.as_str(), answer))
+            .collect();
+        let pairwise_comparisons: HashMap<&str, &PairwiseComparison> = self.pairwise_comparisons.iter()
+            .filter(|(_, comparison)| includes(&comparison.image_a) && includes(&comparison.image_b))
+            .map(|(id, comparison)| (id.as_str(), comparison))
+            .collect();
+        let likert_ratings: HashMap<&str, &LikertRating> = self.likert_ratings.iter()
+            .filter(|(_, rating)| includes(&rating.image_id))
+            .map(|(id, rating)| (id.as_str(), rating))
+            .collect();
+        // Placares calculados sobre todas as avaliações do grupo
+        let rating_scores: Vec<CriterionScores> = groups.values()
+            .flat_map(|group| group.rating_criteria.iter().map(|criterion| (group.id.as_str(), criterion.id.as_str())))
+            .filter_map(|(group_id, criterion_id)| self.criterion_scores(group_id, criterion_id).ok())
+            .collect();
+
         serde_json::to_value(AnnotationsExport {
-            groups: &self.groups,
-            images: &self.images,
-            tag_suggestions: &self.tag_suggestions,
-            approved_tags: &self.approved_tags,
-            tag_upvotes: &self.tag_upvotes,
+            groups,
+            images,
+            tag_suggestions,
+            approved_tags,
+            tag_upvotes,
+            region_annotations,
+            region_geometries,
+            keypoint_annotations,
+            captions,
+            image_text_pairs: self.image_text_pairs(image_ids),
+            vqa_questions,
+            vqa_answers,
+            pairwise_comparisons,
+            likert_ratings,
+            rating_scores,
         }).unwrap_or_else(|_| serde_json::json!({ "error": "Failed to export annotations" }))
     }
 }
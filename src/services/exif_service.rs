@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use exif::{Context, In, Tag};
+use image::{DynamicImage, ImageFormat, ImageOutputFormat};
+
+use crate::models::MetadataPolicy;
+use super::ingest_service::file_extension;
+
+pub const RESTRICTED_DIR: &str = "restricted";
+
+const JPEG_QUALITY: u8 = 92;
+
+// Tags que identificam o equipamento ou o dono da câmera
+const DEVICE_TAGS: [Tag; 4] = [Tag::BodySerialNumber, Tag::LensSerialNumber, Tag::CameraOwnerName, Tag::ImageUniqueID];
+
+pub struct ProcessedImage {
+    pub bytes: Vec<u8>,
+    pub original_metadata: Option<BTreeMap<String, String>>,
+}
+
+fn unchanged(bytes: &[u8]) -> ProcessedImage {
+    ProcessedImage { bytes: bytes.to_vec(), original_metadata: None }
+}
+
+// Aplica a orientação EXIF e remove metadados sensíveis conforme a política do grupo.
+// Ao reescrever a imagem todos os metadados EXIF são descartados (não só os sensíveis), por isso a
+// orientação é sempre aplicada nesse caso; com retain_original eles ficam no armazenamento restrito.
+// Formatos sem codificador (WebP) são guardados como vieram quando só a orientação seria corrigida.
+pub fn process_upload(original_name: &str, bytes: &[u8], policy: &MetadataPolicy) -> Result<ProcessedImage, String> {
+    let Ok(exif) = exif::Reader::new().read_from_container(&mut Cursor::new(bytes)) else {
+        return Ok(unchanged(bytes));
+    };
+
+    let orientation = exif.get_field(Tag::Orientation, In::PRIMARY)
+        .and_then(|field| field.value.get_uint(0))
+        .unwrap_or(1);
+    let has_location = exif.fields().any(|field| field.tag.context() == Context::Gps);
+    let has_device = exif.fields().any(|field| DEVICE_TAGS.contains(&field.tag));
+
+    let needs_rewrite = (policy.normalize_orientation && orientation != 1)
+        || (policy.strip_location && has_location)
+        || (policy.strip_device && has_device);
+    if !needs_rewrite {
+        return Ok(unchanged(bytes));
+    }
+
+    let must_strip = (policy.strip_location && has_location) || (policy.strip_device && has_device);
+    let Some(format) = output_format(original_name) else {
+        if must_strip {
+            return Err(format!("Cannot rewrite metadata of '.{}' files", file_extension(original_name)));
+        }
+        println!("⚠️ Cannot re-encode '.{}' files; '{}' stored without orientation normalization", file_extension(original_name), original_name);
+        return Ok(unchanged(bytes));
+    };
+    let decoded = image::load_from_memory(bytes).map_err(|e| format!("Failed to decode image: {}", e))?;
+    let oriented = apply_orientation(decoded, orientation);
+
+    let mut output = Cursor::new(Vec::new());
+    let encoded = match format {
+        ImageOutputFormat::Jpeg(_) => DynamicImage::ImageRgb8(oriented.to_rgb8()).write_to(&mut output, format),
+        _ => oriented.write_to(&mut output, format),
+    };
+    encoded.map_err(|e| format!("Failed to encode image: {}", e))?;
+    println!("🧹 Re-encoded '{}': {} EXIF fields dropped{}", original_name, exif.fields().count(),
+             if policy.retain_original { " (kept in restricted storage)" } else { "" });
+
+    let original_metadata = policy.retain_original.then(|| {
+        exif.fields()
+            .map(|field| (
+                format!("{}.{}", field.ifd_num, field.tag),
+                field.display_value().with_unit(&exif).to_string(),
+            ))
+            .collect()
+    });
+
+    Ok(ProcessedImage { bytes: output.into_inner(), original_metadata })
+}
+
+fn output_format(original_name: &str) -> Option<ImageOutputFormat> {
+    // .jfif é JPEG, mas o crate image não reconhece a extensão
+    let format = match file_extension(original_name).as_str() {
+        "jfif" => ImageFormat::Jpeg,
+        extension => ImageFormat::from_extension(extension)?,
+    };
+    match format {
+        ImageFormat::Jpeg => Some(ImageOutputFormat::Jpeg(JPEG_QUALITY)),
+        ImageFormat::Png => Some(ImageOutputFormat::Png),
+        ImageFormat::Tiff => Some(ImageOutputFormat::Tiff),
+        ImageFormat::Bmp => Some(ImageOutputFormat::Bmp),
+        ImageFormat::Gif => Some(ImageOutputFormat::Gif),
+        _ => None,
+    }
+}
+
+// Valores 1-8 da tag Orientation (EXIF 2.3, seção 4.6.4)
+fn apply_orientation(image: DynamicImage, orientation: u32) -> DynamicImage {
+    match orientation {
+        2 => image.fliph(),
+        3 => image.rotate180(),
+        4 => image.flipv(),
+        5 => image.rotate90().fliph(),
+        6 => image.rotate90(),
+        7 => image.rotate270().fliph(),
+        8 => image.rotate270(),
+        _ => image,
+    }
+}
+
+pub fn restricted_metadata_path(filename: &str) -> String {
+    format!("{}/{}.json", RESTRICTED_DIR, filename)
+}
+
+pub fn store_restricted_metadata(filename: &str, metadata: &BTreeMap<String, String>) -> std::io::Result<()> {
+    std::fs::create_dir_all(RESTRICTED_DIR)?;
+    let json = serde_json::to_string_pretty(metadata)?;
+    std::fs::write(restricted_metadata_path(filename), json)
+}
+
+pub fn load_restricted_metadata(filename: &str) -> Option<serde_json::Value> {
+    let json = std::fs::read_to_string(restricted_metadata_path(filename)).ok()?;
+    serde_json::from_str(&json).ok()
+}
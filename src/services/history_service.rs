@@ -0,0 +1,236 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use chrono::{DateTime, FixedOffset};
+use serde_json::Value;
+
+use crate::models::{AnnotationEvent, HistoryEntity, ImageStateAt};
+use super::DataService;
+
+type TagRecords = BTreeMap<(HistoryEntity, String), Value>;
+
+// Eventos mantidos por imagem; os mais antigos são descartados
+pub const MAX_EVENTS_PER_IMAGE: usize = 500;
+
+// Registros de tags das imagens antes de uma operação; record_history compara com o estado depois dela
+pub struct HistoryCheckpoint(HashMap<String, TagRecords>);
+
+pub fn parse_timestamp(value: &str) -> Result<DateTime<FixedOffset>, String> {
+    DateTime::parse_from_rfc3339(value.trim()).map_err(|_| format!("Invalid timestamp '{}'; use RFC 3339 (e.g. 2025-09-23T10:30:00Z)", value.trim()))
+}
+
+fn action_for(entity: HistoryEntity, before: Option<&Value>, after: Option<&Value>) -> &'static str {
+    match (entity, before, after) {
+        (HistoryEntity::TagSuggestion, None, _) => "suggested",
+        (HistoryEntity::ApprovedTag, None, _) => "approved",
+        (HistoryEntity::TagUpvote, None, _) => "voted",
+        (HistoryEntity::TagUpvote, Some(_), None) => "unvoted",
+        (_, Some(_), None) => "removed",
+        (HistoryEntity::TagSuggestion, Some(before), Some(after)) if before.get("status") != after.get("status") => "reviewed",
+        _ => "updated",
+    }
+}
+
+fn created_at(entity: HistoryEntity, record: &Value) -> Option<DateTime<FixedOffset>> {
+    let field = match entity {
+        HistoryEntity::TagSuggestion => "suggested_at",
+        HistoryEntity::ApprovedTag => "approved_at",
+        HistoryEntity::TagUpvote => "upvoted_at",
+    };
+    record.get(field).and_then(Value::as_str).and_then(|value| parse_timestamp(value).ok())
+}
+
+impl DataService {
+    // Registros de várias imagens numa única passada pelas coleções.
+    // O contador de votos das tags aprovadas fica de fora: ele é derivado dos próprios votos
+    fn tag_records_by_image(&self, image_ids: &HashSet<&str>) -> HashMap<String, TagRecords> {
+        let mut records: HashMap<String, TagRecords> = image_ids.iter()
+            .map(|image_id| (image_id.to_string(), TagRecords::new()))
+            .collect();
+        let mut tag_images: HashMap<&str, &str> = HashMap::new();
+        for suggestion in self.tag_suggestions.values().filter(|suggestion| image_ids.contains(suggestion.image_id.as_str())) {
+            tag_images.insert(&suggestion.id, &suggestion.image_id);
+            if let (Ok(value), Some(image_records)) = (serde_json::to_value(suggestion), records.get_mut(&suggestion.image_id)) {
+                image_records.insert((HistoryEntity::TagSuggestion, suggestion.id.clone()), value);
+            }
+        }
+        for tag in self.approved_tags.values().filter(|tag| image_ids.contains(tag.image_id.as_str())) {
+            tag_images.insert(&tag.id, &tag.image_id);
+            if let (Ok(mut value), Some(image_records)) = (serde_json::to_value(tag), records.get_mut(&tag.image_id)) {
+                if let Some(fields) = value.as_object_mut() {
+                    fields.remove("upvotes");
+                }
+                image_records.insert((HistoryEntity::ApprovedTag, tag.id.clone()), value);
+            }
+        }
+        for upvote in self.tag_upvotes.values() {
+            let Some(image_id) = tag_images.get(upvote.tag_id.as_str()) else {
+                continue;
+            };
+            if let (Ok(value), Some(image_records)) = (serde_json::to_value(upvote), records.get_mut(*image_id)) {
+                image_records.insert((HistoryEntity::TagUpvote, upvote.id.clone()), value);
+            }
+        }
+        records
+    }
+
+    fn tag_records(&self, image_id: &str) -> TagRecords {
+        self.tag_records_by_image(&HashSet::from([image_id])).remove(image_id).unwrap_or_default()
+    }
+
+    pub fn history_checkpoint<'a>(&self, image_ids: impl IntoIterator<Item = &'a str>) -> HistoryCheckpoint {
+        HistoryCheckpoint(self.tag_records_by_image(&image_ids.into_iter().collect()))
+    }
+
+    // Grava um evento por registro criado, alterado ou removido desde o checkpoint; imagens apagadas são ignoradas
+    pub fn record_history(&mut self, checkpoint: HistoryCheckpoint, actor: &str) -> usize {
+        self.record_events(checkpoint, actor, None)
+    }
+
+    // Imagens recém-criadas por cópia: cada registro delas entra no histórico como "copied"
+    pub fn record_copy_history(&mut self, image_ids: &[String], actor: &str) -> usize {
+        let checkpoint = HistoryCheckpoint(image_ids.iter().map(|id| (id.clone(), TagRecords::new())).collect());
+        self.record_events(checkpoint, actor, Some("copied"))
+    }
+
+    fn record_events(&mut self, checkpoint: HistoryCheckpoint, actor: &str, created_action: Option<&'static str>) -> usize {
+        let mut image_ids: Vec<String> = checkpoint.0.keys()
+            .filter(|image_id| self.images.contains_key(*image_id))
+            .cloned()
+            .collect();
+        image_ids.sort();
+        let mut before_by_image = checkpoint.0;
+        let mut after_by_image = self.tag_records_by_image(&image_ids.iter().map(String::as_str).collect());
+        let mut recorded = 0;
+        for image_id in &image_ids {
+            let mut before = before_by_image.remove(image_id).unwrap_or_default();
+            let mut after = after_by_image.remove(image_id).unwrap_or_default();
+            let keys: BTreeSet<(HistoryEntity, String)> = before.keys().chain(after.keys()).cloned().collect();
+            for key in keys {
+                let (old, new) = (before.remove(&key), after.remove(&key));
+                if old == new {
+                    continue;
+                }
+                let (entity, entity_id) = key;
+                let action = match (created_action, &old) {
+                    (Some(action), None) => action,
+                    _ => action_for(entity, old.as_ref(), new.as_ref()),
+                };
+                let event = AnnotationEvent::new(image_id.clone(), entity, entity_id, action, actor.to_string(), old, new);
+                self.annotation_history.push(event);
+                recorded += 1;
+            }
+        }
+        if recorded > 0 {
+            self.compact_history(&image_ids);
+        }
+        recorded
+    }
+
+    // Mantém só os MAX_EVENTS_PER_IMAGE eventos mais recentes de cada imagem e guarda o instante do último
+    // descartado: antes dele a reconstrução do estado deixa de ser exata
+    fn compact_history(&mut self, image_ids: &[String]) {
+        let touched: HashSet<&str> = image_ids.iter().map(String::as_str).collect();
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for event in self.annotation_history.iter().filter(|event| touched.contains(event.image_id.as_str())) {
+            *counts.entry(event.image_id.clone()).or_default() += 1;
+        }
+        let mut excess: HashMap<String, usize> = counts.into_iter()
+            .filter(|(_, count)| *count > MAX_EVENTS_PER_IMAGE)
+            .map(|(image_id, count)| (image_id, count - MAX_EVENTS_PER_IMAGE))
+            .collect();
+        if excess.is_empty() {
+            return;
+        }
+        let mut compacted_at: HashMap<String, String> = HashMap::new();
+        self.annotation_history.retain(|event| match excess.get_mut(&event.image_id) {
+            Some(remaining) if *remaining > 0 => {
+                *remaining -= 1;
+                compacted_at.insert(event.image_id.clone(), event.at.clone());
+                false
+            }
+            _ => true,
+        });
+        for (image_id, at) in compacted_at {
+            println!("🗜️ Compacted history of image '{}' up to {}", image_id, at);
+            if let Some(image) = self.images.get_mut(&image_id) {
+                image.history_compacted_at = Some(at);
+            }
+        }
+    }
+
+    pub fn image_history(
+        &self,
+        image_id: &str,
+        since: Option<DateTime<FixedOffset>>,
+        until: Option<DateTime<FixedOffset>>,
+    ) -> Vec<&AnnotationEvent> {
+        self.annotation_history.iter()
+            .filter(|event| event.image_id == image_id)
+            .filter(|event| {
+                let at = parse_timestamp(&event.at).ok();
+                since.is_none_or(|since| at.is_some_and(|at| at >= since))
+                    && until.is_none_or(|until| at.is_some_and(|at| at <= until))
+            })
+            .collect()
+    }
+
+    // Parte dos registros anteriores ao histórico (o "before" do primeiro evento, ou o registro atual
+    // se ele nunca mudou) e reaplica os eventos até o instante pedido
+    pub fn image_state_at(&self, image_id: &str, at: DateTime<FixedOffset>) -> ImageStateAt {
+        let events: Vec<&AnnotationEvent> = self.image_history(image_id, None, None);
+        let mut state = TagRecords::new();
+        let mut seen = HashSet::new();
+        for event in &events {
+            let key = (event.entity, event.entity_id.clone());
+            if seen.insert(key.clone()) {
+                if let Some(before) = &event.before {
+                    if created_at(event.entity, before).is_none_or(|created| created <= at) {
+                        state.insert(key, before.clone());
+                    }
+                }
+            }
+        }
+        for (key, record) in self.tag_records(image_id) {
+            if !seen.contains(&key) && created_at(key.0, &record).is_none_or(|created| created <= at) {
+                state.insert(key, record);
+            }
+        }
+        for event in events.iter().filter(|event| parse_timestamp(&event.at).is_ok_and(|event_at| event_at <= at)) {
+            let key = (event.entity, event.entity_id.clone());
+            match &event.after {
+                Some(after) => state.insert(key, after.clone()),
+                None => state.remove(&key),
+            };
+        }
+
+        let mut votes: HashMap<String, usize> = HashMap::new();
+        for ((entity, _), record) in &state {
+            if let (HistoryEntity::TagUpvote, Some(tag_id)) = (entity, record.get("tag_id").and_then(Value::as_str)) {
+                *votes.entry(tag_id.to_string()).or_default() += 1;
+            }
+        }
+        let mut result = ImageStateAt {
+            image_id: image_id.to_string(),
+            at: at.to_rfc3339(),
+            tag_suggestions: Vec::new(),
+            approved_tags: Vec::new(),
+            tag_upvotes: Vec::new(),
+            complete: self.images.get(image_id)
+                .and_then(|image| image.history_compacted_at.as_deref())
+                .and_then(|compacted_at| parse_timestamp(compacted_at).ok())
+                .is_none_or(|compacted_at| at >= compacted_at),
+        };
+        for ((entity, entity_id), mut record) in state {
+            match entity {
+                HistoryEntity::TagSuggestion => result.tag_suggestions.push(record),
+                HistoryEntity::ApprovedTag => {
+                    if let Some(fields) = record.as_object_mut() {
+                        fields.insert("upvotes".to_string(), votes.get(&entity_id).copied().unwrap_or(0).into());
+                    }
+                    result.approved_tags.push(record);
+                }
+                HistoryEntity::TagUpvote => result.tag_upvotes.push(record),
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::{HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use serde::Serialize;
+
+use super::DataService;
+use super::ingest_service::{insert_stored_image, is_supported_image, store_image_file, IngestError};
+
+pub const PROCESSED_DIR: &str = "processed";
+pub const QUARANTINE_DIR: &str = "quarantine";
+pub const HOT_FOLDER_USER: &str = "hot-folder";
+
+const DEFAULT_SCAN_INTERVAL_SECS: u64 = 10;
+// Arquivos modificados há menos tempo que isso ainda podem estar sendo copiados
+const MIN_FILE_AGE: Duration = Duration::from_secs(2);
+const RECENT_EVENTS_LIMIT: usize = 50;
+pub const MAX_FILE_BYTES: u64 = 50 * 1024 * 1024;
+
+// Arquivo que não pôde sair da pasta de entrada; identificado pelo nome e pela data de modificação
+type FileKey = (String, SystemTime);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HotFolderEvent {
+    pub file: String,
+    pub status: String, // "ingested" or "quarantined"
+    pub image_id: Option<String>,
+    pub error: Option<String>,
+    pub at: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HotFolderStatus {
+    pub path: String,
+    pub group_id: String,
+    pub last_scan_at: Option<String>,
+    pub last_error: Option<String>,
+    pub ingested: u64,
+    pub quarantined: u64,
+    pub recent: VecDeque<HotFolderEvent>,
+    #[serde(skip)]
+    unmovable: HashSet<FileKey>, // já tratados, mas presos na pasta; não são ingeridos de novo
+}
+
+impl HotFolderStatus {
+    fn new(path: String, group_id: String) -> Self {
+        Self {
+            path,
+            group_id,
+            last_scan_at: None,
+            last_error: None,
+            ingested: 0,
+            quarantined: 0,
+            recent: VecDeque::new(),
+            unmovable: HashSet::new(),
+        }
+    }
+
+    fn push_event(&mut self, event: HotFolderEvent) {
+        if event.status == "ingested" {
+            self.ingested += 1;
+        } else {
+            self.quarantined += 1;
+        }
+        self.recent.push_front(event);
+        self.recent.truncate(RECENT_EVENTS_LIMIT);
+    }
+}
+
+pub struct HotFolderService {
+    pub interval: Duration,
+    pub folders: Vec<HotFolderStatus>,
+}
+
+impl HotFolderService {
+    // HOT_FOLDERS="/mnt/cam1=group-001;/mnt/cam2=group-004"
+    pub fn from_env() -> Self {
+        let folders = std::env::var("HOT_FOLDERS")
+            .unwrap_or_default()
+            .split(';')
+            .filter_map(|entry| {
+                let (path, group_id) = entry.trim().rsplit_once('=')?;
+                let (path, group_id) = (path.trim(), group_id.trim());
+                if path.is_empty() || group_id.is_empty() {
+                    return None;
+                }
+                Some(HotFolderStatus::new(path.to_string(), group_id.to_string()))
+            })
+            .collect();
+
+        let interval_secs = std::env::var("HOT_FOLDER_INTERVAL_SECS")
+            .ok()
+            .and_then(|value| value.parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_SCAN_INTERVAL_SECS);
+
+        Self {
+            interval: Duration::from_secs(interval_secs),
+            folders,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.folders.is_empty()
+    }
+}
+
+// Inicia a thread que varre periodicamente as pastas configuradas
+pub fn spawn_watcher(
+    hot_folders: actix_web::web::Data<Mutex<HotFolderService>>,
+    data_service: actix_web::web::Data<Mutex<DataService>>,
+) {
+    std::thread::spawn(move || loop {
+        let (interval, folders) = {
+            let service = hot_folders.lock().unwrap();
+            let folders: Vec<(String, String)> = service.folders.iter()
+                .map(|folder| (folder.path.clone(), folder.group_id.clone()))
+                .collect();
+            (service.interval, folders)
+        };
+
+        for (index, (path, group_id)) in folders.iter().enumerate() {
+            let mut unmovable = std::mem::take(&mut hot_folders.lock().unwrap().folders[index].unmovable);
+            let result = scan_folder(Path::new(path), group_id, &data_service, &mut unmovable);
+
+            let mut service = hot_folders.lock().unwrap();
+            let status = &mut service.folders[index];
+            status.unmovable = unmovable;
+            status.last_scan_at = Some(chrono::Utc::now().to_rfc3339());
+            match result {
+                Ok(events) => {
+                    status.last_error = None;
+                    for event in events {
+                        status.push_event(event);
+                    }
+                }
+                Err(e) => {
+                    println!("❌ Hot folder '{}' scan failed: {}", path, e);
+                    status.last_error = Some(e.to_string());
+                }
+            }
+        }
+
+        std::thread::sleep(interval);
+    });
+}
+
+fn scan_folder(
+    folder: &Path,
+    group_id: &str,
+    data_service: &Mutex<DataService>,
+    unmovable: &mut HashSet<FileKey>,
+) -> std::io::Result<Vec<HotFolderEvent>> {
+    let processed_dir = folder.join(PROCESSED_DIR);
+    let quarantine_dir = folder.join(QUARANTINE_DIR);
+    fs::create_dir_all(&processed_dir)?;
+    fs::create_dir_all(&quarantine_dir)?;
+
+    // Decodificação, EXIF e hash rodam fora do lock; ele só é pego para ler a política e registrar cada imagem
+    let policy = data_service.lock().unwrap().get_group(group_id).map(|group| group.metadata_policy.clone());
+
+    let mut events = Vec::new();
+    let mut present = HashSet::new();
+    let mut ingested_any = false;
+    for entry in fs::read_dir(folder)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        if file_name.starts_with('.') {
+            continue;
+        }
+        let age = metadata.modified()
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .unwrap_or_default();
+        if age < MIN_FILE_AGE {
+            continue;
+        }
+        let key = (file_name.clone(), metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH));
+        present.insert(key.clone());
+        if unmovable.contains(&key) {
+            continue;
+        }
+
+        let source = entry.path();
+        let outcome = if !is_supported_image(&file_name) {
+            Err(super::ingest_service::unsupported_type_message())
+        } else if metadata.len() > MAX_FILE_BYTES {
+            Err(format!("File exceeds {} bytes", MAX_FILE_BYTES))
+        } else {
+            fs::read(&source)
+                .map_err(|e| format!("Failed to read file: {}", e))
+                .and_then(|bytes| {
+                    let policy = policy.as_ref().ok_or_else(|| IngestError::GroupNotFound.to_string())?;
+                    let stored = store_image_file(&file_name, &bytes, policy).map_err(|e| e.to_string())?;
+                    let mut data = data_service.lock().unwrap();
+                    insert_stored_image(&mut data, group_id, &file_name, stored, HOT_FOLDER_USER).map_err(|e| e.to_string())
+                })
+        };
+
+        // Falha ao mover afeta só este arquivo: ele fica marcado para não ser ingerido de novo
+        let at = chrono::Utc::now().to_rfc3339();
+        match outcome {
+            Ok(image_id) => {
+                ingested_any = true;
+                println!("📥 Hot folder ingested '{}' into group '{}' (ID: {})", file_name, group_id, image_id);
+                let error = match move_file(&source, &processed_dir, &file_name) {
+                    Ok(_) => None,
+                    Err(e) => {
+                        println!("❌ Hot folder could not move '{}' to {}/: {}", file_name, PROCESSED_DIR, e);
+                        unmovable.insert(key);
+                        Some(format!("Ingested, but could not be moved to {}/: {}", PROCESSED_DIR, e))
+                    }
+                };
+                events.push(HotFolderEvent { file: file_name, status: "ingested".to_string(), image_id: Some(image_id), error, at });
+            }
+            Err(error) => {
+                match move_file(&source, &quarantine_dir, &file_name) {
+                    Ok(moved_to) => {
+                        let mut error_file = moved_to.into_os_string();
+                        error_file.push(".error.txt");
+                        let _ = fs::write(error_file, &error);
+                    }
+                    Err(e) => {
+                        println!("❌ Hot folder could not move '{}' to {}/: {}", file_name, QUARANTINE_DIR, e);
+                        unmovable.insert(key);
+                    }
+                }
+                println!("⚠️ Hot folder quarantined '{}': {}", file_name, error);
+                events.push(HotFolderEvent { file: file_name, status: "quarantined".to_string(), image_id: None, error: Some(error), at });
+            }
+        }
+    }
+
+    if ingested_any {
+        let _ = data_service.lock().unwrap().save_to_json();
+    }
+
+    // Arquivos removidos ou alterados à mão deixam de ser ignorados
+    unmovable.retain(|key| present.contains(key));
+    Ok(events)
+}
+
+// Move o arquivo sem sobrescrever um homônimo já existente no destino
+fn move_file(source: &Path, target_dir: &Path, file_name: &str) -> std::io::Result<PathBuf> {
+    let mut target = target_dir.join(file_name);
+    let mut counter = 1;
+    while target.exists() {
+        target = target_dir.join(format!("{}_{}", counter, file_name));
+        counter += 1;
+    }
+    fs::rename(source, &target)?;
+    Ok(target)
+}
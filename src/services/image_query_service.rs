@@ -0,0 +1,210 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{Image, ImageListQuery};
+use super::DataService;
+
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+pub const MAX_PAGE_SIZE: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortField {
+    UploadedAt,
+    Name,
+    TagCount,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(untagged)]
+enum SortValue {
+    Number(i64),
+    Text(String),
+}
+
+// Cursor opaco: chave de ordenação + id do último item da página anterior
+#[derive(Debug, Serialize, Deserialize)]
+struct Cursor {
+    key: SortValue,
+    id: String,
+}
+
+impl Cursor {
+    fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(value).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImageListItem<'a> {
+    #[serde(flatten)]
+    pub image: &'a Image,
+    pub tag_count: usize,
+    pub upvotes: i32,
+    pub pending_suggestions: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImagePage<'a> {
+    pub images: Vec<ImageListItem<'a>>,
+    pub next_cursor: Option<String>,
+    pub total: usize,
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|date| date.with_timezone(&Utc))
+}
+
+fn parse_tag_list(value: &Option<String>) -> Vec<String> {
+    value.as_deref()
+        .unwrap_or("")
+        .split(',')
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+#[derive(Default)]
+struct ImageStats {
+    tags: HashSet<String>,
+    implied_tags: HashSet<String>, // tags e seus ancestrais, usados nos filtros
+    upvotes: i32,
+    pending: usize,
+}
+
+impl DataService {
+    pub fn list_images(&self, query: &ImageListQuery) -> Result<ImagePage<'_>, String> {
+        let sort = match query.sort.as_deref().unwrap_or("uploaded_at") {
+            "uploaded_at" => SortField::UploadedAt,
+            "name" => SortField::Name,
+            "tag_count" => SortField::TagCount,
+            other => return Err(format!("Invalid sort field '{}'", other)),
+        };
+        let descending = match query.order.as_deref().unwrap_or("desc") {
+            "asc" => false,
+            "desc" => true,
+            other => return Err(format!("Invalid sort order '{}'", other)),
+        };
+        let uploaded_from = match &query.uploaded_from {
+            Some(value) => Some(parse_timestamp(value).ok_or("Invalid uploaded_from date")?),
+            None => None,
+        };
+        let uploaded_to = match &query.uploaded_to {
+            Some(value) => Some(parse_timestamp(value).ok_or("Invalid uploaded_to date")?),
+            None => None,
+        };
+        let cursor = match &query.cursor {
+            Some(value) => Some(Cursor::decode(value).ok_or("Invalid cursor")?),
+            None => None,
+        };
+        let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let matching_ids = match query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+            Some(q) => Some(self.search_image_ids(q).map_err(|e| format!("Invalid query: {}", e))?),
+            None => None,
+        };
+        let has_tags = parse_tag_list(&query.has_tags);
+        let missing_tags = parse_tag_list(&query.missing_tags);
+
+        let member_groups: Option<HashSet<&str>> = query.username.as_ref().map(|username| {
+            self.groups.values()
+                .filter(|group| group.members.contains(username))
+                .map(|group| group.id.as_str())
+                .collect()
+        });
+
+        let mut stats: HashMap<&str, ImageStats> = HashMap::new();
+        for tag in self.approved_tags.values() {
+            let implied = self.images.get(&tag.image_id)
+                .map(|image| self.implied_labels(&image.group_id, &tag.tag))
+                .unwrap_or_default();
+            let entry = stats.entry(tag.image_id.as_str()).or_default();
+            entry.tags.insert(tag.tag.trim().to_lowercase());
+            entry.implied_tags.insert(tag.tag.trim().to_lowercase());
+            entry.implied_tags.extend(implied);
+            entry.upvotes += tag.upvotes;
+        }
+        for suggestion in self.tag_suggestions.values().filter(|suggestion| suggestion.status.is_open()) {
+            stats.entry(suggestion.image_id.as_str()).or_default().pending += 1;
+        }
+        let empty = ImageStats::default();
+
+        let mut matches: Vec<(SortValue, ImageListItem)> = self.images.values()
+            .filter(|image| matching_ids.as_ref().is_none_or(|ids| ids.contains(&image.id)))
+            .filter(|image| member_groups.as_ref().is_none_or(|groups| groups.contains(image.group_id.as_str())))
+            .filter(|image| query.group_id.as_ref().is_none_or(|group_id| &image.group_id == group_id))
+            .filter(|image| query.uploaded_by.as_ref().is_none_or(|user| &image.uploaded_by == user))
+            .filter(|image| {
+                if uploaded_from.is_none() && uploaded_to.is_none() {
+                    return true;
+                }
+                let Some(uploaded_at) = parse_timestamp(&image.uploaded_at) else {
+                    return false;
+                };
+                uploaded_from.is_none_or(|from| uploaded_at >= from)
+                    && uploaded_to.is_none_or(|to| uploaded_at <= to)
+            })
+            .filter_map(|image| {
+                let image_stats = stats.get(image.id.as_str()).unwrap_or(&empty);
+                if !has_tags.iter().all(|tag| image_stats.implied_tags.contains(tag))
+                    || missing_tags.iter().any(|tag| image_stats.implied_tags.contains(tag))
+                    || query.pending_review.is_some_and(|pending| pending != (image_stats.pending > 0))
+                    || query.min_upvotes.is_some_and(|min| image_stats.upvotes < min)
+                {
+                    return None;
+                }
+
+                let key = match sort {
+                    SortField::UploadedAt => SortValue::Number(
+                        parse_timestamp(&image.uploaded_at).map(|date| date.timestamp_millis()).unwrap_or(0),
+                    ),
+                    SortField::Name => SortValue::Text(image.original_name.to_lowercase()),
+                    SortField::TagCount => SortValue::Number(image_stats.tags.len() as i64),
+                };
+                Some((key, ImageListItem {
+                    image,
+                    tag_count: image_stats.tags.len(),
+                    upvotes: image_stats.upvotes,
+                    pending_suggestions: image_stats.pending,
+                }))
+            })
+            .collect();
+
+        // Ordenação estável por (chave, id) para que o cursor seja determinístico
+        let compare = |a: &SortValue, a_id: &str, b: &SortValue, b_id: &str| -> Ordering {
+            let ordering = a.cmp(b).then_with(|| a_id.cmp(b_id));
+            if descending { ordering.reverse() } else { ordering }
+        };
+        matches.sort_by(|(a, a_item), (b, b_item)| compare(a, &a_item.image.id, b, &b_item.image.id));
+
+        let total = matches.len();
+        let start = match &cursor {
+            Some(cursor) => matches
+                .iter()
+                .position(|(key, item)| compare(key, &item.image.id, &cursor.key, &cursor.id) == Ordering::Greater)
+                .unwrap_or(total),
+            None => 0,
+        };
+
+        let mut page: Vec<(SortValue, ImageListItem)> = matches.into_iter().skip(start).take(limit + 1).collect();
+        let next_cursor = if page.len() > limit {
+            page.truncate(limit);
+            page.last().map(|(key, item)| Cursor { key: key.clone(), id: item.image.id.clone() }.encode())
+        } else {
+            None
+        };
+
+        Ok(ImagePage {
+            images: page.into_iter().map(|(_, item)| item).collect(),
+            next_cursor,
+            total,
+        })
+    }
+}
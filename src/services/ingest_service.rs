@@ -0,0 +1,238 @@
+use std::io::Write;
+use std::path::Path;
+use crate::models::{BoundingBox, FlaggedAnnotations, Image, ImageVersion, MetadataPolicy, RegionGeometry, RegionKind};
+use super::DataService;
+use super::phash_service::compute_phash;
+use super::exif_service::{process_upload, restricted_metadata_path, store_restricted_metadata};
+use super::region_service::load_geometry;
+
+pub const UPLOADS_DIR: &str = "uploads";
+
+pub const ALLOWED_EXTENSIONS: [&str; 8] = ["jpg", "jpeg", "png", "gif", "webp", "jfif", "bmp", "tiff"];
+
+pub fn file_extension(name: &str) -> String {
+    name.rsplit('.').next().unwrap_or("").to_lowercase()
+}
+
+pub fn is_supported_image(name: &str) -> bool {
+    name.contains('.') && ALLOWED_EXTENSIONS.contains(&file_extension(name).as_str())
+}
+
+pub fn unsupported_type_message() -> String {
+    format!("Unsupported file type. Supported formats: {}", ALLOWED_EXTENSIONS.join(", "))
+}
+
+// Salva os bytes em uploads/ e retorna o nome do arquivo gerado
+pub fn store_upload(original_name: &str, bytes: &[u8]) -> std::io::Result<String> {
+    let base_name = Path::new(original_name)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(original_name)
+        .replace(' ', "_");
+    let timestamp = chrono::Utc::now().timestamp_millis();
+
+    // Arquivos com o mesmo nome no mesmo milissegundo (ex.: dentro de um ZIP) não podem se sobrescrever
+    let mut filename = format!("{}_{}", timestamp, base_name);
+    let mut counter = 1;
+    while Path::new(UPLOADS_DIR).join(&filename).exists() {
+        filename = format!("{}_{}_{}", timestamp, counter, base_name);
+        counter += 1;
+    }
+
+    let mut file = std::fs::File::create(Path::new(UPLOADS_DIR).join(&filename))?;
+    file.write_all(bytes)?;
+    Ok(filename)
+}
+
+#[derive(Debug)]
+pub enum IngestError {
+    UnsupportedType,
+    GroupNotFound,
+    ImageNotFound,
+    Processing(String),
+    Storage(std::io::Error),
+}
+
+impl std::fmt::Display for IngestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IngestError::UnsupportedType => write!(f, "{}", unsupported_type_message()),
+            IngestError::GroupNotFound => write!(f, "Group not found"),
+            IngestError::ImageNotFound => write!(f, "Image not found"),
+            IngestError::Processing(e) => write!(f, "Failed to process image: {}", e),
+            IngestError::Storage(e) => write!(f, "Failed to save file: {}", e),
+        }
+    }
+}
+
+// Limite de um arquivo de imagem enviado pela API
+pub const MAX_IMAGE_BYTES: usize = 50 * 1024 * 1024;
+
+pub struct StoredFile {
+    pub filename: String,
+    pub phash: Option<String>,
+    pub dimensions: Option<(u32, u32)>,
+    pub restricted_metadata: bool,
+}
+
+pub fn read_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    image::io::Reader::new(std::io::Cursor::new(bytes))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()
+}
+
+// Valida, aplica a política de metadados do grupo e grava o arquivo;
+// usado tanto para imagens novas quanto para substituições
+pub fn store_image_file(original_name: &str, bytes: &[u8], policy: &MetadataPolicy) -> Result<StoredFile, IngestError> {
+    if !is_supported_image(original_name) {
+        return Err(IngestError::UnsupportedType);
+    }
+
+    let processed = process_upload(original_name, bytes, policy).map_err(IngestError::Processing)?;
+    let filename = store_upload(original_name, &processed.bytes).map_err(IngestError::Storage)?;
+
+    let mut restricted_metadata = false;
+    if let Some(metadata) = &processed.original_metadata {
+        if let Err(e) = store_restricted_metadata(&filename, metadata) {
+            remove_stored_file(&filename);
+            return Err(IngestError::Storage(e));
+        }
+        restricted_metadata = true;
+    }
+
+    Ok(StoredFile {
+        filename,
+        phash: compute_phash(&processed.bytes),
+        dimensions: read_dimensions(&processed.bytes),
+        restricted_metadata,
+    })
+}
+
+// Remove o arquivo enviado e os metadados restritos associados
+pub fn remove_stored_file(filename: &str) {
+    let _ = std::fs::remove_file(Path::new(UPLOADS_DIR).join(filename));
+    let _ = std::fs::remove_file(restricted_metadata_path(filename));
+}
+
+// Pipeline comum de ingestão (upload, arquivos compactados, hot folders).
+// Não persiste o data.json; quem chama salva uma vez ao final do lote.
+pub fn ingest_image(
+    data: &mut DataService,
+    group_id: &str,
+    original_name: &str,
+    bytes: &[u8],
+    uploaded_by: &str,
+) -> Result<String, IngestError> {
+    let policy = data.get_group(group_id)
+        .map(|group| group.metadata_policy.clone())
+        .ok_or(IngestError::GroupNotFound)?;
+
+    let stored = store_image_file(original_name, bytes, &policy)?;
+    insert_stored_image(data, group_id, original_name, stored, uploaded_by)
+}
+
+// Registra uma imagem já gravada por store_image_file. Lotes grandes gravam os arquivos
+// antes de pegar o lock do DataService e só chamam esta função com ele.
+pub fn insert_stored_image(
+    data: &mut DataService,
+    group_id: &str,
+    original_name: &str,
+    stored: StoredFile,
+    uploaded_by: &str,
+) -> Result<String, IngestError> {
+    // O grupo pode ter sido apagado enquanto os arquivos eram gravados
+    if data.get_group(group_id).is_none() {
+        remove_stored_file(&stored.filename);
+        return Err(IngestError::GroupNotFound);
+    }
+    let mut image = Image::new(
+        stored.filename,
+        original_name.to_string(),
+        group_id.to_string(),
+        uploaded_by.to_string(),
+    );
+    image.phash = stored.phash;
+    image.width = stored.dimensions.map(|(width, _)| width);
+    image.height = stored.dimensions.map(|(_, height)| height);
+    image.restricted_metadata = stored.restricted_metadata;
+    let image_id = image.id.clone();
+    data.images.insert(image_id.clone(), image);
+    Ok(image_id)
+}
+
+fn bbox_within(bbox: &BoundingBox, width: u32, height: u32) -> bool {
+    bbox.x >= 0.0 && bbox.y >= 0.0 && bbox.x + bbox.width <= width as f64 && bbox.y + bbox.height <= height as f64
+}
+
+// Substitui o arquivo de uma imagem (já gravado por store_image_file) mantendo a versão anterior e todas as anotações.
+// Regiões e pontos que não cabem nas novas dimensões são sempre marcados para revisão;
+// com flag_for_review todas as tags, sugestões abertas, regiões e pontos da imagem são marcados.
+pub fn replace_image(
+    data: &mut DataService,
+    image_id: &str,
+    original_name: &str,
+    stored: StoredFile,
+    replaced_by: &str,
+    flag_for_review: bool,
+) -> Result<(ImageVersion, FlaggedAnnotations), IngestError> {
+    // A imagem pode ter sido apagada enquanto o arquivo era gravado
+    let Some(image) = data.images.get_mut(image_id) else {
+        remove_stored_file(&stored.filename);
+        return Err(IngestError::ImageNotFound);
+    };
+    let previous = ImageVersion::from_image(image, replaced_by.to_string());
+    image.filename = stored.filename;
+    image.original_name = original_name.to_string();
+    image.phash = stored.phash;
+    image.width = stored.dimensions.map(|(width, _)| width);
+    image.height = stored.dimensions.map(|(_, height)| height);
+    image.restricted_metadata = stored.restricted_metadata;
+    image.versions.push(previous.clone());
+
+    let mut flagged = FlaggedAnnotations::default();
+    let checkpoint = data.history_checkpoint([image_id]);
+    // Os pixels mudaram: as tags podem precisar ser conferidas novamente
+    if flag_for_review {
+        for tag in data.approved_tags.values_mut().filter(|tag| tag.image_id == image_id) {
+            tag.needs_review = true;
+            flagged.approved_tags += 1;
+        }
+        for suggestion in data.tag_suggestions.values_mut().filter(|suggestion| suggestion.image_id == image_id && suggestion.status.is_open()) {
+            suggestion.needs_review = true;
+            flagged.tag_suggestions += 1;
+        }
+    }
+
+    let dimensions = stored.dimensions;
+    let fits = |bbox: &BoundingBox| dimensions.is_none_or(|(width, height)| bbox_within(bbox, width, height));
+    for region in data.region_annotations.values_mut().filter(|region| region.image_id == image_id) {
+        // Máscaras RLE guardam o tamanho da imagem; com outro tamanho a máscara não é mais válida
+        let mask_mismatch = region.kind == RegionKind::Mask && dimensions.is_some_and(|(width, height)| {
+            matches!(load_geometry(&region.id), Some(RegionGeometry::Mask(mask)) if mask.size != [height, width])
+        });
+        let out_of_bounds = !fits(&region.bbox) || mask_mismatch;
+        if out_of_bounds {
+            flagged.out_of_bounds += 1;
+        }
+        if out_of_bounds || flag_for_review {
+            region.needs_review = true;
+            flagged.regions += 1;
+        }
+    }
+    for annotation in data.keypoint_annotations.values_mut().filter(|annotation| annotation.image_id == image_id) {
+        let out_of_bounds = dimensions.is_some_and(|(width, height)| annotation.keypoints.iter()
+            .filter(|keypoint| keypoint.visibility > 0)
+            .any(|keypoint| keypoint.x < 0.0 || keypoint.y < 0.0 || keypoint.x > width as f64 || keypoint.y > height as f64));
+        if out_of_bounds {
+            flagged.out_of_bounds += 1;
+        }
+        if out_of_bounds || flag_for_review {
+            annotation.needs_review = true;
+            flagged.keypoints += 1;
+        }
+    }
+    data.record_history(checkpoint, replaced_by);
+    Ok((previous, flagged))
+}
@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::fmt;
+use chrono::Utc;
+
+use crate::models::{
+    BoundingBox, Keypoint, KeypointAnnotation, KeypointListQuery, KeypointSchema, SuggestKeypointsRequest,
+    VISIBILITY_UNLABELED, VISIBILITY_VISIBLE,
+};
+use super::DataService;
+use super::region_service::validate_review_status;
+
+#[derive(Debug)]
+pub enum KeypointError {
+    ImageNotFound,
+    GroupNotFound,
+    AnnotationNotFound,
+    MissingSchema,
+    UnknownDimensions,
+    Invalid(String),
+    Conflict(String),
+}
+
+impl fmt::Display for KeypointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeypointError::ImageNotFound => write!(f, "Image not found"),
+            KeypointError::GroupNotFound => write!(f, "Group not found"),
+            KeypointError::AnnotationNotFound => write!(f, "Keypoint annotation not found"),
+            KeypointError::MissingSchema => write!(f, "The image's group has no keypoint schema"),
+            KeypointError::UnknownDimensions => write!(f, "Image dimensions are unknown; cannot validate keypoints"),
+            KeypointError::Invalid(message) | KeypointError::Conflict(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+// Valida os pontos contra o schema e devolve todos os pontos do schema, em ordem e em pixels.
+// Pontos omitidos são registrados como não anotados.
+pub fn validate_keypoints(
+    schema: &KeypointSchema,
+    keypoints: &[Keypoint],
+    normalized: bool,
+    width: u32,
+    height: u32,
+) -> Result<Vec<Keypoint>, String> {
+    let mut provided: HashMap<&str, &Keypoint> = HashMap::new();
+    for keypoint in keypoints {
+        if !schema.keypoints.contains(&keypoint.name) {
+            return Err(format!("Keypoint '{}' is not part of schema '{}'", keypoint.name, schema.name));
+        }
+        if provided.insert(keypoint.name.as_str(), keypoint).is_some() {
+            return Err(format!("Keypoint '{}' was given more than once", keypoint.name));
+        }
+        if keypoint.visibility > VISIBILITY_VISIBLE {
+            return Err(format!("Keypoint '{}' has invalid visibility {}; use 0, 1 or 2", keypoint.name, keypoint.visibility));
+        }
+    }
+
+    let (scale_x, scale_y) = if normalized { (width as f64, height as f64) } else { (1.0, 1.0) };
+    let mut result = Vec::with_capacity(schema.keypoints.len());
+    for name in &schema.keypoints {
+        let keypoint = match provided.get(name.as_str()) {
+            Some(keypoint) if keypoint.visibility != VISIBILITY_UNLABELED => {
+                if !keypoint.x.is_finite() || !keypoint.y.is_finite() {
+                    return Err(format!("Keypoint '{}' must have finite coordinates", name));
+                }
+                if normalized && (!(0.0..=1.0).contains(&keypoint.x) || !(0.0..=1.0).contains(&keypoint.y)) {
+                    return Err(format!("Normalized coordinates of keypoint '{}' must be between 0 and 1", name));
+                }
+                let (x, y) = (keypoint.x * scale_x, keypoint.y * scale_y);
+                if x < 0.0 || y < 0.0 || x > width as f64 || y > height as f64 {
+                    return Err(format!("Keypoint '{}' is outside the image ({}x{})", name, width, height));
+                }
+                Keypoint { name: name.clone(), x, y, visibility: keypoint.visibility }
+            }
+            _ => Keypoint { name: name.clone(), x: 0.0, y: 0.0, visibility: VISIBILITY_UNLABELED },
+        };
+        result.push(keypoint);
+    }
+
+    if result.iter().all(|keypoint| keypoint.visibility == VISIBILITY_UNLABELED) {
+        return Err("At least one keypoint must be labeled".to_string());
+    }
+    Ok(result)
+}
+
+fn keypoints_bbox(keypoints: &[Keypoint]) -> Option<BoundingBox> {
+    let labeled: Vec<&Keypoint> = keypoints.iter().filter(|keypoint| keypoint.visibility != VISIBILITY_UNLABELED).collect();
+    let min_x = labeled.iter().map(|keypoint| keypoint.x).reduce(f64::min)?;
+    let min_y = labeled.iter().map(|keypoint| keypoint.y).reduce(f64::min)?;
+    let max_x = labeled.iter().map(|keypoint| keypoint.x).reduce(f64::max)?;
+    let max_y = labeled.iter().map(|keypoint| keypoint.y).reduce(f64::max)?;
+    Some(BoundingBox { x: min_x, y: min_y, width: max_x - min_x, height: max_y - min_y })
+}
+
+// Pontos revalidados para o schema de outro grupo: id da anotação → (schema, pontos na ordem dele)
+pub type RemappedKeypoints = HashMap<String, (String, Vec<Keypoint>)>;
+
+impl DataService {
+    // Anotações de pontos de uma imagem que vai para outro grupo precisam valer no schema do destino
+    pub fn remap_keypoints_to_group(&self, image_id: &str, target_group_id: &str) -> Result<RemappedKeypoints, String> {
+        let annotations: Vec<&KeypointAnnotation> = self.keypoint_annotations.values()
+            .filter(|annotation| annotation.image_id == image_id)
+            .collect();
+        let image = self.images.get(image_id).ok_or("Image not found")?;
+        if annotations.is_empty() || image.group_id == target_group_id {
+            return Ok(RemappedKeypoints::new());
+        }
+        let schema = self.groups.get(target_group_id)
+            .and_then(|group| group.keypoint_schema.as_ref())
+            .ok_or("Image has keypoint annotations but the target group has no keypoint schema")?;
+        let (width, height) = image.width.zip(image.height).ok_or_else(|| KeypointError::UnknownDimensions.to_string())?;
+
+        let mut remapped = RemappedKeypoints::new();
+        for annotation in annotations {
+            let labeled: Vec<Keypoint> = annotation.keypoints.iter()
+                .filter(|keypoint| keypoint.visibility != VISIBILITY_UNLABELED)
+                .cloned()
+                .collect();
+            let keypoints = validate_keypoints(schema, &labeled, false, width, height)
+                .map_err(|e| format!("Keypoint annotation '{}' does not fit the target schema: {}", annotation.id, e))?;
+            remapped.insert(annotation.id.clone(), (schema.name.clone(), keypoints));
+        }
+        Ok(remapped)
+    }
+
+    pub fn suggest_keypoints(&mut self, image_id: &str, req: &SuggestKeypointsRequest) -> Result<KeypointAnnotation, KeypointError> {
+        let image = self.images.get(image_id).ok_or(KeypointError::ImageNotFound)?;
+        let group = self.groups.get(&image.group_id).ok_or(KeypointError::GroupNotFound)?;
+        let schema = group.keypoint_schema.as_ref().ok_or(KeypointError::MissingSchema)?;
+        let (width, height) = image.width.zip(image.height).ok_or(KeypointError::UnknownDimensions)?;
+
+        let keypoints = validate_keypoints(schema, &req.keypoints, req.normalized, width, height)
+            .map_err(KeypointError::Invalid)?;
+        let bbox = keypoints_bbox(&keypoints);
+        let annotation = KeypointAnnotation::new(image_id.to_string(), schema.name.clone(), keypoints, bbox, req.suggested_by.clone());
+        self.keypoint_annotations.insert(annotation.id.clone(), annotation.clone());
+        Ok(annotation)
+    }
+
+    pub fn review_keypoints(&mut self, annotation_id: &str, status: &str, reviewed_by: &str) -> Result<KeypointAnnotation, KeypointError> {
+        validate_review_status(status).map_err(KeypointError::Invalid)?;
+        let annotation = self.keypoint_annotations.get_mut(annotation_id).ok_or(KeypointError::AnnotationNotFound)?;
+        annotation.status = status.to_string();
+        annotation.reviewed_by = Some(reviewed_by.to_string());
+        annotation.reviewed_at = Some(Utc::now().to_rfc3339());
+        annotation.needs_review = false;
+        Ok(annotation.clone())
+    }
+
+    pub fn list_keypoints(&self, query: &KeypointListQuery) -> Vec<&KeypointAnnotation> {
+        let mut annotations: Vec<&KeypointAnnotation> = self.keypoint_annotations.values()
+            .filter(|annotation| query.image_id.as_ref().is_none_or(|id| &annotation.image_id == id))
+            .filter(|annotation| query.status.as_ref().is_none_or(|status| &annotation.status == status))
+            .filter(|annotation| query.group_id.as_ref().is_none_or(|group_id| {
+                self.images.get(&annotation.image_id).is_some_and(|image| &image.group_id == group_id)
+            }))
+            .collect();
+        annotations.sort_by(|a, b| a.suggested_at.cmp(&b.suggested_at).then_with(|| a.id.cmp(&b.id)));
+        annotations
+    }
+
+    // Troca ou remove o schema do grupo; recusa se anotações existentes usam pontos que deixariam de existir
+    pub fn set_keypoint_schema(&mut self, group_id: &str, schema: Option<KeypointSchema>) -> Result<(), KeypointError> {
+        if !self.groups.contains_key(group_id) {
+            return Err(KeypointError::GroupNotFound);
+        }
+        if let Some(schema) = &schema {
+            schema.validate().map_err(KeypointError::Invalid)?;
+        }
+
+        let in_use = self.keypoint_annotations.values()
+            .filter(|annotation| self.images.get(&annotation.image_id).is_some_and(|image| image.group_id == group_id))
+            .flat_map(|annotation| annotation.keypoints.iter())
+            .filter(|keypoint| keypoint.visibility != VISIBILITY_UNLABELED)
+            .find(|keypoint| schema.as_ref().is_none_or(|schema| !schema.keypoints.contains(&keypoint.name)));
+        if let Some(keypoint) = in_use {
+            return Err(KeypointError::Conflict(format!(
+                "Keypoint '{}' is used by existing annotations in this group",
+                keypoint.name,
+            )));
+        }
+
+        if let Some(group) = self.groups.get_mut(group_id) {
+            group.keypoint_schema = schema;
+        }
+        Ok(())
+    }
+}
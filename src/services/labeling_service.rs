@@ -0,0 +1,201 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::models::{label_key, ApprovedTag, CompletenessReport, SuggestionStatus, ImageClasses, LabelingMode, LabelingPolicy};
+use super::DataService;
+
+impl DataService {
+    pub fn labeling_policy_for_image(&self, image_id: &str) -> LabelingPolicy {
+        self.images.get(image_id)
+            .and_then(|image| self.groups.get(&image.group_id))
+            .map(|group| group.labeling)
+            .unwrap_or_default()
+    }
+
+    // Rótulos iguais ou na mesma linhagem do vocabulário (husky e dog) descrevem a mesma classe
+    fn same_class(&self, group_id: &str, a: &str, b: &str) -> bool {
+        let (a_key, b_key) = (label_key(a), label_key(b));
+        a_key == b_key
+            || self.implied_labels(group_id, a).iter().any(|ancestor| label_key(ancestor) == b_key)
+            || self.implied_labels(group_id, b).iter().any(|ancestor| label_key(ancestor) == a_key)
+    }
+
+    // Tags aprovadas de outras classes na imagem quando o grupo é exclusivo
+    pub fn exclusive_conflicts(&self, image_id: &str, label: &str) -> Vec<&ApprovedTag> {
+        let Some(image) = self.images.get(image_id) else {
+            return Vec::new();
+        };
+        if self.labeling_policy_for_image(image_id).mode != LabelingMode::Exclusive {
+            return Vec::new();
+        }
+        let mut conflicts: Vec<&ApprovedTag> = self.approved_tags.values()
+            .filter(|tag| tag.image_id == image_id && !self.same_class(&image.group_id, &tag.tag, label))
+            .collect();
+        conflicts.sort_by(|a, b| a.approved_at.cmp(&b.approved_at).then_with(|| a.id.cmp(&b.id)));
+        conflicts
+    }
+
+    // Em grupo exclusivo, percorre as tags em ordem de prioridade e devolve as que caem fora da classe
+    // das anteriores; vale para tags que chegam de outra imagem ou de outro grupo
+    pub fn exclusive_losers(&self, group_id: &str, ranked: &[(String, String)]) -> Vec<(String, String)> {
+        let exclusive = self.groups.get(group_id).is_some_and(|group| group.labeling.mode == LabelingMode::Exclusive);
+        if !exclusive {
+            return Vec::new();
+        }
+        let mut kept: Vec<&str> = Vec::new();
+        let mut losers = Vec::new();
+        for (id, label) in ranked {
+            if kept.iter().all(|class| self.same_class(group_id, class, label)) {
+                kept.push(label);
+            } else {
+                losers.push((id.clone(), label.clone()));
+            }
+        }
+        losers
+    }
+
+    // Tags aprovadas da imagem, da aprovação mais recente para a mais antiga (a mais recente prevalece)
+    pub fn ranked_approved_tags(&self, image_id: &str) -> Vec<(String, String)> {
+        let mut tags: Vec<&ApprovedTag> = self.approved_tags.values()
+            .filter(|tag| tag.image_id == image_id)
+            .collect();
+        tags.sort_by(|a, b| b.approved_at.cmp(&a.approved_at).then_with(|| a.id.cmp(&b.id)));
+        tags.into_iter().map(|tag| (tag.id.clone(), tag.tag.clone())).collect()
+    }
+
+    // Remove as tags aprovadas substituídas (com votos e conversas) e marca as sugestões de origem como "superseded"
+    pub fn supersede_approved_tags(&mut self, tag_ids: &[String]) {
+        for tag_id in tag_ids {
+            let Some(tag) = self.remove_approved_tag(tag_id) else {
+                continue;
+            };
+            let Some(suggestion_id) = tag.suggestion_id else {
+                continue;
+            };
+            self.cascade_to_duplicates(&suggestion_id, SuggestionStatus::Superseded, None, None);
+            if let Some(suggestion) = self.tag_suggestions.get_mut(&suggestion_id) {
+                suggestion.status = SuggestionStatus::Superseded;
+            }
+        }
+    }
+
+    // Imagens sem classe aprovada ou com várias classes distintas (ancestrais implícitos não contam como outra classe)
+    pub fn labeling_completeness(&self, group_id: &str) -> Option<CompletenessReport> {
+        let group = self.groups.get(group_id)?;
+        let mut classes: HashMap<&str, BTreeMap<String, &str>> = self.images.values()
+            .filter(|image| image.group_id == group_id)
+            .map(|image| (image.id.as_str(), BTreeMap::new()))
+            .collect();
+        for tag in self.approved_tags.values() {
+            if let Some(labels) = classes.get_mut(tag.image_id.as_str()) {
+                labels.entry(label_key(&tag.tag)).or_insert(tag.tag.as_str());
+            }
+        }
+        for labels in classes.values_mut() {
+            let implied: Vec<String> = labels.values()
+                .flat_map(|label| self.implied_labels(group_id, label))
+                .map(|ancestor| label_key(&ancestor))
+                .collect();
+            labels.retain(|key, _| !implied.contains(key));
+        }
+
+        let mut unlabeled: Vec<String> = classes.iter()
+            .filter(|(_, labels)| labels.is_empty())
+            .map(|(image_id, _)| image_id.to_string())
+            .collect();
+        unlabeled.sort();
+        let mut multiple_labels: Vec<ImageClasses> = classes.iter()
+            .filter(|(_, labels)| labels.len() > 1)
+            .map(|(image_id, labels)| ImageClasses {
+                image_id: image_id.to_string(),
+                labels: labels.values().map(|label| label.to_string()).collect(),
+            })
+            .collect();
+        multiple_labels.sort_by(|a, b| a.image_id.cmp(&b.image_id));
+
+        // Várias classes só tornam a imagem incompleta no modo exclusivo
+        let conflicting = if group.labeling.mode == LabelingMode::Exclusive { multiple_labels.len() } else { 0 };
+        Some(CompletenessReport {
+            group_id: group_id.to_string(),
+            mode: group.labeling.mode,
+            total_images: classes.len(),
+            complete_images: classes.len() - unlabeled.len() - conflicting,
+            unlabeled,
+            multiple_labels,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ConflictResolution, Group, Image, ReviewTagRequest, TagSuggestion};
+    use crate::services::tag_review_service::ReviewError;
+
+    // Grupo exclusivo com uma imagem e as sugestões "cat" e "dog" de alice
+    fn fixture(on_conflict: ConflictResolution) -> (DataService, String, String) {
+        let mut data = DataService::new();
+        let mut group = Group::new("Pets".to_string(), String::new(), "admin".to_string());
+        group.members.push("alice".to_string());
+        group.labeling = LabelingPolicy { mode: LabelingMode::Exclusive, on_conflict };
+        let image = Image::new("a.png".to_string(), "a.png".to_string(), group.id.clone(), "alice".to_string());
+        let cat = TagSuggestion::new(image.id.clone(), "cat".to_string(), "alice".to_string());
+        let dog = TagSuggestion::new(image.id.clone(), "dog".to_string(), "alice".to_string());
+        let (cat_id, dog_id) = (cat.id.clone(), dog.id.clone());
+        data.groups.insert(group.id.clone(), group);
+        data.images.insert(image.id.clone(), image);
+        data.tag_suggestions.insert(cat_id.clone(), cat);
+        data.tag_suggestions.insert(dog_id.clone(), dog);
+        (data, cat_id, dog_id)
+    }
+
+    fn approve(data: &mut DataService, suggestion_id: &str, supersede: bool) -> Result<Vec<String>, ReviewError> {
+        let req = ReviewTagRequest {
+            status: SuggestionStatus::Approved,
+            reviewed_by: "admin".to_string(),
+            attributes: None,
+            supersede,
+            reason: None,
+            note: None,
+        };
+        data.review_tag_suggestion(suggestion_id, &req).map(|outcome| outcome.superseded_tags)
+    }
+
+    #[test]
+    fn losers_are_the_labels_outside_the_first_class() {
+        let (mut data, _, _) = fixture(ConflictResolution::Reject);
+        let group_id = data.groups.keys().next().unwrap().clone();
+        let ranked = vec![
+            ("1".to_string(), "dog".to_string()),
+            ("2".to_string(), "cat".to_string()),
+            ("3".to_string(), "Dog".to_string()),
+        ];
+        assert_eq!(data.exclusive_losers(&group_id, &ranked), vec![("2".to_string(), "cat".to_string())]);
+
+        data.groups.get_mut(&group_id).unwrap().labeling.mode = LabelingMode::Multi;
+        assert!(data.exclusive_losers(&group_id, &ranked).is_empty());
+    }
+
+    #[test]
+    fn second_class_is_rejected_unless_superseding() {
+        let (mut data, cat_id, dog_id) = fixture(ConflictResolution::Reject);
+        approve(&mut data, &cat_id, false).unwrap();
+        assert!(matches!(approve(&mut data, &dog_id, false), Err(ReviewError::ExclusiveConflict(_))));
+        assert_eq!(data.tag_suggestions[&dog_id].status, SuggestionStatus::Pending);
+
+        let superseded = approve(&mut data, &dog_id, true).unwrap();
+        assert_eq!(superseded.len(), 1);
+        assert_eq!(data.tag_suggestions[&cat_id].status, SuggestionStatus::Superseded);
+        let labels: Vec<&str> = data.approved_tags.values().map(|tag| tag.tag.as_str()).collect();
+        assert_eq!(labels, vec!["dog"]);
+    }
+
+    #[test]
+    fn supersede_policy_replaces_the_previous_class() {
+        let (mut data, cat_id, dog_id) = fixture(ConflictResolution::Supersede);
+        approve(&mut data, &cat_id, false).unwrap();
+        let cat_tag = data.approved_tags.values().next().unwrap().id.clone();
+        assert_eq!(approve(&mut data, &dog_id, false).unwrap(), vec![cat_tag]);
+        assert_eq!(data.tag_suggestions[&cat_id].status, SuggestionStatus::Superseded);
+        assert_eq!(data.approved_tags.len(), 1);
+    }
+}
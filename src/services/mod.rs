@@ -1,5 +1,26 @@
 pub mod data_service;
 pub mod user_service;
+pub mod ingest_service;
+pub mod archive_service;
+pub mod hot_folder_service;
+pub mod phash_service;
+pub mod image_query_service;
+pub mod tag_query_service;
+pub mod batch_service;
+pub mod exif_service;
+pub mod region_service;
+pub mod keypoint_service;
+pub mod vocabulary_service;
+pub mod tag_normalization_service;
+pub mod labeling_service;
+pub mod caption_service;
+pub mod vqa_service;
+pub mod rating_service;
+pub mod comment_service;
+pub mod history_service;
+pub mod tag_review_service;
+pub mod consensus_service;
 
 pub use data_service::*;
 pub use user_service::*;
+pub use hot_folder_service::HotFolderService;
@@ -0,0 +1,114 @@
+use image::imageops::FilterType;
+
+// Distância de Hamming máxima (em 64 bits) para considerar duas imagens quase idênticas
+pub const DEFAULT_MAX_DISTANCE: u32 = 10;
+
+const HASH_INPUT_SIZE: usize = 32;
+const HASH_LOW_FREQ_SIZE: usize = 8;
+
+// pHash baseado em DCT: resistente a redimensionamento, recompressão e pequenos cortes
+pub fn compute_phash(bytes: &[u8]) -> Option<String> {
+    let decoded = image::load_from_memory(bytes).ok()?;
+    let gray = decoded
+        .resize_exact(HASH_INPUT_SIZE as u32, HASH_INPUT_SIZE as u32, FilterType::Triangle)
+        .to_luma8();
+
+    let pixels: Vec<f64> = gray.pixels().map(|pixel| pixel.0[0] as f64).collect();
+    let coefficients = dct_2d(&pixels, HASH_INPUT_SIZE);
+
+    // Coeficientes de baixa frequência, ignorando o termo DC
+    let mut low_freq = Vec::with_capacity(HASH_LOW_FREQ_SIZE * HASH_LOW_FREQ_SIZE);
+    for y in 0..HASH_LOW_FREQ_SIZE {
+        for x in 0..HASH_LOW_FREQ_SIZE {
+            low_freq.push(coefficients[y * HASH_INPUT_SIZE + x]);
+        }
+    }
+    let mut sorted: Vec<f64> = low_freq[1..].to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let median = sorted[sorted.len() / 2];
+
+    let hash = low_freq.iter()
+        .enumerate()
+        .fold(0u64, |hash, (bit, value)| if *value > median { hash | (1 << bit) } else { hash });
+    Some(format!("{:016x}", hash))
+}
+
+pub fn parse_phash(hash: &str) -> Option<u64> {
+    u64::from_str_radix(hash, 16).ok()
+}
+
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+// DCT-II separável (linhas e depois colunas) de uma matriz quadrada
+fn dct_2d(input: &[f64], size: usize) -> Vec<f64> {
+    let cosines: Vec<f64> = (0..size * size)
+        .map(|index| {
+            let (k, n) = (index / size, index % size);
+            (std::f64::consts::PI / size as f64 * (n as f64 + 0.5) * k as f64).cos()
+        })
+        .collect();
+
+    let mut rows = vec![0.0; size * size];
+    for y in 0..size {
+        for k in 0..size {
+            rows[y * size + k] = (0..size).map(|n| input[y * size + n] * cosines[k * size + n]).sum();
+        }
+    }
+
+    let mut output = vec![0.0; size * size];
+    for x in 0..size {
+        for k in 0..size {
+            output[k * size + x] = (0..size).map(|n| rows[n * size + x] * cosines[k * size + n]).sum();
+        }
+    }
+    output
+}
+
+// Agrupa imagens por proximidade de hash (componentes conexas via union-find)
+pub fn cluster_hashes(hashes: &[(String, u64)], max_distance: u32) -> Vec<Vec<(String, u32)>> {
+    let mut parent: Vec<usize> = (0..hashes.len()).collect();
+
+    fn find(parent: &mut [usize], index: usize) -> usize {
+        let mut root = index;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        let mut current = index;
+        while parent[current] != root {
+            let next = parent[current];
+            parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    for i in 0..hashes.len() {
+        for j in (i + 1)..hashes.len() {
+            if hamming_distance(hashes[i].1, hashes[j].1) <= max_distance {
+                let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+                if a != b {
+                    parent[b] = a;
+                }
+            }
+        }
+    }
+
+    let mut clusters: std::collections::HashMap<usize, Vec<usize>> = std::collections::HashMap::new();
+    for index in 0..hashes.len() {
+        let root = find(&mut parent, index);
+        clusters.entry(root).or_default().push(index);
+    }
+
+    // Distância de cada membro em relação ao primeiro da lista
+    clusters.into_values()
+        .filter(|members| members.len() > 1)
+        .map(|members| {
+            let reference = hashes[members[0]].1;
+            members.iter()
+                .map(|&index| (hashes[index].0.clone(), hamming_distance(reference, hashes[index].1)))
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,379 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use chrono::Utc;
+
+use crate::models::{
+    ComparePairRequest, CriterionKind, CriterionScores, Image, LikertRating, LikertScore, PairwiseComparison, PairwiseScore,
+    RateImageRequest, RatingCriterion, ScoreTable,
+};
+use super::DataService;
+
+const BRADLEY_TERRY_ITERATIONS: usize = 500;
+const BRADLEY_TERRY_TOLERANCE: f64 = 1e-9;
+// Empate virtual contra uma referência de força 1: mantém finitas as forças de imagens sem vitórias ou sem derrotas
+const BRADLEY_TERRY_PRIOR: f64 = 0.5;
+
+#[derive(Debug)]
+pub enum RatingError {
+    GroupNotFound,
+    CriterionNotFound,
+    ImageNotFound,
+    Invalid(String),
+    Conflict(String),
+}
+
+impl fmt::Display for RatingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RatingError::GroupNotFound => write!(f, "Group not found"),
+            RatingError::CriterionNotFound => write!(f, "Rating criterion not found in this group"),
+            RatingError::ImageNotFound => write!(f, "Image not found in this group"),
+            RatingError::Invalid(message) | RatingError::Conflict(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+pub fn validate_criteria(criteria: &[RatingCriterion]) -> Result<(), String> {
+    let mut ids = HashSet::new();
+    for criterion in criteria {
+        let id = criterion.id.as_str();
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(format!("Criterion id '{}' must be non-empty and use only letters, digits, '-' or '_'", criterion.id));
+        }
+        if !ids.insert(id) {
+            return Err(format!("Duplicate criterion '{}'", id));
+        }
+        if criterion.prompt.trim().is_empty() {
+            return Err(format!("Criterion '{}' needs a prompt", id));
+        }
+        if let CriterionKind::Likert { scale } = criterion.kind {
+            if !(2..=10).contains(&scale) {
+                return Err(format!("Likert scale of criterion '{}' must be between 2 and 10", id));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let middle = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[middle - 1] + values[middle]) / 2.0
+    } else {
+        values[middle]
+    }
+}
+
+// Forças de Bradley–Terry pelo algoritmo MM (Hunter, 2004), normalizadas para média geométrica 1
+fn bradley_terry(image_ids: &[&str], comparisons: &[&PairwiseComparison]) -> HashMap<String, f64> {
+    let index: HashMap<&str, usize> = image_ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    let mut wins = vec![BRADLEY_TERRY_PRIOR; image_ids.len()];
+    let mut pairs: HashMap<(usize, usize), f64> = HashMap::new();
+    for comparison in comparisons {
+        let (Some(&a), Some(&b)) = (index.get(comparison.image_a.as_str()), index.get(comparison.image_b.as_str())) else {
+            continue;
+        };
+        match comparison.winner_id.as_deref() {
+            Some(winner) if winner == comparison.image_a => wins[a] += 1.0,
+            Some(_) => wins[b] += 1.0,
+            None => {
+                wins[a] += 0.5;
+                wins[b] += 0.5;
+            }
+        }
+        *pairs.entry((a.min(b), a.max(b))).or_default() += 1.0;
+    }
+
+    let mut strengths = vec![1.0; image_ids.len()];
+    for _ in 0..BRADLEY_TERRY_ITERATIONS {
+        // Comparação virtual com a referência (força 1)
+        let mut denominators: Vec<f64> = strengths.iter().map(|p| 2.0 * BRADLEY_TERRY_PRIOR / (p + 1.0)).collect();
+        for (&(a, b), &count) in &pairs {
+            let share = count / (strengths[a] + strengths[b]);
+            denominators[a] += share;
+            denominators[b] += share;
+        }
+        let mut updated: Vec<f64> = wins.iter().zip(&denominators).map(|(w, d)| w / d).collect();
+        let log_mean = updated.iter().map(|p| p.ln()).sum::<f64>() / updated.len().max(1) as f64;
+        for p in updated.iter_mut() {
+            *p /= log_mean.exp();
+        }
+        let change = updated.iter().zip(&strengths).map(|(new, old)| (new - old).abs()).fold(0.0, f64::max);
+        strengths = updated;
+        if change < BRADLEY_TERRY_TOLERANCE {
+            break;
+        }
+    }
+
+    image_ids.iter().zip(strengths).map(|(id, p)| (id.to_string(), p)).collect()
+}
+
+impl DataService {
+    fn rating_criterion(&self, group_id: &str, criterion_id: &str) -> Result<&RatingCriterion, RatingError> {
+        let group = self.groups.get(group_id).ok_or(RatingError::GroupNotFound)?;
+        group.rating_criteria.iter()
+            .find(|criterion| criterion.id == criterion_id)
+            .ok_or(RatingError::CriterionNotFound)
+    }
+
+    fn group_images(&self, group_id: &str) -> Vec<&Image> {
+        let mut images: Vec<&Image> = self.images.values().filter(|image| image.group_id == group_id).collect();
+        images.sort_by(|a, b| a.id.cmp(&b.id));
+        images
+    }
+
+    fn criterion_comparisons(&self, group_id: &str, criterion_id: &str) -> Vec<&PairwiseComparison> {
+        self.pairwise_comparisons.values()
+            .filter(|comparison| comparison.group_id == group_id && comparison.criterion == criterion_id)
+            .collect()
+    }
+
+    fn criterion_ratings(&self, group_id: &str, criterion_id: &str) -> Vec<&LikertRating> {
+        self.likert_ratings.values()
+            .filter(|rating| rating.group_id == group_id && rating.criterion == criterion_id)
+            .collect()
+    }
+
+    // Recusa remover um critério com anotações ou trocar o seu tipo
+    pub fn set_rating_criteria(&mut self, group_id: &str, criteria: Vec<RatingCriterion>) -> Result<(), RatingError> {
+        validate_criteria(&criteria).map_err(RatingError::Invalid)?;
+        if !self.groups.contains_key(group_id) {
+            return Err(RatingError::GroupNotFound);
+        }
+        let kind_of = |id: &str| criteria.iter().find(|criterion| criterion.id == id).map(|criterion| &criterion.kind);
+        let pairwise_in_use = self.pairwise_comparisons.values()
+            .filter(|comparison| comparison.group_id == group_id)
+            .find(|comparison| !matches!(kind_of(&comparison.criterion), Some(CriterionKind::Pairwise)));
+        if let Some(comparison) = pairwise_in_use {
+            return Err(RatingError::Conflict(format!("Pairwise criterion '{}' has comparisons and must be kept", comparison.criterion)));
+        }
+        let likert_in_use = self.likert_ratings.values()
+            .filter(|rating| rating.group_id == group_id)
+            .find(|rating| !matches!(kind_of(&rating.criterion), Some(CriterionKind::Likert { scale }) if rating.score <= *scale));
+        if let Some(rating) = likert_in_use {
+            return Err(RatingError::Conflict(format!("Likert criterion '{}' has ratings that would no longer fit", rating.criterion)));
+        }
+
+        if let Some(group) = self.groups.get_mut(group_id) {
+            group.rating_criteria = criteria;
+        }
+        Ok(())
+    }
+
+    // Próximo par para o anotador: imagens menos comparadas primeiro, sem repetir pares que ele já comparou
+    pub fn next_pair(&self, group_id: &str, criterion_id: &str, annotator: &str) -> Result<Option<(&Image, &Image)>, RatingError> {
+        let criterion = self.rating_criterion(group_id, criterion_id)?;
+        if criterion.kind != CriterionKind::Pairwise {
+            return Err(RatingError::Invalid(format!("Criterion '{}' is not pairwise", criterion_id)));
+        }
+        let comparisons = self.criterion_comparisons(group_id, criterion_id);
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        let mut done: HashSet<(&str, &str)> = HashSet::new();
+        for comparison in &comparisons {
+            *counts.entry(comparison.image_a.as_str()).or_default() += 1;
+            *counts.entry(comparison.image_b.as_str()).or_default() += 1;
+            if comparison.annotator == annotator {
+                let (a, b) = (comparison.image_a.as_str(), comparison.image_b.as_str());
+                done.insert((a.min(b), a.max(b)));
+            }
+        }
+
+        let mut images = self.group_images(group_id);
+        images.sort_by_key(|image| counts.get(image.id.as_str()).copied().unwrap_or(0));
+        for (i, first) in images.iter().enumerate() {
+            let partner = images.iter().enumerate().find(|(j, second)| {
+                let (a, b) = (first.id.as_str(), second.id.as_str());
+                *j != i && !done.contains(&(a.min(b), a.max(b)))
+            });
+            if let Some((_, second)) = partner {
+                return Ok(Some((*first, *second)));
+            }
+        }
+        Ok(None)
+    }
+
+    // Próxima imagem para o anotador: a menos avaliada entre as que ele ainda não avaliou
+    pub fn next_rating_image(&self, group_id: &str, criterion_id: &str, annotator: &str) -> Result<Option<&Image>, RatingError> {
+        let criterion = self.rating_criterion(group_id, criterion_id)?;
+        if criterion.kind == CriterionKind::Pairwise {
+            return Err(RatingError::Invalid(format!("Criterion '{}' is not a Likert rating", criterion_id)));
+        }
+        let ratings = self.criterion_ratings(group_id, criterion_id);
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        let mut rated: HashSet<&str> = HashSet::new();
+        for rating in &ratings {
+            *counts.entry(rating.image_id.as_str()).or_default() += 1;
+            if rating.annotator == annotator {
+                rated.insert(rating.image_id.as_str());
+            }
+        }
+        Ok(self.group_images(group_id).into_iter()
+            .filter(|image| !rated.contains(image.id.as_str()))
+            .min_by_key(|image| counts.get(image.id.as_str()).copied().unwrap_or(0)))
+    }
+
+    // Registra a comparação; uma nova resposta do mesmo anotador para o mesmo par substitui a anterior
+    pub fn compare_pair(&mut self, group_id: &str, criterion_id: &str, req: &ComparePairRequest) -> Result<PairwiseComparison, RatingError> {
+        let criterion = self.rating_criterion(group_id, criterion_id)?;
+        if criterion.kind != CriterionKind::Pairwise {
+            return Err(RatingError::Invalid(format!("Criterion '{}' is not pairwise", criterion_id)));
+        }
+        if req.image_a == req.image_b {
+            return Err(RatingError::Invalid("Cannot compare an image with itself".to_string()));
+        }
+        for image_id in [&req.image_a, &req.image_b] {
+            if self.images.get(image_id).is_none_or(|image| image.group_id != group_id) {
+                return Err(RatingError::ImageNotFound);
+            }
+        }
+        let winner_id = match req.winner.as_str() {
+            "a" => Some(req.image_a.clone()),
+            "b" => Some(req.image_b.clone()),
+            "tie" => None,
+            other => return Err(RatingError::Invalid(format!("Invalid winner '{}'; use 'a', 'b' or 'tie'", other))),
+        };
+
+        self.pairwise_comparisons.retain(|_, comparison| {
+            !(comparison.group_id == group_id
+                && comparison.criterion == criterion_id
+                && comparison.annotator == req.annotator
+                && ((comparison.image_a == req.image_a && comparison.image_b == req.image_b)
+                    || (comparison.image_a == req.image_b && comparison.image_b == req.image_a)))
+        });
+        let comparison = PairwiseComparison::new(
+            group_id.to_string(),
+            criterion_id.to_string(),
+            req.image_a.clone(),
+            req.image_b.clone(),
+            winner_id,
+            req.annotator.clone(),
+        );
+        self.pairwise_comparisons.insert(comparison.id.clone(), comparison.clone());
+        Ok(comparison)
+    }
+
+    // Registra ou substitui a nota do anotador para a imagem
+    pub fn rate_image(&mut self, group_id: &str, criterion_id: &str, req: &RateImageRequest) -> Result<LikertRating, RatingError> {
+        let criterion = self.rating_criterion(group_id, criterion_id)?;
+        let CriterionKind::Likert { scale } = criterion.kind else {
+            return Err(RatingError::Invalid(format!("Criterion '{}' is not a Likert rating", criterion_id)));
+        };
+        if !(1..=scale).contains(&req.score) {
+            return Err(RatingError::Invalid(format!("Score must be between 1 and {}", scale)));
+        }
+        if self.images.get(&req.image_id).is_none_or(|image| image.group_id != group_id) {
+            return Err(RatingError::ImageNotFound);
+        }
+
+        let existing = self.likert_ratings.values_mut().find(|rating| {
+            rating.group_id == group_id && rating.criterion == criterion_id && rating.image_id == req.image_id && rating.annotator == req.annotator
+        });
+        if let Some(existing) = existing {
+            existing.score = req.score;
+            existing.created_at = Utc::now().to_rfc3339();
+            return Ok(existing.clone());
+        }
+        let rating = LikertRating::new(group_id.to_string(), criterion_id.to_string(), req.image_id.clone(), req.score, req.annotator.clone());
+        self.likert_ratings.insert(rating.id.clone(), rating.clone());
+        Ok(rating)
+    }
+
+    // Placar do critério: Bradley–Terry para pares, média e mediana para notas; maior primeiro
+    pub fn criterion_scores(&self, group_id: &str, criterion_id: &str) -> Result<CriterionScores, RatingError> {
+        let criterion = self.rating_criterion(group_id, criterion_id)?;
+        let table = match criterion.kind {
+            CriterionKind::Pairwise => {
+                let comparisons = self.criterion_comparisons(group_id, criterion_id);
+                let mut counts: HashMap<&str, (f64, usize)> = HashMap::new();
+                for comparison in &comparisons {
+                    for image_id in [&comparison.image_a, &comparison.image_b] {
+                        let entry = counts.entry(image_id.as_str()).or_default();
+                        entry.1 += 1;
+                        entry.0 += match &comparison.winner_id {
+                            Some(winner) if winner == image_id => 1.0,
+                            Some(_) => 0.0,
+                            None => 0.5,
+                        };
+                    }
+                }
+                let image_ids: Vec<&str> = self.group_images(group_id).iter()
+                    .map(|image| image.id.as_str())
+                    .filter(|id| counts.contains_key(id))
+                    .collect();
+                let strengths = bradley_terry(&image_ids, &comparisons);
+                let mut scores: Vec<PairwiseScore> = image_ids.iter()
+                    .map(|id| PairwiseScore {
+                        image_id: id.to_string(),
+                        score: strengths.get(*id).copied().unwrap_or(1.0).ln(),
+                        wins: counts[id].0,
+                        comparisons: counts[id].1,
+                    })
+                    .collect();
+                scores.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.image_id.cmp(&b.image_id)));
+                ScoreTable::Pairwise(scores)
+            }
+            CriterionKind::Likert { .. } => {
+                let mut values: HashMap<&str, Vec<f64>> = HashMap::new();
+                for rating in self.criterion_ratings(group_id, criterion_id) {
+                    if self.images.get(&rating.image_id).is_some_and(|image| image.group_id == group_id) {
+                        values.entry(rating.image_id.as_str()).or_default().push(rating.score as f64);
+                    }
+                }
+                let mut scores: Vec<LikertScore> = values.into_iter()
+                    .map(|(image_id, mut values)| LikertScore {
+                        image_id: image_id.to_string(),
+                        mean: values.iter().sum::<f64>() / values.len() as f64,
+                        median: median(&mut values),
+                        ratings: values.len(),
+                    })
+                    .collect();
+                scores.sort_by(|a, b| b.mean.total_cmp(&a.mean).then_with(|| a.image_id.cmp(&b.image_id)));
+                ScoreTable::Likert(scores)
+            }
+        };
+        Ok(CriterionScores { group_id: group_id.to_string(), criterion: criterion_id.to_string(), table })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comparison(a: &str, b: &str, winner: Option<&str>) -> PairwiseComparison {
+        PairwiseComparison::new("g".to_string(), "quality".to_string(), a.to_string(), b.to_string(),
+                                winner.map(str::to_string), "alice".to_string())
+    }
+
+    #[test]
+    fn median_handles_odd_and_even_counts() {
+        assert_eq!(median(&mut [3.0, 1.0, 2.0]), 2.0);
+        assert_eq!(median(&mut [4.0, 1.0, 3.0, 2.0]), 2.5);
+        assert_eq!(median(&mut [5.0]), 5.0);
+    }
+
+    #[test]
+    fn bradley_terry_orders_by_wins_and_stays_finite() {
+        let comparisons = [
+            comparison("a", "b", Some("a")),
+            comparison("a", "c", Some("a")),
+            comparison("b", "c", Some("b")),
+            comparison("a", "b", Some("a")),
+        ];
+        let refs: Vec<&PairwiseComparison> = comparisons.iter().collect();
+        let strengths = bradley_terry(&["a", "b", "c"], &refs);
+        assert!(strengths["a"] > strengths["b"] && strengths["b"] > strengths["c"]);
+        assert!(strengths.values().all(|p| p.is_finite() && *p > 0.0));
+        let log_mean: f64 = strengths.values().map(|p| p.ln()).sum::<f64>() / 3.0;
+        assert!(log_mean.abs() < 1e-6);
+    }
+
+    #[test]
+    fn bradley_terry_treats_ties_and_unseen_images_as_equal() {
+        let comparisons = [comparison("a", "b", None), comparison("b", "a", None)];
+        let refs: Vec<&PairwiseComparison> = comparisons.iter().collect();
+        let strengths = bradley_terry(&["a", "b", "c"], &refs);
+        assert!((strengths["a"] - strengths["b"]).abs() < 1e-6);
+        assert!((strengths["a"] - strengths["c"]).abs() < 1e-6);
+    }
+}
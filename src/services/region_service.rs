@@ -0,0 +1,408 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::models::{BoundingBox, Polygon, RegionAnnotation, RegionGeometry, RegionKind, RegionListQuery, RleMask, SuggestRegionRequest};
+use super::DataService;
+
+pub const ANNOTATIONS_DIR: &str = "annotations";
+
+// Limite de vértices por anotação; a verificação de auto-interseção é quadrática
+const MAX_POLYGON_POINTS: usize = 4096;
+
+// Tolerância para erros de arredondamento de coordenadas normalizadas
+const EPSILON: f64 = 1e-6;
+
+#[derive(Debug)]
+pub enum RegionError {
+    ImageNotFound,
+    RegionNotFound,
+    UnknownDimensions,
+    Invalid(String),
+    Storage(io::Error),
+}
+
+impl fmt::Display for RegionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegionError::ImageNotFound => write!(f, "Image not found"),
+            RegionError::RegionNotFound => write!(f, "Region annotation not found"),
+            RegionError::UnknownDimensions => write!(f, "Image dimensions are unknown; cannot validate region"),
+            RegionError::Invalid(message) => write!(f, "{}", message),
+            RegionError::Storage(e) => write!(f, "Failed to store region geometry: {}", e),
+        }
+    }
+}
+
+// Região acompanhada da geometria carregada do arquivo auxiliar
+#[derive(Debug, Serialize)]
+pub struct RegionView<'a> {
+    #[serde(flatten)]
+    pub region: &'a RegionAnnotation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geometry: Option<RegionGeometry>,
+}
+
+pub fn geometry_path(region_id: &str) -> String {
+    format!("{}/{}.json", ANNOTATIONS_DIR, region_id)
+}
+
+pub fn store_geometry(region_id: &str, geometry: &RegionGeometry) -> io::Result<()> {
+    fs::create_dir_all(ANNOTATIONS_DIR)?;
+    fs::write(geometry_path(region_id), serde_json::to_vec(geometry)?)
+}
+
+pub fn load_geometry(region_id: &str) -> Option<RegionGeometry> {
+    let bytes = fs::read(geometry_path(region_id)).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+pub fn remove_geometry(region_id: &str) {
+    let _ = fs::remove_file(geometry_path(region_id));
+}
+
+fn scale(normalized: bool, width: u32, height: u32) -> (f64, f64) {
+    if normalized { (width as f64, height as f64) } else { (1.0, 1.0) }
+}
+
+fn check_coordinates(values: &[f64], normalized: bool) -> Result<(), String> {
+    if values.iter().any(|value| !value.is_finite()) {
+        return Err("Coordinates must be finite numbers".to_string());
+    }
+    if normalized && values.iter().any(|value| !(0.0..=1.0).contains(value)) {
+        return Err("Normalized coordinates must be between 0 and 1".to_string());
+    }
+    Ok(())
+}
+
+// Converte a caixa para pixels e garante que ela está inteiramente dentro da imagem
+pub fn validate_bbox(bbox: &BoundingBox, normalized: bool, width: u32, height: u32) -> Result<BoundingBox, String> {
+    check_coordinates(&[bbox.x, bbox.y, bbox.width, bbox.height], normalized)?;
+    let (scale_x, scale_y) = scale(normalized, width, height);
+    let pixels = BoundingBox {
+        x: bbox.x * scale_x,
+        y: bbox.y * scale_y,
+        width: bbox.width * scale_x,
+        height: bbox.height * scale_y,
+    };
+
+    if pixels.width <= 0.0 || pixels.height <= 0.0 {
+        return Err("Bounding box must have positive width and height".to_string());
+    }
+    if pixels.x < 0.0 || pixels.y < 0.0 {
+        return Err("Bounding box must start inside the image".to_string());
+    }
+    if pixels.x + pixels.width > width as f64 + EPSILON || pixels.y + pixels.height > height as f64 + EPSILON {
+        return Err(format!("Bounding box exceeds image bounds ({}x{})", width, height));
+    }
+    Ok(pixels)
+}
+
+// Área pela fórmula do laço (shoelace)
+fn polygon_area(points: &[[f64; 2]]) -> f64 {
+    let twice_area: f64 = points.iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| a[0] * b[1] - b[0] * a[1])
+        .sum();
+    twice_area.abs() / 2.0
+}
+
+fn cross(o: [f64; 2], a: [f64; 2], b: [f64; 2]) -> f64 {
+    (a[0] - o[0]) * (b[1] - o[1]) - (a[1] - o[1]) * (b[0] - o[0])
+}
+
+fn segments_intersect(p1: [f64; 2], p2: [f64; 2], q1: [f64; 2], q2: [f64; 2]) -> bool {
+    let d1 = cross(q1, q2, p1);
+    let d2 = cross(q1, q2, p2);
+    let d3 = cross(p1, p2, q1);
+    let d4 = cross(p1, p2, q2);
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0)) && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0)) {
+        return true;
+    }
+    let on_segment = |o: [f64; 2], a: [f64; 2], p: [f64; 2]| {
+        p[0] >= o[0].min(a[0]) && p[0] <= o[0].max(a[0]) && p[1] >= o[1].min(a[1]) && p[1] <= o[1].max(a[1])
+    };
+    (d1 == 0.0 && on_segment(q1, q2, p1))
+        || (d2 == 0.0 && on_segment(q1, q2, p2))
+        || (d3 == 0.0 && on_segment(p1, p2, q1))
+        || (d4 == 0.0 && on_segment(p1, p2, q2))
+}
+
+// Arestas não adjacentes não podem se cruzar nem se tocar
+fn is_simple_polygon(points: &[[f64; 2]]) -> bool {
+    let n = points.len();
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let adjacent = j == i + 1 || (i == 0 && j == n - 1);
+            if adjacent {
+                continue;
+            }
+            if segments_intersect(points[i], points[(i + 1) % n], points[j], points[(j + 1) % n]) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+pub fn validate_polygons(
+    polygons: &[Polygon],
+    normalized: bool,
+    width: u32,
+    height: u32,
+) -> Result<(Vec<Polygon>, BoundingBox, f64), String> {
+    if polygons.is_empty() {
+        return Err("Polygon must contain at least one ring".to_string());
+    }
+    let total_points: usize = polygons.iter().map(Vec::len).sum();
+    if total_points > MAX_POLYGON_POINTS {
+        return Err(format!("Polygon has {} points; the limit is {}", total_points, MAX_POLYGON_POINTS));
+    }
+
+    let (scale_x, scale_y) = scale(normalized, width, height);
+    let mut scaled = Vec::with_capacity(polygons.len());
+    let mut area = 0.0;
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+
+    for (index, points) in polygons.iter().enumerate() {
+        let mut ring: Polygon = points.clone();
+        // Anéis fechados explicitamente repetem o primeiro ponto no final
+        if ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+        if ring.len() < 3 {
+            return Err(format!("Polygon {} needs at least 3 distinct points", index));
+        }
+        for point in ring.iter_mut() {
+            check_coordinates(point, normalized)?;
+            point[0] *= scale_x;
+            point[1] *= scale_y;
+            if point[0] < -EPSILON || point[1] < -EPSILON
+                || point[0] > width as f64 + EPSILON || point[1] > height as f64 + EPSILON {
+                return Err(format!("Polygon {} exceeds image bounds ({}x{})", index, width, height));
+            }
+            min_x = min_x.min(point[0]);
+            min_y = min_y.min(point[1]);
+            max_x = max_x.max(point[0]);
+            max_y = max_y.max(point[1]);
+        }
+        if !is_simple_polygon(&ring) {
+            return Err(format!("Polygon {} is self-intersecting", index));
+        }
+        let ring_area = polygon_area(&ring);
+        if ring_area <= 0.0 {
+            return Err(format!("Polygon {} has zero area", index));
+        }
+        area += ring_area;
+        scaled.push(ring);
+    }
+
+    let bbox = BoundingBox { x: min_x, y: min_y, width: max_x - min_x, height: max_y - min_y };
+    Ok((scaled, bbox, area))
+}
+
+pub fn validate_mask(mask: &RleMask, width: u32, height: u32) -> Result<(BoundingBox, f64), String> {
+    if mask.size != [height, width] {
+        return Err(format!(
+            "Mask size {:?} does not match image dimensions [{}, {}] (height, width)",
+            mask.size, height, width,
+        ));
+    }
+    let total: u64 = mask.counts.iter().map(|&count| count as u64).sum();
+    if total != width as u64 * height as u64 {
+        return Err(format!("Mask counts sum to {} but the image has {} pixels", total, width as u64 * height as u64));
+    }
+
+    // Pixels em ordem de coluna: índice = x * altura + y
+    let height = height as u64;
+    let mut area = 0u64;
+    let mut position = 0u64;
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (u64::MAX, u64::MAX, 0u64, 0u64);
+    for (index, &count) in mask.counts.iter().enumerate() {
+        let count = count as u64;
+        if index % 2 == 1 && count > 0 {
+            let (start, end) = (position, position + count - 1);
+            let (start_x, end_x) = (start / height, end / height);
+            let (start_y, end_y) = if start_x == end_x { (start % height, end % height) } else { (0, height - 1) };
+            min_x = min_x.min(start_x);
+            max_x = max_x.max(end_x);
+            min_y = min_y.min(start_y);
+            max_y = max_y.max(end_y);
+            area += count;
+        }
+        position += count;
+    }
+    if area == 0 {
+        return Err("Mask is empty".to_string());
+    }
+
+    let bbox = BoundingBox {
+        x: min_x as f64,
+        y: min_y as f64,
+        width: (max_x - min_x + 1) as f64,
+        height: (max_y - min_y + 1) as f64,
+    };
+    Ok((bbox, area as f64))
+}
+
+pub fn validate_review_status(status: &str) -> Result<(), String> {
+    match status {
+        "approved" | "rejected" => Ok(()),
+        other => Err(format!("Invalid review status '{}'", other)),
+    }
+}
+
+impl DataService {
+    pub fn image_dimensions(&self, image_id: &str) -> Result<(u32, u32), RegionError> {
+        let image = self.images.get(image_id).ok_or(RegionError::ImageNotFound)?;
+        image.width.zip(image.height).ok_or(RegionError::UnknownDimensions)
+    }
+
+    pub fn suggest_region(&mut self, image_id: &str, req: &SuggestRegionRequest) -> Result<RegionAnnotation, RegionError> {
+        let (width, height) = self.image_dimensions(image_id)?;
+        let label = req.label.trim();
+        if label.is_empty() {
+            return Err(RegionError::Invalid("Label cannot be empty".to_string()));
+        }
+
+        let (kind, bbox, area, geometry) = match (&req.bbox, &req.polygon, &req.mask) {
+            (Some(bbox), None, None) => {
+                let bbox = validate_bbox(bbox, req.normalized, width, height).map_err(RegionError::Invalid)?;
+                (RegionKind::Box, bbox, bbox.width * bbox.height, None)
+            }
+            (None, Some(polygons), None) => {
+                let (polygons, bbox, area) = validate_polygons(polygons, req.normalized, width, height)
+                    .map_err(RegionError::Invalid)?;
+                (RegionKind::Polygon, bbox, area, Some(RegionGeometry::Polygon { polygons }))
+            }
+            (None, None, Some(mask)) => {
+                let (bbox, area) = validate_mask(mask, width, height).map_err(RegionError::Invalid)?;
+                (RegionKind::Mask, bbox, area, Some(RegionGeometry::Mask(mask.clone())))
+            }
+            _ => return Err(RegionError::Invalid("Provide exactly one of bbox, polygon or mask".to_string())),
+        };
+
+        let region = RegionAnnotation::new(image_id.to_string(), label.to_string(), kind, bbox, area, req.suggested_by.clone());
+        if let Some(geometry) = &geometry {
+            store_geometry(&region.id, geometry).map_err(RegionError::Storage)?;
+        }
+        self.region_annotations.insert(region.id.clone(), region.clone());
+        Ok(region)
+    }
+
+    pub fn review_region(&mut self, region_id: &str, status: &str, reviewed_by: &str) -> Result<RegionAnnotation, RegionError> {
+        validate_review_status(status).map_err(RegionError::Invalid)?;
+        let region = self.region_annotations.get_mut(region_id).ok_or(RegionError::RegionNotFound)?;
+        region.status = status.to_string();
+        region.reviewed_by = Some(reviewed_by.to_string());
+        region.reviewed_at = Some(Utc::now().to_rfc3339());
+        region.needs_review = false;
+        Ok(region.clone())
+    }
+
+    pub fn delete_region(&mut self, region_id: &str) -> bool {
+        if self.region_annotations.remove(region_id).is_some() {
+            remove_geometry(region_id);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn list_regions(&self, query: &RegionListQuery) -> Vec<&RegionAnnotation> {
+        let mut regions: Vec<&RegionAnnotation> = self.region_annotations.values()
+            .filter(|region| query.image_id.as_ref().is_none_or(|id| &region.image_id == id))
+            .filter(|region| query.status.as_ref().is_none_or(|status| &region.status == status))
+            .filter(|region| query.kind.is_none_or(|kind| region.kind == kind))
+            .filter(|region| query.label.as_ref().is_none_or(|label| region.label.eq_ignore_ascii_case(label.trim())))
+            .filter(|region| query.group_id.as_ref().is_none_or(|group_id| {
+                self.images.get(&region.image_id).is_some_and(|image| &image.group_id == group_id)
+            }))
+            .collect();
+        regions.sort_by(|a, b| a.suggested_at.cmp(&b.suggested_at).then_with(|| a.id.cmp(&b.id)));
+        regions
+    }
+
+    pub fn region_views<'a>(&self, regions: Vec<&'a RegionAnnotation>, include_geometry: bool) -> Vec<RegionView<'a>> {
+        regions.into_iter()
+            .map(|region| RegionView {
+                region,
+                geometry: (include_geometry && region.kind != RegionKind::Box)
+                    .then(|| load_geometry(&region.id))
+                    .flatten(),
+            })
+            .collect()
+    }
+
+    // Remove arquivos de geometria que não pertencem mais a nenhuma região
+    // (após exclusões em lote, mesclagens ou lotes desfeitos)
+    pub fn prune_region_geometries(&self) {
+        let Ok(entries) = fs::read_dir(ANNOTATIONS_DIR) else {
+            return;
+        };
+        let known: HashSet<&str> = self.region_annotations.keys().map(String::as_str).collect();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let orphan = path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|region_id| !known.contains(region_id));
+            if orphan && path.extension().is_some_and(|extension| extension == "json") {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bbox_is_scaled_and_kept_inside_the_image() {
+        let bbox = BoundingBox { x: 0.25, y: 0.5, width: 0.5, height: 0.5 };
+        let pixels = validate_bbox(&bbox, true, 200, 100).unwrap();
+        assert_eq!(pixels, BoundingBox { x: 50.0, y: 50.0, width: 100.0, height: 50.0 });
+
+        assert!(validate_bbox(&BoundingBox { x: 150.0, y: 0.0, width: 60.0, height: 10.0 }, false, 200, 100).is_err());
+        assert!(validate_bbox(&BoundingBox { x: 0.0, y: 0.0, width: 0.0, height: 10.0 }, false, 200, 100).is_err());
+        assert!(validate_bbox(&BoundingBox { x: f64::NAN, y: 0.0, width: 1.0, height: 1.0 }, false, 200, 100).is_err());
+        assert!(validate_bbox(&BoundingBox { x: 0.0, y: 0.0, width: 1.5, height: 0.5 }, true, 200, 100).is_err());
+    }
+
+    #[test]
+    fn polygons_report_area_and_bounds() {
+        let square = vec![[10.0, 10.0], [30.0, 10.0], [30.0, 20.0], [10.0, 20.0], [10.0, 10.0]];
+        let (rings, bbox, area) = validate_polygons(&[square], false, 100, 100).unwrap();
+        assert_eq!(rings[0].len(), 4);
+        assert_eq!(bbox, BoundingBox { x: 10.0, y: 10.0, width: 20.0, height: 10.0 });
+        assert_eq!(area, 200.0);
+    }
+
+    #[test]
+    fn rejects_degenerate_and_self_intersecting_polygons() {
+        let bowtie = vec![[0.0, 0.0], [10.0, 10.0], [10.0, 0.0], [0.0, 10.0]];
+        let line = vec![[0.0, 0.0], [5.0, 5.0], [10.0, 10.0]];
+        let outside = vec![[0.0, 0.0], [200.0, 0.0], [0.0, 10.0]];
+        assert!(validate_polygons(&[bowtie], false, 100, 100).is_err());
+        assert!(validate_polygons(&[line], false, 100, 100).is_err());
+        assert!(validate_polygons(&[outside], false, 100, 100).is_err());
+        assert!(validate_polygons(&[vec![[0.0, 0.0], [1.0, 1.0]]], false, 100, 100).is_err());
+        assert!(validate_polygons(&[], false, 100, 100).is_err());
+    }
+
+    #[test]
+    fn masks_are_read_in_column_order() {
+        // Imagem 3x2: a coluna do meio inteira está marcada
+        let mask = RleMask { size: [2, 3], counts: vec![2, 2, 2] };
+        let (bbox, area) = validate_mask(&mask, 3, 2).unwrap();
+        assert_eq!(bbox, BoundingBox { x: 1.0, y: 0.0, width: 1.0, height: 2.0 });
+        assert_eq!(area, 2.0);
+
+        assert!(validate_mask(&RleMask { size: [3, 2], counts: vec![2, 2, 2] }, 3, 2).is_err());
+        assert!(validate_mask(&RleMask { size: [2, 3], counts: vec![2, 2] }, 3, 2).is_err());
+        assert!(validate_mask(&RleMask { size: [2, 3], counts: vec![6] }, 3, 2).is_err());
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::models::{label_key, collapse_whitespace, AttributeMap, MergeTagsReport, MergeTagsRequest, TagAlias};
+use super::DataService;
+use super::vocabulary_service::VocabularyError;
+
+impl DataService {
+    // Alias do grupo tem prioridade sobre o alias global
+    pub fn resolve_alias(&self, group_id: Option<&str>, tag: &str) -> Option<&TagAlias> {
+        let key = label_key(tag);
+        let mut global = None;
+        for alias in self.tag_aliases.values().filter(|alias| label_key(&alias.alias) == key) {
+            match alias.group_id.as_deref() {
+                Some(id) if Some(id) == group_id => return Some(alias),
+                None => global = global.or(Some(alias)),
+                _ => {}
+            }
+        }
+        global
+    }
+
+    // Normalização do grupo seguida da tabela de aliases
+    pub fn normalize_tag(&self, group_id: Option<&str>, tag: &str) -> String {
+        let normalized = match group_id.and_then(|id| self.groups.get(id)) {
+            Some(group) => group.tag_normalization.apply(tag),
+            None => collapse_whitespace(tag),
+        };
+        match self.resolve_alias(group_id, &normalized) {
+            Some(alias) => alias.canonical.clone(),
+            None => normalized,
+        }
+    }
+
+    pub fn list_tag_aliases(&self, group_id: Option<&str>) -> Vec<&TagAlias> {
+        let mut aliases: Vec<&TagAlias> = self.tag_aliases.values()
+            .filter(|alias| group_id.is_none_or(|group_id| alias.group_id.as_deref().is_none_or(|id| id == group_id)))
+            .collect();
+        aliases.sort_by(|a, b| label_key(&a.alias).cmp(&label_key(&b.alias)).then_with(|| a.id.cmp(&b.id)));
+        aliases
+    }
+
+    // Cria o alias, ou aponta para o novo canônico o alias já existente no mesmo escopo
+    pub fn upsert_tag_alias(
+        &mut self,
+        alias: &str,
+        canonical: &str,
+        group_id: Option<&str>,
+        created_by: &str,
+    ) -> Result<TagAlias, VocabularyError> {
+        let (alias, canonical) = (collapse_whitespace(alias), collapse_whitespace(canonical));
+        if alias.is_empty() || canonical.is_empty() {
+            return Err(VocabularyError::Invalid("Alias and canonical label cannot be empty".to_string()));
+        }
+        if label_key(&alias) == label_key(&canonical) {
+            return Err(VocabularyError::Invalid("Alias and canonical label are the same".to_string()));
+        }
+        if let Some(group_id) = group_id {
+            if !self.groups.contains_key(group_id) {
+                return Err(VocabularyError::GroupNotFound);
+            }
+        }
+        if let Some(chained) = self.resolve_alias(group_id, &canonical) {
+            return Err(VocabularyError::Invalid(format!(
+                "'{}' is itself an alias of '{}'; use the canonical label instead",
+                canonical, chained.canonical,
+            )));
+        }
+
+        let key = label_key(&alias);
+        let existing = self.tag_aliases.values_mut()
+            .find(|existing| label_key(&existing.alias) == key && existing.group_id.as_deref() == group_id);
+        if let Some(existing) = existing {
+            existing.canonical = canonical;
+            return Ok(existing.clone());
+        }
+        let created = TagAlias::new(alias, canonical, group_id.map(str::to_string), created_by.to_string());
+        self.tag_aliases.insert(created.id.clone(), created.clone());
+        Ok(created)
+    }
+
+    // Renomeia as tags de origem para o destino em sugestões, tags aprovadas, votos e vocabulários, em uma
+    // única transação. Em modo preview as alterações são calculadas e desfeitas.
+    pub fn merge_tags(&mut self, req: &MergeTagsRequest) -> Result<MergeTagsReport, VocabularyError> {
+        let target = collapse_whitespace(&req.target);
+        if target.is_empty() {
+            return Err(VocabularyError::Invalid("Target label cannot be empty".to_string()));
+        }
+        let source_keys: HashSet<String> = req.sources.iter()
+            .map(|source| label_key(source))
+            .filter(|key| !key.is_empty())
+            .collect();
+        if source_keys.is_empty() {
+            return Err(VocabularyError::Invalid("At least one source label is required".to_string()));
+        }
+        if let Some(group_id) = &req.group_id {
+            if !self.groups.contains_key(group_id) {
+                return Err(VocabularyError::GroupNotFound);
+            }
+        }
+
+        let snapshot = self.snapshot();
+        // Só as imagens com algum rótulo de origem ou de destino podem mudar
+        let target_key = label_key(&target);
+        let touches = |label: &str| {
+            let key = label_key(label);
+            key == target_key || source_keys.contains(&key)
+        };
+        let candidates: HashSet<String> = self.tag_suggestions.values()
+            .filter(|suggestion| touches(&suggestion.tag))
+            .map(|suggestion| suggestion.image_id.clone())
+            .chain(self.approved_tags.values().filter(|tag| touches(&tag.tag)).map(|tag| tag.image_id.clone()))
+            .filter(|image_id| self.images.get(image_id)
+                .is_some_and(|image| req.group_id.as_ref().is_none_or(|group_id| &image.group_id == group_id)))
+            .collect();
+        let checkpoint = self.history_checkpoint(candidates.iter().map(String::as_str));
+        let result = self.apply_tag_merge(req, &target, &source_keys);
+        if req.preview || result.is_err() {
+            self.restore(snapshot);
+        } else {
+            self.record_history(checkpoint, &req.performed_by);
+        }
+        result
+    }
+
+    fn apply_tag_merge(
+        &mut self,
+        req: &MergeTagsRequest,
+        target: &str,
+        source_keys: &HashSet<String>,
+    ) -> Result<MergeTagsReport, VocabularyError> {
+        let group_id = req.group_id.as_deref();
+        let target_key = label_key(target);
+        let mut report = MergeTagsReport { preview: req.preview, target: target.to_string(), ..Default::default() };
+        let in_scope: HashSet<String> = self.images.values()
+            .filter(|image| group_id.is_none_or(|group_id| image.group_id == group_id))
+            .map(|image| image.id.clone())
+            .collect();
+        let mut affected: HashSet<String> = HashSet::new();
+
+        for suggestion in self.tag_suggestions.values_mut().filter(|suggestion| in_scope.contains(&suggestion.image_id)) {
+            if source_keys.contains(&label_key(&suggestion.tag)) && suggestion.tag != target {
+                suggestion.tag = target.to_string();
+                report.suggestions_renamed += 1;
+                affected.insert(suggestion.image_id.clone());
+            }
+        }
+        for tag in self.approved_tags.values_mut().filter(|tag| in_scope.contains(&tag.image_id)) {
+            if source_keys.contains(&label_key(&tag.tag)) && tag.tag != target {
+                tag.tag = target.to_string();
+                report.approved_renamed += 1;
+                affected.insert(tag.image_id.clone());
+            }
+        }
+
+        // Duplicatas na mesma imagem: mantém a mais antiga e migra os votos
+        let mut approved: Vec<(String, String, String)> = self.approved_tags.values()
+            .filter(|tag| affected.contains(&tag.image_id) && label_key(&tag.tag) == target_key)
+            .map(|tag| (tag.image_id.clone(), tag.approved_at.clone(), tag.id.clone()))
+            .collect();
+        approved.sort();
+        let mut keepers: HashMap<String, String> = HashMap::new();
+        for (image_id, _, tag_id) in approved {
+            match keepers.get(&image_id).cloned() {
+                Some(keeper_id) => {
+                    let (moved, dropped) = self.fold_tag_upvotes(&tag_id, &keeper_id);
+                    self.retarget_comments(&tag_id, &keeper_id, &image_id);
+                    report.upvotes_moved += moved;
+                    report.upvotes_dropped += dropped;
+                    if let Some(removed) = self.approved_tags.remove(&tag_id) {
+                        fold_attributes(removed.attributes, self.approved_tags.get_mut(&keeper_id).map(|tag| &mut tag.attributes));
+                    }
+                    report.approved_merged += 1;
+                }
+                None => {
+                    keepers.insert(image_id, tag_id);
+                }
+            }
+        }
+        for keeper_id in keepers.values() {
+            self.recount_upvotes(keeper_id);
+        }
+
+        let mut pending: Vec<(String, String, String)> = self.tag_suggestions.values()
+            .filter(|suggestion| {
+                suggestion.status.is_open() && affected.contains(&suggestion.image_id) && label_key(&suggestion.tag) == target_key
+            })
+            .map(|suggestion| (suggestion.image_id.clone(), suggestion.suggested_at.clone(), suggestion.id.clone()))
+            .collect();
+        pending.sort();
+        let mut pending_keepers: HashMap<String, String> = HashMap::new();
+        for (image_id, _, suggestion_id) in pending {
+            match pending_keepers.get(&image_id).cloned() {
+                Some(keeper_id) => {
+                    let (moved, dropped) = self.fold_tag_upvotes(&suggestion_id, &keeper_id);
+                    self.retarget_comments(&suggestion_id, &keeper_id, &image_id);
+                    report.upvotes_moved += moved;
+                    report.upvotes_dropped += dropped;
+                    if let Some(removed) = self.tag_suggestions.remove(&suggestion_id) {
+                        fold_attributes(removed.attributes, self.tag_suggestions.get_mut(&keeper_id).map(|suggestion| &mut suggestion.attributes));
+                    }
+                    report.suggestions_merged += 1;
+                }
+                None => {
+                    pending_keepers.insert(image_id, suggestion_id);
+                }
+            }
+        }
+        report.images_affected = affected.len();
+
+        report.vocabularies_updated = self.merge_vocabulary_labels(group_id, target, source_keys)?;
+
+        // Aliases existentes que apontavam para uma origem passam a apontar para o destino
+        for alias in self.tag_aliases.values_mut() {
+            let alias_in_scope = group_id.is_none() || alias.group_id.as_deref() == group_id;
+            if alias_in_scope && source_keys.contains(&label_key(&alias.canonical)) {
+                alias.canonical = target.to_string();
+            }
+        }
+        self.tag_aliases.retain(|_, alias| label_key(&alias.alias) != label_key(&alias.canonical));
+        if req.create_aliases {
+            for source in &req.sources {
+                if label_key(source) != target_key && !source.trim().is_empty() {
+                    self.upsert_tag_alias(source, target, group_id, &req.performed_by)?;
+                    report.aliases_created += 1;
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    // Funde as definições de origem no destino dentro dos vocabulários do escopo, religando os filhos
+    fn merge_vocabulary_labels(&mut self, group_id: Option<&str>, target: &str, source_keys: &HashSet<String>) -> Result<usize, VocabularyError> {
+        let target_key = label_key(target);
+        let mut updated = 0;
+        for group in self.groups.values_mut().filter(|group| group_id.is_none_or(|id| group.id == id)) {
+            let Some(vocabulary) = group.vocabulary.as_mut() else {
+                continue;
+            };
+            let matching: Vec<usize> = vocabulary.labels.iter()
+                .enumerate()
+                .filter(|(_, definition)| {
+                    let key = label_key(&definition.name);
+                    source_keys.contains(&key) || key == target_key
+                })
+                .map(|(index, _)| index)
+                .collect();
+            if matching.is_empty() || (matching.len() == 1 && vocabulary.labels[matching[0]].name == target) {
+                continue;
+            }
+
+            // Mantém a definição do destino (ou a primeira origem) e herda o primeiro pai externo ao grupo fundido
+            let keep = matching.iter().copied()
+                .find(|&index| label_key(&vocabulary.labels[index].name) == target_key)
+                .unwrap_or(matching[0]);
+            let merged_keys: HashSet<String> = matching.iter().map(|&index| label_key(&vocabulary.labels[index].name)).collect();
+            let parent = matching.iter()
+                .filter_map(|&index| vocabulary.labels[index].parent.clone())
+                .find(|parent| !merged_keys.contains(&label_key(parent)));
+
+            let mut kept = vocabulary.labels[keep].clone();
+            kept.name = target.to_string();
+            kept.parent = parent;
+            for &index in &matching {
+                for attribute in &vocabulary.labels[index].attributes {
+                    if !kept.attributes.iter().any(|existing| existing.name.eq_ignore_ascii_case(&attribute.name)) {
+                        kept.attributes.push(attribute.clone());
+                    }
+                }
+            }
+            if kept.description.is_empty() {
+                kept.description = matching.iter()
+                    .map(|&index| vocabulary.labels[index].description.clone())
+                    .find(|description| !description.is_empty())
+                    .unwrap_or_default();
+            }
+            let mut index = 0;
+            vocabulary.labels.retain(|_| {
+                let retain = !matching.contains(&index);
+                index += 1;
+                retain
+            });
+            for definition in vocabulary.labels.iter_mut() {
+                if definition.parent.as_ref().is_some_and(|parent| merged_keys.contains(&label_key(parent))) {
+                    definition.parent = Some(target.to_string());
+                }
+            }
+            vocabulary.labels.insert(keep.min(vocabulary.labels.len()), kept);
+            vocabulary.validate()
+                .map_err(|e| VocabularyError::Invalid(format!("Merge would break the vocabulary of group '{}': {}", group.name, e)))?;
+            updated += 1;
+        }
+        Ok(updated)
+    }
+}
+
+// Atributos da tag descartada completam os da tag mantida, sem sobrescrever
+fn fold_attributes(from: AttributeMap, into: Option<&mut AttributeMap>) {
+    if let Some(into) = into {
+        for (name, value) in from {
+            into.entry(name).or_insert(value);
+        }
+    }
+}
@@ -0,0 +1,550 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::models::{AttributeMap, AttributeValue, Image};
+use super::DataService;
+
+// Linguagem de consulta booleana sobre tags, por exemplo:
+//   cat AND outdoor AND NOT blurry
+//   group:Animais AND (dog OR wolf)
+//   pending:"golden retriever" OR name:IMG_*
+//   car[color=red, occluded] AND NOT car[truncated=partial]
+// Termos sem campo procuram nas tags aprovadas. Termos adjacentes sem operador são combinados com AND.
+// Filtros de atributos entre colchetes valem para tags e sugestões pendentes (`attr` sozinho equivale a `attr=true`).
+
+// Limites contra consultas que estourariam a pilha do parser ou da avaliação
+pub const MAX_QUERY_DEPTH: usize = 32;
+pub const MAX_QUERY_TERMS: usize = 256;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryField {
+    Tag,
+    Pending,
+    Group,
+    Uploader,
+    Name,
+}
+
+impl QueryField {
+    fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "tag" => Some(QueryField::Tag),
+            "pending" => Some(QueryField::Pending),
+            "group" => Some(QueryField::Group),
+            "uploader" => Some(QueryField::Uploader),
+            "name" => Some(QueryField::Name),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeFilter {
+    pub name: String,
+    pub op: CompareOp,
+    pub value: String,
+}
+
+impl AttributeFilter {
+    fn parse(filter: &str) -> Option<Self> {
+        // Operadores de dois caracteres primeiro para que "<=" não seja lido como "<"
+        const OPERATORS: [(&str, CompareOp); 6] = [
+            ("!=", CompareOp::Ne), ("<=", CompareOp::Le), (">=", CompareOp::Ge),
+            ("=", CompareOp::Eq), ("<", CompareOp::Lt), (">", CompareOp::Gt),
+        ];
+        let (name, op, value) = OPERATORS.iter()
+            .filter_map(|(symbol, op)| filter.find(symbol).map(|index| (index, symbol, op)))
+            .min_by_key(|(index, symbol, _)| (*index, std::cmp::Reverse(symbol.len())))
+            .map(|(index, symbol, op)| (&filter[..index], *op, &filter[index + symbol.len()..]))
+            .unwrap_or((filter, CompareOp::Eq, "true"));
+        let name = name.trim().to_lowercase();
+        let value = value.trim().to_lowercase();
+        if name.is_empty() || value.is_empty() {
+            return None;
+        }
+        Some(AttributeFilter { name, op, value })
+    }
+
+    fn matches(&self, attributes: &AttributeMap) -> bool {
+        let Some(actual) = attributes.iter().find(|(name, _)| name.to_lowercase() == self.name).map(|(_, value)| value) else {
+            return false;
+        };
+        let ordering = match actual {
+            AttributeValue::Number(number) => match self.value.parse::<f64>() {
+                Ok(expected) => number.partial_cmp(&expected),
+                Err(_) => return false,
+            },
+            // Texto e booleanos só aceitam igualdade
+            _ if !matches!(self.op, CompareOp::Eq | CompareOp::Ne) => return false,
+            _ => Some(actual.to_string().to_lowercase().cmp(&self.value)),
+        };
+        let Some(ordering) = ordering else {
+            return false;
+        };
+        match self.op {
+            CompareOp::Eq => ordering.is_eq(),
+            CompareOp::Ne => ordering.is_ne(),
+            CompareOp::Lt => ordering.is_lt(),
+            CompareOp::Le => ordering.is_le(),
+            CompareOp::Gt => ordering.is_gt(),
+            CompareOp::Ge => ordering.is_ge(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryTerm {
+    pub field: QueryField,
+    pub value: String,
+    pub prefix: bool,
+    pub attributes: Vec<AttributeFilter>,
+}
+
+impl QueryTerm {
+    fn matches(&self, candidate: &str) -> bool {
+        let candidate = candidate.trim().to_lowercase();
+        if self.prefix {
+            candidate.starts_with(&self.value)
+        } else {
+            candidate == self.value
+        }
+    }
+
+    fn matches_attributes(&self, tags: &[(&str, &AttributeMap)]) -> bool {
+        tags.iter().any(|(tag, attributes)| self.matches(tag) && self.attributes.iter().all(|filter| filter.matches(attributes)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryExpr {
+    Term(QueryTerm),
+    Not(Box<QueryExpr>),
+    And(Box<QueryExpr>, Box<QueryExpr>),
+    Or(Box<QueryExpr>, Box<QueryExpr>),
+}
+
+#[derive(Debug)]
+pub struct QueryError {
+    pub message: String,
+    pub position: usize,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at position {})", self.message, self.position)
+    }
+}
+
+// Palavra da consulta; ':' e '[' entre aspas fazem parte do valor, então as posições
+// (em bytes) do separador de campo e do início dos filtros são guardadas na leitura
+#[derive(Debug, Clone, PartialEq)]
+struct Word {
+    text: String,
+    field_end: Option<usize>,
+    filters_start: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Word(Word),
+}
+
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, QueryError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        if c.is_whitespace() {
+            index += 1;
+            continue;
+        }
+        let start = index;
+        match c {
+            '(' => {
+                tokens.push((Token::LParen, start));
+                index += 1;
+            }
+            ')' => {
+                tokens.push((Token::RParen, start));
+                index += 1;
+            }
+            _ => {
+                // Palavra, possivelmente com campo e valor entre aspas: group:"Animais Selvagens"
+                let mut word = String::new();
+                let mut quoted = false;
+                let mut in_brackets = false;
+                let (mut field_end, mut filters_start) = (None, None);
+                while index < chars.len() {
+                    let c = chars[index];
+                    if c == '"' {
+                        let close = chars[index + 1..].iter().position(|&c| c == '"').ok_or(QueryError {
+                            message: "Unterminated quoted string".to_string(),
+                            position: index,
+                        })?;
+                        word.extend(&chars[index + 1..index + 1 + close]);
+                        index += close + 2;
+                        quoted = true;
+                        continue;
+                    }
+                    if !in_brackets && (c.is_whitespace() || c == '(' || c == ')') {
+                        break;
+                    }
+                    match c {
+                        '[' => {
+                            if !in_brackets && filters_start.is_none() {
+                                filters_start = Some(word.len());
+                            }
+                            in_brackets = true;
+                        }
+                        ']' => in_brackets = false,
+                        ':' if !in_brackets && field_end.is_none() && filters_start.is_none() => field_end = Some(word.len()),
+                        _ => {}
+                    }
+                    word.push(c);
+                    index += 1;
+                }
+
+                let token = match word.as_str() {
+                    _ if quoted => Token::Word(Word { text: word, field_end, filters_start }),
+                    "AND" | "and" | "&&" => Token::And,
+                    "OR" | "or" | "||" => Token::Or,
+                    "NOT" | "not" | "!" => Token::Not,
+                    _ => Token::Word(Word { text: word, field_end, filters_start }),
+                };
+                tokens.push((token, start));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    index: usize,
+    end: usize,
+    depth: usize, // parênteses e NOTs abertos
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(token, _)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens.get(self.index).map(|(_, position)| *position).unwrap_or(self.end)
+    }
+
+    fn error(&self, message: &str) -> QueryError {
+        QueryError { message: message.to_string(), position: self.position() }
+    }
+
+    fn descend(&mut self) -> Result<(), QueryError> {
+        self.depth += 1;
+        if self.depth > MAX_QUERY_DEPTH {
+            return Err(self.error(&format!("Query is nested more than {} levels deep", MAX_QUERY_DEPTH)));
+        }
+        Ok(())
+    }
+
+    fn parse_or(&mut self) -> Result<QueryExpr, QueryError> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.index += 1;
+            let right = self.parse_and()?;
+            left = QueryExpr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<QueryExpr, QueryError> {
+        let mut left = self.parse_not()?;
+        loop {
+            match self.peek() {
+                Some(Token::And) => self.index += 1,
+                // AND implícito entre termos adjacentes
+                Some(Token::Word(_)) | Some(Token::Not) | Some(Token::LParen) => {}
+                _ => break,
+            }
+            let right = self.parse_not()?;
+            left = QueryExpr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<QueryExpr, QueryError> {
+        if self.peek() == Some(&Token::Not) {
+            self.index += 1;
+            self.descend()?;
+            let inner = self.parse_not()?;
+            self.depth -= 1;
+            return Ok(QueryExpr::Not(Box::new(inner)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<QueryExpr, QueryError> {
+        match self.peek().cloned() {
+            Some(Token::LParen) => {
+                self.index += 1;
+                self.descend()?;
+                let expr = self.parse_or()?;
+                if self.peek() != Some(&Token::RParen) {
+                    return Err(self.error("Expected ')'"));
+                }
+                self.index += 1;
+                self.depth -= 1;
+                Ok(expr)
+            }
+            Some(Token::Word(word)) => {
+                let term = self.parse_term(&word)?;
+                self.index += 1;
+                Ok(QueryExpr::Term(term))
+            }
+            Some(_) => Err(self.error("Unexpected operator")),
+            None => Err(self.error("Unexpected end of query")),
+        }
+    }
+
+    fn parse_term(&self, word: &Word) -> Result<QueryTerm, QueryError> {
+        let (text, attributes) = match word.filters_start {
+            Some(start) => {
+                let filters = word.text[start + 1..].strip_suffix(']').ok_or_else(|| self.error("Expected ']'"))?;
+                let attributes = filters.split(',')
+                    .map(|filter| AttributeFilter::parse(filter).ok_or_else(|| self.error(&format!("Invalid attribute filter '{}'", filter.trim()))))
+                    .collect::<Result<Vec<_>, _>>()?;
+                (&word.text[..start], attributes)
+            }
+            None => (word.text.as_str(), Vec::new()),
+        };
+        let (field, value) = match word.field_end {
+            Some(end) => {
+                let name = &text[..end];
+                let field = QueryField::parse(name)
+                    .ok_or_else(|| self.error(&format!("Unknown field '{}'", name)))?;
+                (field, &text[end + 1..])
+            }
+            None => (QueryField::Tag, text),
+        };
+
+        let value = value.trim().to_lowercase();
+        let (value, prefix) = match value.strip_suffix('*') {
+            Some(stripped) => (stripped.to_string(), true),
+            None => (value, false),
+        };
+        if value.is_empty() && !prefix {
+            return Err(self.error("Empty search term"));
+        }
+        if !attributes.is_empty() && !matches!(field, QueryField::Tag | QueryField::Pending) {
+            return Err(self.error("Attribute filters only apply to tag and pending terms"));
+        }
+        Ok(QueryTerm { field, value, prefix, attributes })
+    }
+}
+
+pub fn parse_query(input: &str) -> Result<QueryExpr, QueryError> {
+    let tokens = tokenize(input)?;
+    let mut words = tokens.iter().filter(|(token, _)| matches!(token, Token::Word(_)));
+    if let Some((_, position)) = words.nth(MAX_QUERY_TERMS) {
+        return Err(QueryError { message: format!("Query has more than {} terms", MAX_QUERY_TERMS), position: *position });
+    }
+    let mut parser = Parser { tokens, index: 0, end: input.chars().count(), depth: 0 };
+    let expr = parser.parse_or()?;
+    if parser.index < parser.tokens.len() {
+        return Err(parser.error("Unexpected token"));
+    }
+    Ok(expr)
+}
+
+// Dados de uma imagem usados na avaliação da consulta
+pub struct QueryContext<'a> {
+    pub image: &'a Image,
+    pub group_name: &'a str,
+    pub approved_tags: HashSet<String>,
+    pub pending_tags: HashSet<String>,
+    pub approved_attributes: Vec<(&'a str, &'a AttributeMap)>,
+    pub pending_attributes: Vec<(&'a str, &'a AttributeMap)>,
+}
+
+impl QueryExpr {
+    pub fn evaluate(&self, context: &QueryContext) -> bool {
+        match self {
+            QueryExpr::Term(term) => match term.field {
+                // Com filtros, a mesma tag (sem ancestrais implícitos) precisa satisfazer todos eles
+                QueryField::Tag if !term.attributes.is_empty() => term.matches_attributes(&context.approved_attributes),
+                QueryField::Pending if !term.attributes.is_empty() => term.matches_attributes(&context.pending_attributes),
+                QueryField::Tag => context.approved_tags.iter().any(|tag| term.matches(tag)),
+                QueryField::Pending => context.pending_tags.iter().any(|tag| term.matches(tag)),
+                QueryField::Group => term.matches(context.group_name) || term.matches(&context.image.group_id),
+                QueryField::Uploader => term.matches(&context.image.uploaded_by),
+                QueryField::Name => term.matches(&context.image.original_name),
+            },
+            QueryExpr::Not(inner) => !inner.evaluate(context),
+            QueryExpr::And(left, right) => left.evaluate(context) && right.evaluate(context),
+            QueryExpr::Or(left, right) => left.evaluate(context) || right.evaluate(context),
+        }
+    }
+}
+
+impl DataService {
+    pub fn query_contexts(&self) -> HashMap<&str, QueryContext<'_>> {
+        let mut contexts: HashMap<&str, QueryContext> = self.images.values()
+            .map(|image| {
+                let group_name = self.groups.get(&image.group_id).map(|group| group.name.as_str()).unwrap_or("");
+                (image.id.as_str(), QueryContext {
+                    image,
+                    group_name,
+                    approved_tags: HashSet::new(),
+                    pending_tags: HashSet::new(),
+                    approved_attributes: Vec::new(),
+                    pending_attributes: Vec::new(),
+                })
+            })
+            .collect();
+
+        for tag in self.approved_tags.values() {
+            if let Some(context) = contexts.get_mut(tag.image_id.as_str()) {
+                context.approved_tags.insert(tag.tag.trim().to_lowercase());
+                // Tags aprovadas implicam seus ancestrais no vocabulário do grupo
+                context.approved_tags.extend(self.implied_labels(&context.image.group_id, &tag.tag));
+                context.approved_attributes.push((tag.tag.as_str(), &tag.attributes));
+            }
+        }
+        for suggestion in self.tag_suggestions.values().filter(|suggestion| suggestion.status.is_open()) {
+            if let Some(context) = contexts.get_mut(suggestion.image_id.as_str()) {
+                context.pending_tags.insert(suggestion.tag.trim().to_lowercase());
+                context.pending_attributes.push((suggestion.tag.as_str(), &suggestion.attributes));
+            }
+        }
+        contexts
+    }
+
+    // Ids das imagens que satisfazem a consulta
+    pub fn search_image_ids(&self, query: &str) -> Result<HashSet<String>, QueryError> {
+        let expr = parse_query(query)?;
+        Ok(self.query_contexts()
+            .into_iter()
+            .filter(|(_, context)| expr.evaluate(context))
+            .map(|(image_id, _)| image_id.to_string())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(field: QueryField, value: &str) -> QueryExpr {
+        QueryExpr::Term(QueryTerm { field, value: value.to_string(), prefix: false, attributes: Vec::new() })
+    }
+
+    fn tag(value: &str) -> QueryExpr {
+        term(QueryField::Tag, value)
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let expr = parse_query("cat OR dog AND outdoor").unwrap();
+        let expected = QueryExpr::Or(
+            Box::new(tag("cat")),
+            Box::new(QueryExpr::And(Box::new(tag("dog")), Box::new(tag("outdoor")))),
+        );
+        assert_eq!(expr, expected);
+    }
+
+    #[test]
+    fn adjacent_terms_are_joined_with_and() {
+        let expr = parse_query("cat NOT blurry").unwrap();
+        let expected = QueryExpr::And(Box::new(tag("cat")), Box::new(QueryExpr::Not(Box::new(tag("blurry")))));
+        assert_eq!(expr, expected);
+    }
+
+    #[test]
+    fn parentheses_override_precedence() {
+        let expr = parse_query("(cat OR dog) AND outdoor").unwrap();
+        let expected = QueryExpr::And(
+            Box::new(QueryExpr::Or(Box::new(tag("cat")), Box::new(tag("dog")))),
+            Box::new(tag("outdoor")),
+        );
+        assert_eq!(expr, expected);
+    }
+
+    #[test]
+    fn fields_and_prefixes() {
+        assert_eq!(parse_query("group:Animais").unwrap(), term(QueryField::Group, "animais"));
+        let expr = parse_query("name:IMG_*").unwrap();
+        assert_eq!(expr, QueryExpr::Term(QueryTerm { field: QueryField::Name, value: "img_".to_string(), prefix: true, attributes: Vec::new() }));
+    }
+
+    #[test]
+    fn quoted_colon_is_part_of_the_value() {
+        assert_eq!(parse_query("\"ratio:16:9\"").unwrap(), tag("ratio:16:9"));
+        assert_eq!(parse_query("group:\"Animais: Selvagens\"").unwrap(), term(QueryField::Group, "animais: selvagens"));
+        assert_eq!(parse_query("pending:\"golden retriever\"").unwrap(), term(QueryField::Pending, "golden retriever"));
+    }
+
+    #[test]
+    fn quoted_keywords_are_terms() {
+        assert_eq!(parse_query("\"and\"").unwrap(), tag("and"));
+    }
+
+    #[test]
+    fn attribute_filters() {
+        let QueryExpr::Term(term) = parse_query("car[color=red, speed>=3, occluded]").unwrap() else {
+            panic!("expected a term");
+        };
+        assert_eq!(term.value, "car");
+        let filters: Vec<(&str, CompareOp, &str)> = term.attributes.iter()
+            .map(|filter| (filter.name.as_str(), filter.op, filter.value.as_str()))
+            .collect();
+        assert_eq!(filters, vec![("color", CompareOp::Eq, "red"), ("speed", CompareOp::Ge, "3"), ("occluded", CompareOp::Eq, "true")]);
+        assert!(parse_query("group:x[color=red]").is_err());
+    }
+
+    #[test]
+    fn syntax_errors_report_position() {
+        let error = parse_query("(cat OR dog").unwrap_err();
+        assert_eq!(error.message, "Expected ')'");
+        assert_eq!(error.position, 11);
+        assert!(parse_query("cat AND").is_err());
+        assert!(parse_query("cat )").is_err());
+        assert!(parse_query("colour:red").unwrap_err().message.contains("Unknown field"));
+        assert!(parse_query("\"open").unwrap_err().message.contains("Unterminated"));
+    }
+
+    #[test]
+    fn deep_nesting_is_rejected_without_overflowing() {
+        let parens = format!("{}cat{}", "(".repeat(100_000), ")".repeat(100_000));
+        assert!(parse_query(&parens).unwrap_err().message.contains("nested"));
+        let nots = format!("{}cat", "NOT ".repeat(100_000));
+        assert!(parse_query(&nots).unwrap_err().message.contains("nested"));
+
+        let allowed = format!("{}cat{}", "(".repeat(MAX_QUERY_DEPTH), ")".repeat(MAX_QUERY_DEPTH));
+        assert_eq!(parse_query(&allowed).unwrap(), tag("cat"));
+    }
+
+    #[test]
+    fn long_queries_are_rejected() {
+        let terms = vec!["cat"; MAX_QUERY_TERMS + 1].join(" OR ");
+        assert!(parse_query(&terms).unwrap_err().message.contains("terms"));
+        let terms = vec!["cat"; MAX_QUERY_TERMS].join(" OR ");
+        assert!(parse_query(&terms).is_ok());
+    }
+}
@@ -0,0 +1,516 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use chrono::Utc;
+
+use crate::models::{
+    label_key, AnnotatorRejectStats, ApprovedTag, AttributeMap, ConflictResolution, QuorumState, RejectStatsReport, ReviewApproval, ReviewQuorum,
+    ReviewTagRequest, SuggestionStatus, TagSuggestion, DEFAULT_REJECT_REASONS,
+};
+use super::DataService;
+use super::consensus_service::SYSTEM_REVIEWER;
+
+pub const MAX_REVIEW_NOTE_LENGTH: usize = 1000;
+
+#[derive(Debug)]
+pub enum ReviewError {
+    SuggestionNotFound,
+    GroupNotFound,
+    IllegalTransition { from: SuggestionStatus, to: SuggestionStatus },
+    Invalid(String),
+    ExclusiveConflict(Vec<(String, String)>), // (id, tag) das classes já aprovadas
+    Forbidden(&'static str),
+}
+
+impl fmt::Display for ReviewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReviewError::SuggestionNotFound => write!(f, "Tag suggestion not found"),
+            ReviewError::GroupNotFound => write!(f, "Group not found"),
+            ReviewError::IllegalTransition { from, to } => write!(f, "Illegal status transition: {} → {}", from, to),
+            ReviewError::Invalid(message) => write!(f, "{}", message),
+            ReviewError::ExclusiveConflict(_) => write!(f, "Image already has an approved class in this exclusive group"),
+            ReviewError::Forbidden(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ReviewOutcome {
+    pub suggestion: TagSuggestion,
+    pub changed: bool, // false quando a sugestão já estava no estado pedido
+    pub approved_tag_id: Option<String>,
+    pub removed_tags: Vec<String>, // tags aprovadas desfeitas por esta revisão
+    pub superseded_tags: Vec<String>,
+}
+
+impl fmt::Display for SuggestionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SuggestionStatus::Pending => "pending",
+            SuggestionStatus::Approved => "approved",
+            SuggestionStatus::Rejected => "rejected",
+            SuggestionStatus::Reopened => "reopened",
+            SuggestionStatus::Superseded => "superseded",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// Códigos de motivo em snake_case: "Too vague" → "too_vague"
+pub fn normalize_reason(reason: &str) -> String {
+    reason.split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|part| !part.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+pub fn validate_reject_reasons(reasons: &[String]) -> Result<Vec<String>, String> {
+    let mut seen = HashSet::new();
+    let mut normalized = Vec::new();
+    for reason in reasons {
+        let code = normalize_reason(reason);
+        if code.is_empty() {
+            return Err("Reject reasons cannot be empty".to_string());
+        }
+        if !seen.insert(code.clone()) {
+            return Err(format!("Duplicate reject reason '{}'", code));
+        }
+        normalized.push(code);
+    }
+    if normalized.is_empty() {
+        return Err("At least one reject reason is required".to_string());
+    }
+    Ok(normalized)
+}
+
+impl ReviewOutcome {
+    fn unchanged(suggestion: TagSuggestion) -> Self {
+        Self {
+            suggestion,
+            changed: false,
+            approved_tag_id: None,
+            removed_tags: Vec::new(),
+            superseded_tags: Vec::new(),
+        }
+    }
+}
+
+impl ReviewQuorum {
+    // Só muda a revisão quando exige mais de uma aprovação ou restringe os revisores
+    pub fn is_active(&self) -> bool {
+        self.approvals_required > 1 || !self.reviewers.is_empty()
+    }
+}
+
+impl SuggestionStatus {
+    // Aguardando revisão
+    pub fn is_open(self) -> bool {
+        matches!(self, SuggestionStatus::Pending | SuggestionStatus::Reopened)
+    }
+
+    pub fn can_transition_to(self, next: SuggestionStatus) -> bool {
+        use SuggestionStatus::*;
+        matches!(
+            (self, next),
+            (Pending | Reopened, Approved | Rejected) | (Approved, Reopened | Rejected) | (Rejected | Superseded, Reopened)
+        )
+    }
+}
+
+impl DataService {
+    // Remove a tag aprovada com seus votos e conversas; usado na remoção, na substituição e ao desfazer a aprovação
+    pub fn remove_approved_tag(&mut self, tag_id: &str) -> Option<ApprovedTag> {
+        let tag = self.approved_tags.remove(tag_id)?;
+        self.tag_upvotes.retain(|_, upvote| upvote.tag_id != tag_id);
+        self.remove_target_comments(tag_id);
+        Some(tag)
+    }
+
+    // Tags aprovadas a partir da sugestão; as anteriores ao vínculo suggestion_id são achadas pelo rótulo
+    fn approved_tags_of(&self, suggestion: &TagSuggestion) -> Vec<String> {
+        let linked: Vec<String> = self.approved_tags.values()
+            .filter(|tag| tag.suggestion_id.as_deref() == Some(suggestion.id.as_str()))
+            .map(|tag| tag.id.clone())
+            .collect();
+        if !linked.is_empty() {
+            return linked;
+        }
+        self.approved_tags.values()
+            .filter(|tag| tag.suggestion_id.is_none() && tag.image_id == suggestion.image_id && label_key(&tag.tag) == label_key(&suggestion.tag))
+            .map(|tag| tag.id.clone())
+            .collect()
+    }
+
+    // Aplica a transição pedida; aprovar de novo uma sugestão aprovada não cria outra tag
+    pub fn review_tag_suggestion(&mut self, suggestion_id: &str, req: &ReviewTagRequest) -> Result<ReviewOutcome, ReviewError> {
+        let suggestion = self.tag_suggestions.get(suggestion_id).cloned().ok_or(ReviewError::SuggestionNotFound)?;
+        let (from, to) = (suggestion.status, req.status);
+        if from == to && matches!(to, SuggestionStatus::Approved | SuggestionStatus::Rejected | SuggestionStatus::Reopened) {
+            return Ok(ReviewOutcome::unchanged(suggestion));
+        }
+        if !from.can_transition_to(to) {
+            return Err(ReviewError::IllegalTransition { from, to });
+        }
+        let reject_reason = self.check_reject_reason(&suggestion.image_id, to, req.reason.as_deref())?;
+        let note = req.note.as_deref().map(str::trim).filter(|note| !note.is_empty()).map(str::to_string);
+        if note.as_ref().is_some_and(|note| note.chars().count() > MAX_REVIEW_NOTE_LENGTH) {
+            return Err(ReviewError::Invalid(format!("Review note must have at most {} characters", MAX_REVIEW_NOTE_LENGTH)));
+        }
+
+        // Toda aprovação (inclusive os votos de um quórum ainda incompleto) passa pelas mesmas verificações
+        let mut attributes = suggestion.attributes.clone();
+        let mut superseded_tags = Vec::new();
+        if to == SuggestionStatus::Approved {
+            // Os atributos enviados pelo revisor substituem os já aceitos no quórum, que substituem os da sugestão
+            let values = req.attributes.as_ref()
+                .or(suggestion.quorum.as_ref().map(|state| &state.attributes).filter(|values| !values.is_empty()))
+                .unwrap_or(&suggestion.attributes);
+            attributes = self.tag_attributes(&suggestion.image_id, &suggestion.tag, values, true)
+                .map_err(|e| ReviewError::Invalid(e.to_string()))?;
+
+            // Grupo exclusivo: outra classe aprovada na imagem é conflito, a menos que seja substituída
+            let conflicts: Vec<(String, String)> = self.exclusive_conflicts(&suggestion.image_id, &suggestion.tag)
+                .into_iter()
+                .map(|tag| (tag.id.clone(), tag.tag.clone()))
+                .collect();
+            if !conflicts.is_empty() && self.labeling_policy_for_image(&suggestion.image_id).on_conflict == ConflictResolution::Reject && !req.supersede {
+                return Err(ReviewError::ExclusiveConflict(conflicts));
+            }
+            superseded_tags = conflicts.into_iter().map(|(id, _)| id).collect();
+        }
+
+        // Quórum: cada aprovação é registrada; a tag só é criada quando a última aprovação exigida chega.
+        // O consenso (revisor "system") vale como um voto e não precisa estar na lista de revisores
+        let quorum = self.review_quorum_for_image(&suggestion.image_id);
+        let mut quorum_state = suggestion.quorum.clone();
+        if quorum.is_active() {
+            let consensus_vote = to == SuggestionStatus::Approved && req.reviewed_by == SYSTEM_REVIEWER;
+            let listed = quorum.reviewers.is_empty() || quorum.reviewers.contains(&req.reviewed_by);
+            if !listed && !consensus_vote {
+                return Err(ReviewError::Forbidden("Only the group's reviewers can review this suggestion"));
+            }
+            if to == SuggestionStatus::Approved {
+                if req.reviewed_by == suggestion.suggested_by {
+                    return Err(ReviewError::Forbidden("Reviewers cannot approve their own suggestion"));
+                }
+                let mut state = quorum_state.take().unwrap_or(QuorumState {
+                    required: quorum.approvals_required,
+                    approvals: Vec::new(),
+                    attributes: AttributeMap::new(),
+                });
+                if state.approvals.iter().any(|approval| approval.reviewer == req.reviewed_by) {
+                    return Ok(ReviewOutcome::unchanged(suggestion));
+                }
+                state.required = quorum.approvals_required;
+                state.approvals.push(ReviewApproval { reviewer: req.reviewed_by.clone(), approved_at: Utc::now().to_rfc3339() });
+                state.attributes = attributes.clone();
+                if state.approvals.len() < state.required {
+                    let suggestion = self.tag_suggestions.get_mut(suggestion_id).ok_or(ReviewError::SuggestionNotFound)?;
+                    suggestion.quorum = Some(state);
+                    return Ok(ReviewOutcome {
+                        changed: true,
+                        ..ReviewOutcome::unchanged(suggestion.clone())
+                    });
+                }
+                quorum_state = Some(state);
+            }
+        }
+        if to == SuggestionStatus::Reopened {
+            quorum_state = None;
+        }
+        self.supersede_approved_tags(&superseded_tags);
+
+        let mut removed_tags = Vec::new();
+        if from == SuggestionStatus::Approved {
+            for tag_id in self.approved_tags_of(&suggestion) {
+                if self.remove_approved_tag(&tag_id).is_some() {
+                    removed_tags.push(tag_id);
+                }
+            }
+        }
+
+        let mut approved_tag_id = None;
+        if to == SuggestionStatus::Approved {
+            let mut approved_tag = ApprovedTag::new(suggestion.image_id.clone(), suggestion.tag.clone(), req.reviewed_by.clone());
+            approved_tag.attributes = attributes.clone();
+            approved_tag.suggestion_id = Some(suggestion.id.clone());
+            approved_tag_id = Some(approved_tag.id.clone());
+            self.approved_tags.insert(approved_tag.id.clone(), approved_tag);
+        }
+
+        if from == SuggestionStatus::Approved {
+            self.cascade_to_duplicates(suggestion_id, to, Some(&req.reviewed_by), reject_reason.as_deref());
+        }
+
+        let suggestion = self.tag_suggestions.get_mut(suggestion_id).ok_or(ReviewError::SuggestionNotFound)?;
+        suggestion.status = to;
+        suggestion.reviewed_by = Some(req.reviewed_by.clone());
+        suggestion.reviewed_at = Some(Utc::now().to_rfc3339());
+        suggestion.attributes = attributes;
+        suggestion.reject_reason = reject_reason;
+        suggestion.review_note = note;
+        suggestion.quorum = quorum_state;
+        suggestion.needs_review = false;
+        suggestion.approved_with = None; // revisada à parte, deixa de acompanhar a principal
+        Ok(ReviewOutcome {
+            suggestion: suggestion.clone(),
+            changed: true,
+            approved_tag_id,
+            removed_tags,
+            superseded_tags,
+        })
+    }
+
+    // Remover uma tag aprovada desfaz a aprovação: a sugestão de origem volta para revisão
+    pub fn delete_approved_tag(&mut self, tag_id: &str, requested_by: &str) -> Option<ApprovedTag> {
+        let tag = self.remove_approved_tag(tag_id)?;
+        if let Some(suggestion_id) = &tag.suggestion_id {
+            self.cascade_to_duplicates(suggestion_id, SuggestionStatus::Reopened, Some(requested_by), None);
+        }
+        if let Some(suggestion) = tag.suggestion_id.as_ref().and_then(|id| self.tag_suggestions.get_mut(id)) {
+            if suggestion.status == SuggestionStatus::Approved {
+                suggestion.status = SuggestionStatus::Reopened;
+                suggestion.reviewed_by = Some(requested_by.to_string());
+                suggestion.reviewed_at = Some(Utc::now().to_rfc3339());
+                suggestion.review_note = None;
+                suggestion.quorum = None;
+            }
+        }
+        Some(tag)
+    }
+
+    fn review_quorum_for_image(&self, image_id: &str) -> ReviewQuorum {
+        self.images.get(image_id)
+            .and_then(|image| self.groups.get(&image.group_id))
+            .map(|group| group.review_quorum.clone())
+            .unwrap_or_default()
+    }
+
+    // Revisores precisam ser membros do grupo e em número suficiente para o quórum
+    pub fn set_review_quorum(&mut self, group_id: &str, quorum: &ReviewQuorum) -> Result<ReviewQuorum, ReviewError> {
+        let group = self.groups.get_mut(group_id).ok_or(ReviewError::GroupNotFound)?;
+        if quorum.approvals_required == 0 {
+            return Err(ReviewError::Invalid("approvals_required must be at least 1".to_string()));
+        }
+        let mut reviewers: Vec<String> = Vec::new();
+        for reviewer in quorum.reviewers.iter().map(|reviewer| reviewer.trim()).filter(|reviewer| !reviewer.is_empty()) {
+            if !group.members.iter().any(|member| member == reviewer) {
+                return Err(ReviewError::Invalid(format!("Reviewer '{}' is not a member of the group", reviewer)));
+            }
+            if !reviewers.iter().any(|existing| existing == reviewer) {
+                reviewers.push(reviewer.to_string());
+            }
+        }
+        if !reviewers.is_empty() && quorum.approvals_required > reviewers.len() {
+            return Err(ReviewError::Invalid(format!(
+                "approvals_required ({}) exceeds the number of reviewers ({})", quorum.approvals_required, reviewers.len()
+            )));
+        }
+        group.review_quorum = ReviewQuorum { approvals_required: quorum.approvals_required, reviewers };
+        Ok(group.review_quorum.clone())
+    }
+
+    // Rejeições exigem um motivo da lista do grupo; os demais estados não aceitam motivo
+    fn check_reject_reason(&self, image_id: &str, to: SuggestionStatus, reason: Option<&str>) -> Result<Option<String>, ReviewError> {
+        let reason = reason.map(normalize_reason).filter(|reason| !reason.is_empty());
+        if to != SuggestionStatus::Rejected {
+            return match reason {
+                Some(_) => Err(ReviewError::Invalid("A reason can only be given when rejecting".to_string())),
+                None => Ok(None),
+            };
+        }
+        let allowed: Vec<String> = self.images.get(image_id)
+            .and_then(|image| self.groups.get(&image.group_id))
+            .map(|group| group.reject_reasons.clone())
+            .unwrap_or_else(|| DEFAULT_REJECT_REASONS.iter().map(|reason| reason.to_string()).collect());
+        match reason {
+            Some(reason) if allowed.contains(&reason) => Ok(Some(reason)),
+            Some(reason) => Err(ReviewError::Invalid(format!("Unknown reject reason '{}'; allowed: {}", reason, allowed.join(", ")))),
+            None => Err(ReviewError::Invalid(format!("A reason is required to reject; allowed: {}", allowed.join(", ")))),
+        }
+    }
+
+    pub fn set_reject_reasons(&mut self, group_id: &str, reasons: &[String]) -> Result<Vec<String>, ReviewError> {
+        let reasons = validate_reject_reasons(reasons).map_err(ReviewError::Invalid)?;
+        let group = self.groups.get_mut(group_id).ok_or(ReviewError::GroupNotFound)?;
+        group.reject_reasons = reasons.clone();
+        Ok(reasons)
+    }
+
+    // Sugestões do usuário, da mais recente para a mais antiga
+    pub fn user_suggestions(&self, username: &str, status: Option<SuggestionStatus>) -> Vec<&TagSuggestion> {
+        let mut suggestions: Vec<&TagSuggestion> = self.tag_suggestions.values()
+            .filter(|suggestion| suggestion.suggested_by == username)
+            .filter(|suggestion| status.is_none_or(|status| suggestion.status == status))
+            .collect();
+        suggestions.sort_by(|a, b| b.suggested_at.cmp(&a.suggested_at).then_with(|| a.id.cmp(&b.id)));
+        suggestions
+    }
+
+    // Rejeições antigas, sem motivo registrado, entram como "unspecified"
+    pub fn reject_stats(&self, group_id: &str) -> Option<RejectStatsReport> {
+        self.groups.get(group_id)?;
+        let mut reasons: BTreeMap<String, usize> = BTreeMap::new();
+        let mut annotators: BTreeMap<&str, AnnotatorRejectStats> = BTreeMap::new();
+        let suggestions = self.tag_suggestions.values()
+            .filter(|suggestion| self.images.get(&suggestion.image_id).is_some_and(|image| image.group_id == group_id));
+        for suggestion in suggestions {
+            let stats = annotators.entry(suggestion.suggested_by.as_str()).or_insert_with(|| AnnotatorRejectStats {
+                annotator: suggestion.suggested_by.clone(),
+                suggestions: 0,
+                approved: 0,
+                rejected: 0,
+                rejection_rate: 0.0,
+                reasons: BTreeMap::new(),
+            });
+            stats.suggestions += 1;
+            match suggestion.status {
+                SuggestionStatus::Approved => stats.approved += 1,
+                SuggestionStatus::Rejected => {
+                    stats.rejected += 1;
+                    let reason = suggestion.reject_reason.clone().unwrap_or_else(|| "unspecified".to_string());
+                    *stats.reasons.entry(reason.clone()).or_default() += 1;
+                    *reasons.entry(reason).or_default() += 1;
+                }
+                _ => {}
+            }
+        }
+
+        let mut annotators: Vec<AnnotatorRejectStats> = annotators.into_values()
+            .map(|mut stats| {
+                let reviewed = stats.approved + stats.rejected;
+                if reviewed > 0 {
+                    stats.rejection_rate = stats.rejected as f64 / reviewed as f64;
+                }
+                stats
+            })
+            .collect();
+        annotators.sort_by(|a, b| b.rejected.cmp(&a.rejected).then_with(|| a.annotator.cmp(&b.annotator)));
+        Some(RejectStatsReport { group_id: group_id.to_string(), reasons, annotators })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Group, Image};
+    use SuggestionStatus::*;
+
+    // Grupo com admin, alice e bob, uma imagem e uma sugestão "cat" de alice
+    fn fixture(quorum: ReviewQuorum) -> (DataService, String) {
+        let mut data = DataService::new();
+        let mut group = Group::new("Pets".to_string(), String::new(), "admin".to_string());
+        group.members.extend(["alice".to_string(), "bob".to_string()]);
+        group.review_quorum = quorum;
+        let image = Image::new("a.png".to_string(), "a.png".to_string(), group.id.clone(), "alice".to_string());
+        let suggestion = TagSuggestion::new(image.id.clone(), "cat".to_string(), "alice".to_string());
+        let suggestion_id = suggestion.id.clone();
+        data.groups.insert(group.id.clone(), group);
+        data.images.insert(image.id.clone(), image);
+        data.tag_suggestions.insert(suggestion_id.clone(), suggestion);
+        (data, suggestion_id)
+    }
+
+    fn approve(data: &mut DataService, suggestion_id: &str, reviewer: &str) -> Result<ReviewOutcome, ReviewError> {
+        let req = ReviewTagRequest {
+            status: Approved,
+            reviewed_by: reviewer.to_string(),
+            attributes: None,
+            supersede: false,
+            reason: None,
+            note: None,
+        };
+        data.review_tag_suggestion(suggestion_id, &req)
+    }
+
+    fn quorum(approvals_required: usize, reviewers: &[&str]) -> ReviewQuorum {
+        ReviewQuorum { approvals_required, reviewers: reviewers.iter().map(|reviewer| reviewer.to_string()).collect() }
+    }
+
+    const ALL: [SuggestionStatus; 5] = [Pending, Approved, Rejected, Reopened, Superseded];
+
+    #[test]
+    fn open_suggestions_can_be_approved_or_rejected() {
+        for from in [Pending, Reopened] {
+            assert!(from.can_transition_to(Approved), "{} → approved", from);
+            assert!(from.can_transition_to(Rejected), "{} → rejected", from);
+            assert!(!from.can_transition_to(Superseded), "{} → superseded", from);
+        }
+        assert!(!Pending.can_transition_to(Reopened));
+    }
+
+    #[test]
+    fn reviewed_suggestions_only_reopen_or_flip() {
+        assert!(Approved.can_transition_to(Reopened));
+        assert!(Approved.can_transition_to(Rejected));
+        assert!(Rejected.can_transition_to(Reopened));
+        assert!(!Rejected.can_transition_to(Approved));
+        assert!(Superseded.can_transition_to(Reopened));
+        assert!(!Superseded.can_transition_to(Approved));
+        assert!(!Superseded.can_transition_to(Rejected));
+    }
+
+    #[test]
+    fn nothing_returns_to_pending_or_stays_in_place() {
+        for from in ALL {
+            assert!(!from.can_transition_to(Pending), "{} → pending", from);
+            assert!(!from.can_transition_to(from), "{} → {}", from, from);
+        }
+    }
+
+    #[test]
+    fn only_pending_and_reopened_are_open() {
+        let open: Vec<SuggestionStatus> = ALL.into_iter().filter(|status| status.is_open()).collect();
+        assert_eq!(open, vec![Pending, Reopened]);
+        // Toda sugestão aberta tem uma saída de revisão
+        for from in open {
+            assert!(ALL.iter().any(|&next| from.can_transition_to(next)));
+        }
+    }
+
+    #[test]
+    fn suggester_can_approve_without_quorum() {
+        let (mut data, id) = fixture(ReviewQuorum::default());
+        let outcome = approve(&mut data, &id, "alice").unwrap();
+        assert!(outcome.approved_tag_id.is_some());
+        assert_eq!(data.tag_suggestions[&id].status, Approved);
+    }
+
+    #[test]
+    fn quorum_blocks_self_approval() {
+        let (mut data, id) = fixture(quorum(2, &[]));
+        assert!(matches!(approve(&mut data, &id, "alice"), Err(ReviewError::Forbidden(_))));
+        assert!(data.tag_suggestions[&id].quorum.is_none());
+    }
+
+    #[test]
+    fn quorum_creates_the_tag_on_the_last_distinct_approval() {
+        let (mut data, id) = fixture(quorum(2, &[]));
+        let first = approve(&mut data, &id, "admin").unwrap();
+        assert!(first.changed && first.approved_tag_id.is_none());
+        assert_eq!(data.tag_suggestions[&id].status, Pending);
+
+        let repeated = approve(&mut data, &id, "admin").unwrap();
+        assert!(!repeated.changed);
+        assert_eq!(data.tag_suggestions[&id].quorum.as_ref().unwrap().approvals.len(), 1);
+
+        let second = approve(&mut data, &id, "bob").unwrap();
+        assert!(second.approved_tag_id.is_some());
+        assert_eq!(data.tag_suggestions[&id].status, Approved);
+        assert_eq!(data.approved_tags.len(), 1);
+    }
+
+    #[test]
+    fn quorum_reviewer_list_admits_consensus_vote() {
+        let (mut data, id) = fixture(quorum(2, &["admin", "bob"]));
+        let err = approve(&mut data, &id, "carol").unwrap_err();
+        assert!(matches!(err, ReviewError::Forbidden(_)));
+
+        approve(&mut data, &id, SYSTEM_REVIEWER).unwrap();
+        let outcome = approve(&mut data, &id, "bob").unwrap();
+        assert!(outcome.approved_tag_id.is_some());
+        let reviewers: Vec<String> = outcome.suggestion.quorum.unwrap().approvals.into_iter().map(|approval| approval.reviewer).collect();
+        assert_eq!(reviewers, vec![SYSTEM_REVIEWER.to_string(), "bob".to_string()]);
+    }
+}
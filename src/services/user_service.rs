@@ -41,4 +41,10 @@ impl UserService {
     pub fn get_user(&self, username: &str) -> Option<&User> {
         self.users.get(username)
     }
+
+    pub fn is_admin(&self, username: &str) -> bool {
+        self.get_user(username)
+            .map(|user| user.role == "admin")
+            .unwrap_or(false)
+    }
 }
@@ -0,0 +1,259 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use chrono::Utc;
+
+use crate::models::{label_key, validate_attributes, AttributeMap, LabelDefinition, LabelProposal, LabelProposalQuery, LabelStats, LabelVocabulary, TagSuggestion, UnknownLabelMode};
+use super::DataService;
+
+#[derive(Debug)]
+pub enum VocabularyError {
+    GroupNotFound,
+    ProposalNotFound,
+    AliasNotFound,
+    NotInVocabulary { label: String, allowed: Vec<String> },
+    Invalid(String),
+}
+
+impl fmt::Display for VocabularyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VocabularyError::GroupNotFound => write!(f, "Group not found"),
+            VocabularyError::ProposalNotFound => write!(f, "Label proposal not found"),
+            VocabularyError::AliasNotFound => write!(f, "Tag alias not found"),
+            VocabularyError::NotInVocabulary { label, .. } => write!(f, "Label '{}' is not in the group's vocabulary", label),
+            VocabularyError::Invalid(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+// Resultado do envio de um rótulo para uma imagem
+pub enum LabelOutcome {
+    Suggested(TagSuggestion),
+    Proposed(LabelProposal),
+}
+
+impl DataService {
+    pub fn vocabulary_for_image(&self, image_id: &str) -> Option<(&str, &LabelVocabulary)> {
+        let image = self.images.get(image_id)?;
+        let group = self.groups.get(&image.group_id)?;
+        group.vocabulary.as_ref().map(|vocabulary| (group.id.as_str(), vocabulary))
+    }
+
+    // Valida os atributos de uma tag da imagem contra as definições do rótulo no vocabulário do grupo
+    pub fn tag_attributes(&self, image_id: &str, label: &str, values: &AttributeMap, complete: bool) -> Result<AttributeMap, VocabularyError> {
+        let definitions = self.vocabulary_for_image(image_id)
+            .and_then(|(_, vocabulary)| vocabulary.find(label))
+            .map(|definition| definition.attributes.as_slice())
+            .unwrap_or_default();
+        validate_attributes(label, definitions, values, complete).map_err(VocabularyError::Invalid)
+    }
+
+    // Normaliza a tag (regras do grupo e aliases) e cria a sugestão com o nome canônico do vocabulário,
+    // ou uma proposta de rótulo quando o grupo aceita propostas
+    pub fn submit_tag_suggestion(
+        &mut self,
+        image_id: &str,
+        label: &str,
+        attributes: &AttributeMap,
+        suggested_by: &str,
+    ) -> Result<LabelOutcome, VocabularyError> {
+        let group_id = self.images.get(image_id).map(|image| image.group_id.clone());
+        let label = self.normalize_tag(group_id.as_deref(), label);
+        let label = label.as_str();
+        if label.is_empty() {
+            return Err(VocabularyError::Invalid("Tag cannot be empty".to_string()));
+        }
+
+        let canonical = match self.vocabulary_for_image(image_id) {
+            None => label.to_string(),
+            Some((group_id, vocabulary)) => match (vocabulary.find(label), vocabulary.unknown_labels) {
+                (Some(definition), _) => definition.name.clone(),
+                (None, UnknownLabelMode::Reject) => {
+                    return Err(VocabularyError::NotInVocabulary {
+                        label: label.to_string(),
+                        allowed: vocabulary.labels.iter().map(|definition| definition.name.clone()).collect(),
+                    });
+                }
+                (None, UnknownLabelMode::Propose) => {
+                    // Rótulos propostos ainda não têm atributos definidos
+                    self.tag_attributes(image_id, label, attributes, false)?;
+                    let group_id = group_id.to_string();
+                    let existing = self.label_proposals.values().find(|proposal| {
+                        proposal.status == "pending"
+                            && proposal.group_id == group_id
+                            && proposal.image_id == image_id
+                            && label_key(&proposal.label) == label_key(label)
+                    });
+                    if let Some(existing) = existing {
+                        return Ok(LabelOutcome::Proposed(existing.clone()));
+                    }
+                    let proposal = LabelProposal::new(group_id, label.to_string(), image_id.to_string(), suggested_by.to_string());
+                    self.label_proposals.insert(proposal.id.clone(), proposal.clone());
+                    return Ok(LabelOutcome::Proposed(proposal));
+                }
+            },
+        };
+
+        let attributes = self.tag_attributes(image_id, &canonical, attributes, false)?;
+        let mut suggestion = TagSuggestion::new(image_id.to_string(), canonical, suggested_by.to_string());
+        suggestion.attributes = attributes;
+        let checkpoint = self.history_checkpoint([image_id]);
+        self.tag_suggestions.insert(suggestion.id.clone(), suggestion.clone());
+        self.record_history(checkpoint, suggested_by);
+
+        // A nova sugestão pode completar o consenso e já sair aprovada
+        if self.apply_suggestion_consensus(image_id, &suggestion.tag).is_some() {
+            if let Some(updated) = self.tag_suggestions.get(&suggestion.id) {
+                suggestion = updated.clone();
+            }
+        }
+        Ok(LabelOutcome::Suggested(suggestion))
+    }
+
+    // Ancestrais implícitos de um rótulo no vocabulário do grupo, em minúsculas
+    pub fn implied_labels(&self, group_id: &str, label: &str) -> Vec<String> {
+        self.groups.get(group_id)
+            .and_then(|group| group.vocabulary.as_ref())
+            .map(|vocabulary| vocabulary.ancestors(label).into_iter().map(str::to_lowercase).collect())
+            .unwrap_or_default()
+    }
+
+    // Imagens por rótulo: diretas (tag aprovada) e totais (incluindo descendentes)
+    pub fn label_stats(&self, group_id: &str) -> Result<Vec<LabelStats>, VocabularyError> {
+        let group = self.groups.get(group_id).ok_or(VocabularyError::GroupNotFound)?;
+        let vocabulary = group.vocabulary.as_ref();
+
+        let mut direct: HashMap<String, HashSet<&str>> = HashMap::new();
+        let mut total: HashMap<String, HashSet<&str>> = HashMap::new();
+        let mut names: HashMap<String, String> = HashMap::new();
+        for tag in self.approved_tags.values() {
+            if self.images.get(&tag.image_id).is_none_or(|image| image.group_id != group_id) {
+                continue;
+            }
+            let name = vocabulary
+                .and_then(|vocabulary| vocabulary.find(&tag.tag))
+                .map(|definition| definition.name.clone())
+                .unwrap_or_else(|| tag.tag.trim().to_string());
+            let key = name.to_lowercase();
+            names.entry(key.clone()).or_insert(name);
+            direct.entry(key.clone()).or_default().insert(tag.image_id.as_str());
+            total.entry(key).or_default().insert(tag.image_id.as_str());
+            for ancestor in self.implied_labels(group_id, &tag.tag) {
+                total.entry(ancestor).or_default().insert(tag.image_id.as_str());
+            }
+        }
+        if let Some(vocabulary) = vocabulary {
+            for definition in &vocabulary.labels {
+                names.insert(definition.name.to_lowercase(), definition.name.clone());
+            }
+        }
+
+        let mut stats: Vec<LabelStats> = names.into_iter()
+            .map(|(key, label)| {
+                let definition = vocabulary.and_then(|vocabulary| vocabulary.find(&label));
+                let mut path: Vec<&str> = vocabulary.map(|vocabulary| vocabulary.ancestors(&label)).unwrap_or_default();
+                path.reverse();
+                path.push(&label);
+                LabelStats {
+                    path: path.join("/"),
+                    parent: definition.and_then(|definition| definition.parent.clone()),
+                    depth: vocabulary.and_then(|vocabulary| vocabulary.depth(&label)),
+                    in_vocabulary: definition.is_some(),
+                    direct_images: direct.get(&key).map_or(0, HashSet::len),
+                    total_images: total.get(&key).map_or(0, HashSet::len),
+                    label,
+                }
+            })
+            .collect();
+        stats.sort_by(|a, b| b.in_vocabulary.cmp(&a.in_vocabulary).then_with(|| a.path.to_lowercase().cmp(&b.path.to_lowercase())));
+        Ok(stats)
+    }
+
+    pub fn set_vocabulary(&mut self, group_id: &str, vocabulary: Option<LabelVocabulary>) -> Result<(), VocabularyError> {
+        if let Some(vocabulary) = &vocabulary {
+            vocabulary.validate().map_err(VocabularyError::Invalid)?;
+        }
+        let group = self.groups.get_mut(group_id).ok_or(VocabularyError::GroupNotFound)?;
+        group.vocabulary = vocabulary;
+        Ok(())
+    }
+
+    pub fn list_label_proposals(&self, query: &LabelProposalQuery) -> Vec<&LabelProposal> {
+        let mut proposals: Vec<&LabelProposal> = self.label_proposals.values()
+            .filter(|proposal| query.group_id.as_ref().is_none_or(|group_id| &proposal.group_id == group_id))
+            .filter(|proposal| query.status.as_ref().is_none_or(|status| &proposal.status == status))
+            .collect();
+        proposals.sort_by(|a, b| a.proposed_at.cmp(&b.proposed_at).then_with(|| a.id.cmp(&b.id)));
+        proposals
+    }
+
+    // A decisão vale para todas as propostas pendentes do mesmo rótulo no grupo.
+    // Ao aceitar, o rótulo entra no vocabulário e cada imagem recebe a sugestão original.
+    pub fn review_label_proposal(
+        &mut self,
+        proposal_id: &str,
+        status: &str,
+        reviewed_by: &str,
+        description: &str,
+        parent: Option<&str>,
+    ) -> Result<(Vec<String>, Vec<String>), VocabularyError> {
+        if status != "accepted" && status != "rejected" {
+            return Err(VocabularyError::Invalid(format!("Invalid review status '{}'", status)));
+        }
+        let proposal = self.label_proposals.get(proposal_id).ok_or(VocabularyError::ProposalNotFound)?;
+        if proposal.status != "pending" {
+            return Err(VocabularyError::Invalid(format!("Label proposal was already {}", proposal.status)));
+        }
+        let group_id = proposal.group_id.clone();
+        let label = proposal.label.trim().to_string();
+
+        let related: Vec<String> = self.label_proposals.values()
+            .filter(|other| other.status == "pending" && other.group_id == group_id && label_key(&other.label) == label_key(&label))
+            .map(|other| other.id.clone())
+            .collect();
+
+        let mut canonical = label.clone();
+        if status == "accepted" {
+            if let Some(vocabulary) = self.groups.get_mut(&group_id).and_then(|group| group.vocabulary.as_mut()) {
+                match vocabulary.find(&label) {
+                    Some(definition) => canonical = definition.name.clone(),
+                    None => {
+                        let parent = match parent.map(str::trim).filter(|parent| !parent.is_empty()) {
+                            Some(parent) => Some(vocabulary.find(parent)
+                                .map(|definition| definition.name.clone())
+                                .ok_or_else(|| VocabularyError::Invalid(format!("Parent '{}' is not in the vocabulary", parent)))?),
+                            None => None,
+                        };
+                        vocabulary.labels.push(LabelDefinition {
+                            name: label.clone(),
+                            description: description.to_string(),
+                            parent,
+                            attributes: Vec::new(),
+                        });
+                    }
+                }
+            }
+        }
+
+        let now = Utc::now().to_rfc3339();
+        let mut suggestion_ids = Vec::new();
+        for id in &related {
+            let Some(proposal) = self.label_proposals.get_mut(id) else {
+                continue;
+            };
+            proposal.status = status.to_string();
+            proposal.reviewed_by = Some(reviewed_by.to_string());
+            proposal.reviewed_at = Some(now.clone());
+            let (image_id, proposed_by) = (proposal.image_id.clone(), proposal.proposed_by.clone());
+
+            if status == "accepted" && self.images.contains_key(&image_id) {
+                let checkpoint = self.history_checkpoint([image_id.as_str()]);
+                let suggestion = TagSuggestion::new(image_id, canonical.clone(), proposed_by);
+                suggestion_ids.push(suggestion.id.clone());
+                self.tag_suggestions.insert(suggestion.id.clone(), suggestion);
+                self.record_history(checkpoint, reviewed_by);
+            }
+        }
+        Ok((related, suggestion_ids))
+    }
+}
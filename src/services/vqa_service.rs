@@ -0,0 +1,285 @@
+use std::collections::HashSet;
+use std::fmt;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::models::{
+    collapse_whitespace, label_key, AdjudicateQuestionRequest, AnswerType, AskQuestionRequest, QuestionListQuery,
+    QuestionTemplate, QuestionView, VqaAnswer, VqaQuestion,
+};
+use super::DataService;
+
+const MAX_ANSWER_LENGTH: usize = 200;
+
+#[derive(Debug)]
+pub enum VqaError {
+    ImageNotFound,
+    GroupNotFound,
+    TemplateNotFound,
+    QuestionNotFound,
+    AnswerNotFound,
+    Invalid(String),
+    Conflict(String),
+}
+
+impl fmt::Display for VqaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VqaError::ImageNotFound => write!(f, "Image not found"),
+            VqaError::GroupNotFound => write!(f, "Group not found"),
+            VqaError::TemplateNotFound => write!(f, "Question template not found in the image's group"),
+            VqaError::QuestionNotFound => write!(f, "Question not found"),
+            VqaError::AnswerNotFound => write!(f, "Answer not found for this question"),
+            VqaError::Invalid(message) | VqaError::Conflict(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl AnswerType {
+    pub fn validate(&self) -> Result<(), String> {
+        if let AnswerType::Choice { options } = self {
+            if options.is_empty() {
+                return Err("Choice questions need at least one option".to_string());
+            }
+            let mut seen = HashSet::new();
+            if let Some(option) = options.iter().find(|option| option.trim().is_empty() || !seen.insert(label_key(option))) {
+                return Err(format!("Empty or duplicate option '{}'", option));
+            }
+        }
+        Ok(())
+    }
+
+    // Tipo de resposta no formato VQA ("yes/no", "number" ou "other")
+    pub fn vqa_name(&self) -> &'static str {
+        match self {
+            AnswerType::YesNo => "yes/no",
+            AnswerType::Number => "number",
+            AnswerType::Text | AnswerType::Choice { .. } => "other",
+        }
+    }
+}
+
+// Normaliza a resposta como no VQA (minúsculas, espaços colapsados, sem ponto final) e confere o tipo
+pub fn normalize_answer(answer_type: &AnswerType, answer: &str) -> Result<String, String> {
+    let answer = collapse_whitespace(answer).to_lowercase();
+    let answer = answer.trim_end_matches('.').trim().to_string();
+    if answer.is_empty() {
+        return Err("Answer cannot be empty".to_string());
+    }
+    if answer.chars().count() > MAX_ANSWER_LENGTH {
+        return Err(format!("Answer must have at most {} characters", MAX_ANSWER_LENGTH));
+    }
+    match answer_type {
+        AnswerType::Text => Ok(answer),
+        AnswerType::YesNo => match answer.as_str() {
+            "yes" | "y" | "true" | "sim" => Ok("yes".to_string()),
+            "no" | "n" | "false" | "não" | "nao" => Ok("no".to_string()),
+            _ => Err("Answer must be 'yes' or 'no'".to_string()),
+        },
+        AnswerType::Number => answer.replace(',', ".").parse::<f64>().ok()
+            .filter(|number| number.is_finite())
+            .map(|number| number.to_string())
+            .ok_or_else(|| "Answer must be a number".to_string()),
+        AnswerType::Choice { options } => options.iter()
+            .find(|option| label_key(option) == label_key(&answer))
+            .map(|option| option.to_lowercase())
+            .ok_or_else(|| format!("Answer must be one of: {}", options.join(", "))),
+    }
+}
+
+pub fn validate_templates(templates: &mut [QuestionTemplate]) -> Result<(), String> {
+    let mut ids = HashSet::new();
+    let mut questions = HashSet::new();
+    for template in templates.iter_mut() {
+        template.question = collapse_whitespace(&template.question);
+        if template.question.is_empty() {
+            return Err("Template questions cannot be empty".to_string());
+        }
+        if !questions.insert(label_key(&template.question)) {
+            return Err(format!("Duplicate template question '{}'", template.question));
+        }
+        template.answer_type.validate()?;
+        if template.id.trim().is_empty() {
+            template.id = Uuid::new_v4().to_string();
+        }
+        if !ids.insert(template.id.clone()) {
+            return Err(format!("Duplicate template id '{}'", template.id));
+        }
+    }
+    Ok(())
+}
+
+impl DataService {
+    pub fn set_question_templates(&mut self, group_id: &str, mut templates: Vec<QuestionTemplate>) -> Result<Vec<QuestionTemplate>, VqaError> {
+        validate_templates(&mut templates).map_err(VqaError::Invalid)?;
+        let group = self.groups.get_mut(group_id).ok_or(VqaError::GroupNotFound)?;
+        group.question_templates = templates.clone();
+        Ok(templates)
+    }
+
+    // Cria a pergunta, ou devolve a já existente com o mesmo texto na imagem
+    pub fn ask_question(&mut self, image_id: &str, req: &AskQuestionRequest) -> Result<VqaQuestion, VqaError> {
+        let image = self.images.get(image_id).ok_or(VqaError::ImageNotFound)?;
+        let group = self.groups.get(&image.group_id).ok_or(VqaError::GroupNotFound)?;
+        let (template_id, question, answer_type) = match (&req.template_id, &req.question) {
+            (Some(template_id), None) => {
+                let template = group.question_templates.iter()
+                    .find(|template| &template.id == template_id)
+                    .ok_or(VqaError::TemplateNotFound)?;
+                (Some(template.id.clone()), template.question.clone(), template.answer_type.clone())
+            }
+            (None, Some(question)) => {
+                let question = collapse_whitespace(question);
+                if question.is_empty() {
+                    return Err(VqaError::Invalid("Question cannot be empty".to_string()));
+                }
+                let answer_type = req.answer_type.clone().unwrap_or_default();
+                answer_type.validate().map_err(VqaError::Invalid)?;
+                (None, question, answer_type)
+            }
+            _ => return Err(VqaError::Invalid("Provide exactly one of template_id or question".to_string())),
+        };
+
+        let existing = self.vqa_questions.values()
+            .find(|existing| existing.image_id == image_id && label_key(&existing.question) == label_key(&question));
+        if let Some(existing) = existing {
+            return Ok(existing.clone());
+        }
+        let question = VqaQuestion::new(image_id.to_string(), template_id, question, answer_type, req.asked_by.clone());
+        self.vqa_questions.insert(question.id.clone(), question.clone());
+        Ok(question)
+    }
+
+    // Registra ou substitui a resposta do anotador
+    pub fn answer_question(&mut self, question_id: &str, answer: &str, answered_by: &str) -> Result<VqaAnswer, VqaError> {
+        let question = self.vqa_questions.get(question_id).ok_or(VqaError::QuestionNotFound)?;
+        if question.status == "resolved" {
+            return Err(VqaError::Conflict("Question was already adjudicated".to_string()));
+        }
+        let answer = normalize_answer(&question.answer_type, answer).map_err(VqaError::Invalid)?;
+
+        let existing = self.vqa_answers.values_mut()
+            .find(|existing| existing.question_id == question_id && existing.answered_by == answered_by);
+        if let Some(existing) = existing {
+            existing.answer = answer;
+            existing.answered_at = Utc::now().to_rfc3339();
+            return Ok(existing.clone());
+        }
+        let answer = VqaAnswer::new(question_id.to_string(), answer, answered_by.to_string());
+        self.vqa_answers.insert(answer.id.clone(), answer.clone());
+        Ok(answer)
+    }
+
+    // Define a resposta final; respostas iguais a ela são aceitas e as demais rejeitadas
+    pub fn adjudicate_question(&mut self, question_id: &str, req: &AdjudicateQuestionRequest) -> Result<VqaQuestion, VqaError> {
+        let question = self.vqa_questions.get(question_id).ok_or(VqaError::QuestionNotFound)?;
+        let final_answer = match (&req.answer_id, &req.answer) {
+            (Some(answer_id), None) => self.vqa_answers.get(answer_id)
+                .filter(|answer| answer.question_id == question_id)
+                .map(|answer| answer.answer.clone())
+                .ok_or(VqaError::AnswerNotFound)?,
+            (None, Some(answer)) => normalize_answer(&question.answer_type, answer).map_err(VqaError::Invalid)?,
+            _ => return Err(VqaError::Invalid("Provide exactly one of answer_id or answer".to_string())),
+        };
+
+        for answer in self.vqa_answers.values_mut().filter(|answer| answer.question_id == question_id) {
+            answer.status = if answer.answer == final_answer { "accepted" } else { "rejected" }.to_string();
+        }
+        let question = self.vqa_questions.get_mut(question_id).ok_or(VqaError::QuestionNotFound)?;
+        question.status = "resolved".to_string();
+        question.final_answer = Some(final_answer);
+        question.adjudicated_by = Some(req.adjudicated_by.clone());
+        question.adjudicated_at = Some(Utc::now().to_rfc3339());
+        Ok(question.clone())
+    }
+
+    pub fn delete_question(&mut self, question_id: &str) -> bool {
+        if self.vqa_questions.remove(question_id).is_none() {
+            return false;
+        }
+        self.vqa_answers.retain(|_, answer| answer.question_id != question_id);
+        true
+    }
+
+    fn question_answers(&self, question_id: &str) -> Vec<&VqaAnswer> {
+        let mut answers: Vec<&VqaAnswer> = self.vqa_answers.values()
+            .filter(|answer| answer.question_id == question_id)
+            .collect();
+        answers.sort_by(|a, b| a.answered_at.cmp(&b.answered_at).then_with(|| a.id.cmp(&b.id)));
+        answers
+    }
+
+    pub fn list_questions(&self, query: &QuestionListQuery) -> Vec<QuestionView<'_>> {
+        let mut questions: Vec<&VqaQuestion> = self.vqa_questions.values()
+            .filter(|question| query.image_id.as_ref().is_none_or(|id| &question.image_id == id))
+            .filter(|question| query.status.as_ref().is_none_or(|status| &question.status == status))
+            .filter(|question| query.group_id.as_ref().is_none_or(|group_id| {
+                self.images.get(&question.image_id).is_some_and(|image| &image.group_id == group_id)
+            }))
+            .collect();
+        questions.sort_by(|a, b| a.asked_at.cmp(&b.asked_at).then_with(|| a.id.cmp(&b.id)));
+        questions.into_iter()
+            .map(|question| QuestionView { question, answers: self.question_answers(&question.id) })
+            .collect()
+    }
+
+    // Exportação no formato do VQA: perguntas e anotações (somente perguntas adjudicadas)
+    pub fn export_vqa(&self, image_ids: Option<&HashSet<String>>) -> serde_json::Value {
+        let mut questions: Vec<&VqaQuestion> = self.vqa_questions.values()
+            .filter(|question| self.images.contains_key(&question.image_id))
+            .filter(|question| image_ids.is_none_or(|ids| ids.contains(&question.image_id)))
+            .collect();
+        questions.sort_by(|a, b| a.image_id.cmp(&b.image_id).then_with(|| a.asked_at.cmp(&b.asked_at)).then_with(|| a.id.cmp(&b.id)));
+
+        let mut image_ids: Vec<&str> = questions.iter().map(|question| question.image_id.as_str()).collect();
+        image_ids.dedup();
+        let images: Vec<serde_json::Value> = image_ids.iter()
+            .filter_map(|id| self.images.get(*id))
+            .map(|image| serde_json::json!({
+                "id": image.id,
+                "file_name": image.filename,
+                "width": image.width,
+                "height": image.height
+            }))
+            .collect();
+
+        let annotations: Vec<serde_json::Value> = questions.iter()
+            .filter_map(|question| {
+                let final_answer = question.final_answer.as_ref()?;
+                let answers: Vec<serde_json::Value> = self.question_answers(&question.id).iter()
+                    .enumerate()
+                    .map(|(index, answer)| serde_json::json!({
+                        "answer_id": index + 1,
+                        "answer": answer.answer,
+                        "answer_confidence": if &answer.answer == final_answer { "yes" } else { "no" }
+                    }))
+                    .collect();
+                Some(serde_json::json!({
+                    "question_id": question.id,
+                    "image_id": question.image_id,
+                    "question_type": question.template_id.as_deref().unwrap_or("free_form"),
+                    "answer_type": question.answer_type.vqa_name(),
+                    "multiple_choice_answer": final_answer,
+                    "answers": answers
+                }))
+            })
+            .collect();
+
+        serde_json::json!({
+            "info": {
+                "description": "VQA annotations",
+                "version": "1.0",
+                "date_created": Utc::now().to_rfc3339()
+            },
+            "task_type": "Open-Ended",
+            "data_type": "images",
+            "images": images,
+            "questions": questions.iter().map(|question| serde_json::json!({
+                "question_id": question.id,
+                "image_id": question.image_id,
+                "question": question.question
+            })).collect::<Vec<_>>(),
+            "annotations": annotations
+        })
+    }
+}